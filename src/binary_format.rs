@@ -0,0 +1,294 @@
+// Propagating I/O errors by hand would obscure the format.
+#![allow(clippy::question_mark_used)]
+
+use std::{
+    cmp::Ordering,
+    collections::HashMap,
+    io::{self, Read, Write},
+};
+
+use crate::{DisjointSet, ElementIndex, FindStrategy};
+
+/// Identifies the binary format of a `DisjointSet`.
+const MAGIC: [u8; 4] = *b"DJST";
+
+/// The current version of the binary format.
+const VERSION: u8 = 1;
+
+/// The number of payload bits in each byte of a varint.
+const VARINT_PAYLOAD_BITS: u32 = 7;
+
+/// The maximal number of bytes of a varint.
+const MAX_VARINT_LEN: usize = 10;
+
+/// The number of checksum bytes ending each record of a journal.
+// Contradicts `unreachable_pub` and `clippy::pub_without_shorthand`.
+#[allow(clippy::pub_with_shorthand, clippy::redundant_pub_crate)]
+pub(crate) const RECORD_CHECKSUM_LEN: usize = 4;
+
+/// The number of bytes buffered before they are passed on to the writer.
+const WRITE_BUFFER_SIZE: usize = 1 << 13;
+
+/// The FNV-1a hash, used as checksum over all bytes preceding it.
+// Contradicts `unreachable_pub` and `clippy::pub_without_shorthand`.
+#[allow(clippy::pub_with_shorthand, clippy::redundant_pub_crate)]
+pub(crate) struct Checksum(u64);
+
+impl Checksum {
+    // `Self` as constructor is not available on old Rust versions.
+    #[allow(clippy::use_self)]
+    pub(crate) const fn new() -> Self {
+        Checksum(0xcbf2_9ce4_8422_2325)
+    }
+
+    pub(crate) fn update(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 ^= u64::from(byte);
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+
+    // `u64::to_le_bytes` is not available on old Rust versions.
+    #[allow(clippy::as_conversions, clippy::cast_possible_truncation)]
+    pub(crate) fn to_bytes(&self) -> [u8; 8] {
+        let mut bytes = [0; 8];
+        for (index, byte) in bytes.iter_mut().enumerate() {
+            *byte = (self.0 >> (8 * index)) as u8;
+        }
+        bytes
+    }
+}
+
+/// Appends `value` to `bytes` as unsigned LEB128 varint.
+// `u8::try_from` is not available on old Rust versions.
+#[allow(clippy::as_conversions, clippy::cast_possible_truncation)]
+// Contradicts `unreachable_pub` and `clippy::pub_without_shorthand`.
+#[allow(clippy::pub_with_shorthand, clippy::redundant_pub_crate)]
+pub(crate) fn push_varint(bytes: &mut Vec<u8>, mut value: usize) {
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= VARINT_PAYLOAD_BITS;
+        if value == 0 {
+            bytes.push(byte);
+            return;
+        }
+        bytes.push(byte | 0x80);
+    }
+}
+
+/// Buffers the bytes written to `W`, keeping track of their checksum.
+struct Encoder<W> {
+    writer: W,
+    buffer: Vec<u8>,
+    checksum: Checksum,
+}
+
+impl<W> Encoder<W>
+where
+    W: Write,
+{
+    fn new(writer: W) -> Self {
+        Self {
+            writer,
+            // A varint may be pushed onto an almost full buffer before it is flushed.
+            buffer: Vec::with_capacity(WRITE_BUFFER_SIZE + MAX_VARINT_LEN),
+            checksum: Checksum::new(),
+        }
+    }
+
+    fn flush_buffer(&mut self) -> io::Result<()> {
+        self.checksum.update(&self.buffer);
+        self.writer.write_all(&self.buffer)?;
+        self.buffer.clear();
+        Ok(())
+    }
+
+    fn flush_if_full(&mut self) -> io::Result<()> {
+        if self.buffer.len() >= WRITE_BUFFER_SIZE {
+            self.flush_buffer()?;
+        }
+        Ok(())
+    }
+
+    fn write_bytes(&mut self, bytes: &[u8]) -> io::Result<()> {
+        self.buffer.extend_from_slice(bytes);
+        self.flush_if_full()
+    }
+
+    fn write_varint(&mut self, value: usize) -> io::Result<()> {
+        push_varint(&mut self.buffer, value);
+        self.flush_if_full()
+    }
+
+    fn finish(mut self) -> io::Result<()> {
+        self.flush_buffer()?;
+        let checksum = self.checksum.to_bytes();
+        self.writer.write_all(&checksum)?;
+        self.writer.flush()
+    }
+}
+
+/// Reads bytes from `R`, keeping track of their checksum.
+// Contradicts `unreachable_pub` and `clippy::pub_without_shorthand`.
+#[allow(clippy::pub_with_shorthand, clippy::redundant_pub_crate)]
+pub(crate) struct Decoder<R> {
+    reader: R,
+    checksum: Checksum,
+    /// The number of bytes read so far.
+    position: u64,
+}
+
+// Contradicts `unreachable_pub` and `clippy::pub_without_shorthand`.
+#[allow(clippy::pub_with_shorthand, clippy::redundant_pub_crate)]
+pub(crate) fn invalid_data(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+impl<R> Decoder<R>
+where
+    R: Read,
+{
+    // Trait bounds in `const fn` are not available on old Rust versions.
+    #[allow(clippy::missing_const_for_fn)]
+    pub(crate) fn new(reader: R) -> Self {
+        Self {
+            reader,
+            checksum: Checksum::new(),
+            position: 0,
+        }
+    }
+
+    /// Returns the number of bytes read so far.
+    pub(crate) const fn position(&self) -> u64 {
+        self.position
+    }
+
+    // `u64::try_from` is not available on old Rust versions, and `usize` has at most 64 bits.
+    #[allow(clippy::as_conversions)]
+    pub(crate) fn read_bytes(&mut self, bytes: &mut [u8]) -> io::Result<()> {
+        self.reader.read_exact(bytes)?;
+        self.checksum.update(bytes);
+        self.position += bytes.len() as u64;
+        Ok(())
+    }
+
+    /// Reads an unsigned LEB128 varint, failing if it does not fit into a `usize`.
+    pub(crate) fn read_varint(&mut self) -> io::Result<usize> {
+        let mut value: usize = 0;
+        let mut shift: u32 = 0;
+        loop {
+            let mut byte = [0];
+            self.read_bytes(&mut byte)?;
+            let payload = usize::from(byte[0] & 0x7f);
+
+            match payload.checked_shl(shift) {
+                Some(shifted) if shifted >> shift == payload => value |= shifted,
+                _ => return Err(invalid_data("varint does not fit into usize")),
+            }
+            shift += VARINT_PAYLOAD_BITS;
+
+            if byte[0] & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+    }
+
+    /// Reads the truncated checksum ending a record of a journal and starts a new checksum for the next record.
+    // `u64::try_from` is not available on old Rust versions, and `usize` has at most 64 bits.
+    #[allow(clippy::as_conversions)]
+    pub(crate) fn finish_record(&mut self) -> io::Result<()> {
+        let expected = self.checksum.to_bytes();
+        let mut actual = [0; RECORD_CHECKSUM_LEN];
+        self.reader.read_exact(&mut actual)?;
+        self.checksum = Checksum::new();
+        self.position += RECORD_CHECKSUM_LEN as u64;
+        if actual == expected[..RECORD_CHECKSUM_LEN] {
+            Ok(())
+        } else {
+            Err(invalid_data("checksum mismatch"))
+        }
+    }
+
+    fn finish(mut self) -> io::Result<()> {
+        let expected = self.checksum.to_bytes();
+        let mut actual = [0; 8];
+        self.reader.read_exact(&mut actual)?;
+        if actual == expected {
+            Ok(())
+        } else {
+            Err(invalid_data("checksum mismatch"))
+        }
+    }
+}
+
+/// Writes `ds` into `writer`, see [`DisjointSet::write_to`].
+// Contradicts `unreachable_pub` and `clippy::pub_without_shorthand`.
+#[allow(clippy::pub_with_shorthand, clippy::redundant_pub_crate)]
+pub(crate) fn write_disjoint_set<O, F, L, I, S, W>(
+    ds: &DisjointSet<O, F, L, I, S>,
+    writer: W,
+) -> io::Result<()>
+where
+    F: FindStrategy,
+    I: ElementIndex,
+    W: Write,
+{
+    let mut encoder = Encoder::new(writer);
+    encoder.write_bytes(&MAGIC)?;
+    encoder.write_bytes(&[VERSION])?;
+    encoder.write_varint(ds.len())?;
+
+    // Only roots get a label, so this holds one entry per set instead of one per element.
+    let mut root_to_label = HashMap::with_capacity(ds.num_sets());
+    for element in 0..ds.len() {
+        let number_labels = root_to_label.len();
+        let label = *root_to_label
+            .entry(ds.root_of(element))
+            .or_insert(number_labels);
+        encoder.write_varint(label)?;
+    }
+
+    encoder.finish()
+}
+
+/// Reads a `DisjointSet` from `reader`, see [`DisjointSet::read_from`].
+// Contradicts `unreachable_pub` and `clippy::pub_without_shorthand`.
+#[allow(clippy::pub_with_shorthand, clippy::redundant_pub_crate)]
+pub(crate) fn read_disjoint_set<R>(reader: R) -> io::Result<DisjointSet>
+where
+    R: Read,
+{
+    let mut decoder = Decoder::new(reader);
+
+    let mut magic = [0; 4];
+    decoder.read_bytes(&mut magic)?;
+    if magic != MAGIC {
+        return Err(invalid_data("not a disjoint set"));
+    }
+
+    let mut version = [0];
+    decoder.read_bytes(&mut version)?;
+    if version[0] != VERSION {
+        return Err(invalid_data("unsupported version"));
+    }
+
+    let len = decoder.read_varint()?;
+
+    // Elements are added one by one, so a corrupted length does not allocate up front.
+    let mut ds = DisjointSet::new();
+    let mut label_to_element = Vec::new();
+    for element in 0..len {
+        let label = decoder.read_varint()?;
+        ds.add_singleton();
+        match label.cmp(&label_to_element.len()) {
+            Ordering::Less => {
+                ds.join(label_to_element[label], element);
+            }
+            Ordering::Equal => label_to_element.push(element),
+            Ordering::Greater => return Err(invalid_data("labels are not canonical")),
+        }
+    }
+
+    decoder.finish()?;
+    Ok(ds)
+}
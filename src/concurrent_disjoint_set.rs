@@ -0,0 +1,388 @@
+use std::{
+    collections::HashMap,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use crate::DisjointSet;
+
+/// A lock-free disjoint-set data structure that can be queried and modified from many threads at once.
+///
+/// Unlike [`DisjointSet`], the methods [`join`], [`is_joined`] and [`root_of`] only need a shared reference, so a `ConcurrentDisjointSet` can be shared between threads (e.g. in an [`Arc`]) without wrapping it in a `Mutex`.
+/// All these operations are linearizable: They behave as if they happened atomically at some point between their invocation and their return.
+///
+/// Internally, joining is done by atomically linking one root below the other (compare-and-swap), and finding a root compresses the traversed path by path halving.
+/// To ensure that concurrent links can never form a cycle, roots are linked by a fixed pseudo-random priority of their indices instead of by rank.
+///
+/// A `ConcurrentDisjointSet` can be converted from and into a [`DisjointSet`] via [`From`].
+///
+/// [`join`]: ConcurrentDisjointSet::join
+/// [`is_joined`]: ConcurrentDisjointSet::is_joined
+/// [`root_of`]: ConcurrentDisjointSet::root_of
+/// [`Arc`]: std::sync::Arc
+///
+/// # Examples
+///
+/// ```
+/// use std::{sync::Arc, thread};
+/// use disjoint::ConcurrentDisjointSet;
+///
+/// let ds = Arc::new(ConcurrentDisjointSet::with_len(8)); // {0}, {1}, ..., {7}
+///
+/// let handles: Vec<_> = (0..4)
+///     .map(|i| {
+///         let ds = Arc::clone(&ds);
+///         thread::spawn(move || {
+///             ds.join(2 * i, 2 * i + 1);
+///         })
+///     })
+///     .collect();
+///
+/// for handle in handles {
+///     handle.join().unwrap();
+/// }
+///
+/// // {0, 1}, {2, 3}, {4, 5}, {6, 7}
+/// assert!(ds.is_joined(0, 1));
+/// assert!(ds.is_joined(6, 7));
+/// assert!(!ds.is_joined(1, 2));
+/// ```
+#[allow(clippy::missing_inline_in_public_items)]
+#[derive(Debug, Default)]
+pub struct ConcurrentDisjointSet {
+    parents: Vec<AtomicUsize>,
+}
+
+/// A bijection on `usize` that scrambles the natural order of the indices.
+///
+/// Linking by this priority instead of by index avoids degenerate trees when joining elements in index order.
+#[inline]
+#[allow(clippy::missing_const_for_fn)]
+fn priority(id: usize) -> usize {
+    let scrambled = id.wrapping_mul(0x9E37_79B9);
+    scrambled ^ (scrambled >> 16)
+}
+
+impl ConcurrentDisjointSet {
+    #[inline]
+    #[must_use]
+    fn get_parent(&self, id: usize) -> usize {
+        self.parents[id].load(Ordering::SeqCst)
+    }
+
+    /// Tries to change the parent of `id` from `current` to `new`. Returns `true` on success.
+    #[inline]
+    fn replace_parent(&self, id: usize, current: usize, new: usize) -> bool {
+        self.parents[id]
+            .compare_exchange(current, new, Ordering::SeqCst, Ordering::SeqCst)
+            .is_ok()
+    }
+
+    /// Constructs a new `ConcurrentDisjointSet` with `len` elements, named `0` to `n - 1`, each in its own set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::ConcurrentDisjointSet;
+    ///
+    /// let ds = ConcurrentDisjointSet::with_len(4);
+    ///
+    /// // The disjoint set contains 4 elements.
+    /// assert_eq!(ds.len(), 4);
+    ///
+    /// // Two elements i and j are not joined in the same set, unless i = j.
+    /// assert!(!ds.is_joined(0, 3));
+    /// assert!(ds.is_joined(1, 1));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn with_len(len: usize) -> Self {
+        Self {
+            parents: (0..len).map(AtomicUsize::new).collect(),
+        }
+    }
+
+    /// Constructs a new, empty `ConcurrentDisjointSet`.
+    ///
+    /// The disjoint set will not allocate until elements are added to it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::ConcurrentDisjointSet;
+    ///
+    /// let ds = ConcurrentDisjointSet::new();
+    /// assert!(ds.is_empty());
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a new element, not joined to any other element. Returns the index
+    /// of the new element.
+    ///
+    /// This is the only modifying operation that needs exclusive access.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new capacity exceeds `isize::MAX` bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::ConcurrentDisjointSet;
+    ///
+    /// let mut ds = ConcurrentDisjointSet::with_len(1);
+    /// assert_eq!(ds.add_singleton(), 1);
+    /// assert_eq!(ds.len(), 2);
+    /// assert!(!ds.is_joined(0, 1));
+    /// ```
+    #[inline]
+    pub fn add_singleton(&mut self) -> usize {
+        let id = self.len();
+        self.parents.push(AtomicUsize::new(id));
+        id
+    }
+
+    /// Returns an element of the subset containing `child`.
+    /// This exact element is returned for every member of the subset, as long as no other thread joins this subset to another one.
+    ///
+    /// # Important
+    ///
+    /// The specific choice of the returned element is an implementation detail.
+    /// There are no further guarantees beyond what is documented here.
+    /// If you just want to check if two elements are in the same subset, use [`is_joined`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `child` is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::ConcurrentDisjointSet;
+    ///
+    /// let ds = ConcurrentDisjointSet::with_len(3); // {0}, {1}, {2}
+    /// assert_eq!(ds.root_of(0), 0);
+    ///
+    /// ds.join(0, 1); // {0, 1}, {2}
+    /// assert_eq!(ds.root_of(0), ds.root_of(1));
+    /// assert_ne!(ds.root_of(0), ds.root_of(2));
+    /// ```
+    ///
+    /// [`is_joined`]: ConcurrentDisjointSet::is_joined
+    #[inline]
+    #[must_use]
+    pub fn root_of(&self, child: usize) -> usize {
+        let mut current = child;
+
+        loop {
+            let parent = self.get_parent(current);
+            if parent == current {
+                return current;
+            }
+
+            let grandparent = self.get_parent(parent);
+            if parent != grandparent {
+                // Path halving. If this fails, another thread already moved `current` further up.
+                self.replace_parent(current, parent, grandparent);
+            }
+
+            current = grandparent;
+        }
+    }
+
+    /// If `first_element` and `second_element` are in different sets, joins them together and returns `true`.
+    ///
+    /// Otherwise, does nothing and returns `false`.
+    ///
+    /// If several threads join the same two sets at the same time, exactly one of them returns `true`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `first_element` or `second_element` is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::ConcurrentDisjointSet;
+    ///
+    /// let ds = ConcurrentDisjointSet::with_len(4); // {0}, {1}, {2}, {3}
+    ///
+    /// assert!(ds.join(0, 1)); // {0, 1}, {2}, {3}
+    /// assert!(ds.join(2, 3)); // {0, 1}, {2, 3}
+    /// assert!(!ds.join(1, 0));
+    /// assert!(!ds.is_joined(0, 3));
+    ///
+    /// assert!(ds.join(1, 2)); // {0, 1, 2, 3}
+    /// assert!(ds.is_joined(0, 3));
+    /// ```
+    #[inline]
+    #[allow(clippy::must_use_candidate)]
+    pub fn join(&self, first_element: usize, second_element: usize) -> bool {
+        loop {
+            let root_first = self.root_of(first_element);
+            let root_second = self.root_of(second_element);
+
+            if root_first == root_second {
+                return false;
+            }
+
+            let (lower, higher) = if priority(root_first) < priority(root_second) {
+                (root_first, root_second)
+            } else {
+                (root_second, root_first)
+            };
+
+            // Only succeeds if `lower` is still a root. Otherwise, retry with the new roots.
+            if self.replace_parent(lower, lower, higher) {
+                return true;
+            }
+        }
+    }
+
+    /// Returns `true` if `first_element` and `second_element` are in the same subset.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `first_element` or `second_element` is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::ConcurrentDisjointSet;
+    ///
+    /// let ds = ConcurrentDisjointSet::with_len(3); // {0}, {1}, {2}
+    /// assert!(ds.is_joined(0, 0));
+    /// assert!(!ds.is_joined(0, 1));
+    ///
+    /// ds.join(1, 0); // {0, 1}, {2}
+    /// assert!(ds.is_joined(0, 1));
+    /// assert!(!ds.is_joined(0, 2));
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn is_joined(&self, first_element: usize, second_element: usize) -> bool {
+        loop {
+            let root_first = self.root_of(first_element);
+            let root_second = self.root_of(second_element);
+
+            if root_first == root_second {
+                return true;
+            }
+
+            // If `root_first` is still a root, the elements were disjoint when `root_second` was found.
+            if self.get_parent(root_first) == root_first {
+                return false;
+            }
+        }
+    }
+
+    /// Returns the number of elements in the disjoint set, regardless of how they are joined together.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::ConcurrentDisjointSet;
+    ///
+    /// let ds = ConcurrentDisjointSet::with_len(4);
+    /// assert_eq!(ds.len(), 4);
+    ///
+    /// ds.join(1, 3);
+    /// assert_eq!(ds.len(), 4);
+    /// ```
+    #[inline]
+    #[must_use]
+    #[allow(clippy::missing_const_for_fn)]
+    pub fn len(&self) -> usize {
+        self.parents.len()
+    }
+
+    /// Returns `true` if the disjoint set contains no elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::ConcurrentDisjointSet;
+    ///
+    /// assert!(ConcurrentDisjointSet::new().is_empty());
+    /// assert!(!ConcurrentDisjointSet::with_len(10).is_empty());
+    /// ```
+    #[inline]
+    #[must_use]
+    #[allow(clippy::missing_const_for_fn)]
+    pub fn is_empty(&self) -> bool {
+        self.parents.is_empty()
+    }
+
+    /// Returns a `Vec` of all sets. Each entry corresponds to one set, and is a `Vec` of its elements.
+    ///
+    /// The sets are ordered by their smallest contained element. The elements inside each sets are ordered.
+    ///
+    /// If other threads join elements while this runs, the result is not guaranteed to be a snapshot of any single point in time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::ConcurrentDisjointSet;
+    ///
+    /// let ds = ConcurrentDisjointSet::with_len(4); // {0}, {1}, {2}, {3}
+    /// ds.join(3, 1); // {0}, {1, 3}, {2}
+    /// assert_eq!(ds.sets(), vec![vec![0], vec![1, 3], vec![2]]);
+    /// ```
+    #[must_use]
+    #[allow(clippy::missing_inline_in_public_items)]
+    pub fn sets(&self) -> Vec<Vec<usize>> {
+        let mut result = Vec::new();
+        let mut root_to_result_id = HashMap::new();
+
+        for index in 0..self.len() {
+            let root = self.root_of(index);
+            let &mut result_id = root_to_result_id.entry(root).or_insert_with(|| {
+                let id = result.len();
+                result.push(Vec::with_capacity(1));
+                id
+            });
+            result[result_id].push(index);
+        }
+
+        result
+    }
+}
+
+impl From<DisjointSet> for ConcurrentDisjointSet {
+    #[allow(clippy::missing_inline_in_public_items)]
+    fn from(disjoint_set: DisjointSet) -> Self {
+        // Every set is flattened below its member of highest priority, to uphold that parents have a higher priority than their children.
+        let mut root_to_new_root = HashMap::new();
+
+        for index in 0..disjoint_set.len() {
+            let new_root = root_to_new_root
+                .entry(disjoint_set.root_of(index))
+                .or_insert(index);
+            if priority(*new_root) < priority(index) {
+                *new_root = index;
+            }
+        }
+
+        Self {
+            parents: (0..disjoint_set.len())
+                .map(|index| AtomicUsize::new(root_to_new_root[&disjoint_set.root_of(index)]))
+                .collect(),
+        }
+    }
+}
+
+impl From<ConcurrentDisjointSet> for DisjointSet {
+    #[allow(clippy::missing_inline_in_public_items)]
+    fn from(concurrent: ConcurrentDisjointSet) -> Self {
+        let mut result = Self::with_len(concurrent.len());
+
+        for index in 0..concurrent.len() {
+            result.join(index, concurrent.root_of(index));
+        }
+
+        result
+    }
+}
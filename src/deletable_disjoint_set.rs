@@ -0,0 +1,511 @@
+use std::collections::{hash_map::Entry, HashMap};
+
+use crate::DisjointSet;
+
+/// A disjoint-set data structure that additionally supports taking elements out of their subset again, via [`isolate`] and [`remove`].
+///
+/// Internally, every element is represented by a node in a [`DisjointSet`]. Isolating an element gives it a fresh node, and leaves the old node behind as a vacant placeholder in its tree, so the other members of the subset stay joined. Once there are as many vacant nodes as elements, all trees are rebuilt without them.
+/// The members of each subset are additionally linked in a cycle, so that a remaining member can take over as representative when an element leaves.
+/// This keeps [`join`], [`is_joined`] and [`root_of`] as fast as for a [`DisjointSet`], and [`isolate`] and [`remove`] take nearly constant amortized time as well.
+///
+/// [`isolate`]: DeletableDisjointSet::isolate
+/// [`remove`]: DeletableDisjointSet::remove
+/// [`join`]: DeletableDisjointSet::join
+/// [`is_joined`]: DeletableDisjointSet::is_joined
+/// [`root_of`]: DeletableDisjointSet::root_of
+///
+/// Removed elements keep their index, which is never reused, so memory grows with [`len`] including removed elements.
+/// Apart from that, isolating and removing elements does not grow memory: at most two nodes are kept per element.
+///
+/// [`len`]: DeletableDisjointSet::len
+///
+/// # Examples
+///
+/// ```
+/// use disjoint::DeletableDisjointSet;
+///
+/// let mut ds = DeletableDisjointSet::with_len(4); // {0}, {1}, {2}, {3}
+/// ds.join(0, 1);
+/// ds.join(1, 2); // {0, 1, 2}, {3}
+///
+/// // 1 was joined by mistake, so it is taken out again.
+/// ds.isolate(1); // {0, 2}, {1}, {3}
+/// assert!(ds.is_joined(0, 2));
+/// assert!(!ds.is_joined(0, 1));
+///
+/// // 3 is not needed anymore.
+/// ds.remove(3); // {0, 2}, {1}
+/// assert!(!ds.contains(3));
+/// assert_eq!(ds.sets(), vec![vec![0, 2], vec![1]]);
+/// ```
+#[allow(clippy::missing_inline_in_public_items)]
+#[derive(Debug, Clone, Default)]
+pub struct DeletableDisjointSet {
+    nodes: DisjointSet,
+    element_nodes: Vec<Option<usize>>,
+    /// For each root node, the number of elements in its tree. Vacant nodes are not counted.
+    live_sizes: Vec<usize>,
+    /// For each root node with elements in its tree, one of these elements.
+    representatives: Vec<usize>,
+    /// For each element, the next member of its subset, forming a cycle through all of them.
+    next_members: Vec<usize>,
+    /// For each element, the previous member of its subset, forming a cycle through all of them.
+    previous_members: Vec<usize>,
+}
+
+impl DeletableDisjointSet {
+    #[inline]
+    #[allow(clippy::expect_used)]
+    fn node_of(&self, element: usize) -> usize {
+        self.element_nodes[element].expect("element was removed")
+    }
+
+    fn root_node_of(&self, element: usize) -> usize {
+        self.nodes.root_of(self.node_of(element))
+    }
+
+    /// Takes `element` out of the cycle of members of its subset, and hands over the representative of `root` if necessary.
+    fn unlink(&mut self, element: usize, root: usize) {
+        let previous = self.previous_members[element];
+        let next = self.next_members[element];
+        self.next_members[previous] = next;
+        self.previous_members[next] = previous;
+        self.next_members[element] = element;
+        self.previous_members[element] = element;
+
+        if self.representatives[root] == element {
+            self.representatives[root] = next;
+        }
+    }
+
+    /// Constructs a new `DeletableDisjointSet` with `len` elements, named `0` to `n - 1`, each in its own set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::DeletableDisjointSet;
+    ///
+    /// let ds = DeletableDisjointSet::with_len(4);
+    ///
+    /// // The disjoint set contains 4 elements.
+    /// assert_eq!(ds.len(), 4);
+    ///
+    /// // Two elements i and j are not joined in the same set, unless i = j.
+    /// assert!(!ds.is_joined(0, 3));
+    /// assert!(ds.is_joined(1, 1));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn with_len(len: usize) -> Self {
+        Self::from(DisjointSet::with_len(len))
+    }
+
+    /// Constructs a new, empty `DeletableDisjointSet`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::DeletableDisjointSet;
+    ///
+    /// let ds = DeletableDisjointSet::new();
+    /// assert!(ds.is_empty());
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a new element, not joined to any other element. Returns the index
+    /// of the new element.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new capacity exceeds `isize::MAX` bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::DeletableDisjointSet;
+    ///
+    /// let mut ds = DeletableDisjointSet::with_len(1);
+    /// assert_eq!(ds.add_singleton(), 1);
+    /// assert_eq!(ds.len(), 2);
+    /// assert!(!ds.is_joined(0, 1));
+    /// ```
+    #[inline]
+    pub fn add_singleton(&mut self) -> usize {
+        let id = self.len();
+        self.element_nodes.push(Some(self.nodes.add_singleton()));
+        self.live_sizes.push(1);
+        self.representatives.push(id);
+        self.next_members.push(id);
+        self.previous_members.push(id);
+        id
+    }
+
+    /// Returns the element representing the subset containing `child`.
+    /// This exact element is returned for every member of the subset, as long as the disjoint set is not modified.
+    ///
+    /// # Important
+    ///
+    /// The representative is always a member of the subset. If it is isolated or removed, another member takes over.
+    /// The specific choice of the representative is an implementation detail.
+    /// If you just want to check if two elements are in the same subset, use [`is_joined`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `child` is out of bounds or was removed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::DeletableDisjointSet;
+    ///
+    /// let mut ds = DeletableDisjointSet::with_len(3); // {0}, {1}, {2}
+    /// ds.join(0, 1); // {0, 1}, {2}
+    /// assert_eq!(ds.root_of(0), ds.root_of(1));
+    /// assert_ne!(ds.root_of(0), ds.root_of(2));
+    ///
+    /// ds.remove(0); // {1}, {2}
+    /// assert_eq!(ds.root_of(1), 1);
+    /// ```
+    ///
+    /// [`is_joined`]: DeletableDisjointSet::is_joined
+    #[inline]
+    #[must_use]
+    pub fn root_of(&self, child: usize) -> usize {
+        self.representatives[self.root_node_of(child)]
+    }
+
+    /// If `first_element` and `second_element` are in different sets, joins them together and returns `true`.
+    ///
+    /// Otherwise, does nothing and returns `false`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `first_element` or `second_element` is out of bounds or was removed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::DeletableDisjointSet;
+    ///
+    /// let mut ds = DeletableDisjointSet::with_len(3); // {0}, {1}, {2}
+    /// assert!(ds.join(0, 1)); // {0, 1}, {2}
+    /// assert!(!ds.join(1, 0));
+    /// assert!(ds.is_joined(0, 1));
+    /// ```
+    #[inline]
+    pub fn join(&mut self, first_element: usize, second_element: usize) -> bool {
+        let root_first = self.root_node_of(first_element);
+        let root_second = self.root_node_of(second_element);
+
+        if !self.nodes.join(root_first, root_second) {
+            return false;
+        }
+
+        let new_root = self.nodes.root_of(root_first);
+        self.live_sizes[new_root] = self.live_sizes[root_first] + self.live_sizes[root_second];
+        self.representatives[new_root] = self.representatives[root_first];
+
+        // Splices the two cycles of members into one.
+        let next_first = self.next_members[first_element];
+        let next_second = self.next_members[second_element];
+        self.next_members[first_element] = next_second;
+        self.previous_members[next_second] = first_element;
+        self.next_members[second_element] = next_first;
+        self.previous_members[next_first] = second_element;
+        true
+    }
+
+    /// Returns `true` if `first_element` and `second_element` are in the same subset.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `first_element` or `second_element` is out of bounds or was removed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::DeletableDisjointSet;
+    ///
+    /// let mut ds = DeletableDisjointSet::with_len(3); // {0}, {1}, {2}
+    /// assert!(!ds.is_joined(0, 1));
+    ///
+    /// ds.join(1, 0); // {0, 1}, {2}
+    /// assert!(ds.is_joined(0, 1));
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn is_joined(&self, first_element: usize, second_element: usize) -> bool {
+        self.root_node_of(first_element) == self.root_node_of(second_element)
+    }
+
+    /// Takes `element` out of its subset, so it is afterwards in a subset on its own. All other members of its former subset stay joined to each other.
+    ///
+    /// Returns `true` if `element` was joined to any other element before.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `element` is out of bounds or was removed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::DeletableDisjointSet;
+    ///
+    /// let mut ds = DeletableDisjointSet::with_len(3);
+    /// ds.join(0, 1);
+    /// ds.join(1, 2); // {0, 1, 2}
+    ///
+    /// assert!(ds.isolate(0)); // {0}, {1, 2}
+    /// assert!(!ds.isolate(0));
+    /// assert!(!ds.is_joined(0, 1));
+    /// assert!(ds.is_joined(1, 2));
+    /// ```
+    #[inline]
+    pub fn isolate(&mut self, element: usize) -> bool {
+        let root = self.root_node_of(element);
+        if self.live_sizes[root] == 1 {
+            return false;
+        }
+
+        self.live_sizes[root] -= 1;
+        self.unlink(element, root);
+        self.element_nodes[element] = Some(self.nodes.add_singleton());
+        self.live_sizes.push(1);
+        self.representatives.push(element);
+        self.rebuild_if_sparse();
+        true
+    }
+
+    /// Removes `element` from the disjoint set. All other members of its former subset stay joined to each other.
+    ///
+    /// The indices of the other elements do not change. Afterwards, [`contains`] returns `false` for `element`, and all methods taking `element` as an argument panic.
+    ///
+    /// [`contains`]: DeletableDisjointSet::contains
+    ///
+    /// # Panics
+    ///
+    /// Panics if `element` is out of bounds or was already removed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::DeletableDisjointSet;
+    ///
+    /// let mut ds = DeletableDisjointSet::with_len(3);
+    /// ds.join(0, 1);
+    /// ds.join(1, 2); // {0, 1, 2}
+    ///
+    /// ds.remove(1); // {0, 2}
+    /// assert!(!ds.contains(1));
+    /// assert!(ds.is_joined(0, 2));
+    /// assert_eq!(ds.sets(), vec![vec![0, 2]]);
+    /// ```
+    #[inline]
+    pub fn remove(&mut self, element: usize) {
+        let root = self.root_node_of(element);
+        self.live_sizes[root] -= 1;
+        self.unlink(element, root);
+        self.element_nodes[element] = None;
+    }
+
+    /// Returns the number of elements in the subset containing `element`, including `element` itself.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `element` is out of bounds or was removed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::DeletableDisjointSet;
+    ///
+    /// let mut ds = DeletableDisjointSet::with_len(3);
+    /// ds.join(0, 1);
+    /// ds.join(1, 2); // {0, 1, 2}
+    /// assert_eq!(ds.size_of(0), 3);
+    ///
+    /// ds.remove(1); // {0, 2}
+    /// assert_eq!(ds.size_of(0), 2);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn size_of(&self, element: usize) -> usize {
+        self.live_sizes[self.root_node_of(element)]
+    }
+
+    /// Returns `true` if `element` is in bounds and was not removed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::DeletableDisjointSet;
+    ///
+    /// let mut ds = DeletableDisjointSet::with_len(2);
+    /// ds.remove(0);
+    /// assert!(!ds.contains(0));
+    /// assert!(ds.contains(1));
+    /// assert!(!ds.contains(2));
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn contains(&self, element: usize) -> bool {
+        element < self.len() && self.element_nodes[element].is_some()
+    }
+
+    /// Returns the number of elements in the disjoint set, including removed ones.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::DeletableDisjointSet;
+    ///
+    /// let mut ds = DeletableDisjointSet::with_len(4);
+    /// ds.remove(2);
+    /// assert_eq!(ds.len(), 4);
+    /// ```
+    #[inline]
+    #[must_use]
+    #[allow(clippy::missing_const_for_fn)]
+    pub fn len(&self) -> usize {
+        self.element_nodes.len()
+    }
+
+    /// Returns `true` if the disjoint set contains no elements, including removed ones.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::DeletableDisjointSet;
+    ///
+    /// assert!(DeletableDisjointSet::new().is_empty());
+    /// assert!(!DeletableDisjointSet::with_len(10).is_empty());
+    /// ```
+    #[inline]
+    #[must_use]
+    #[allow(clippy::missing_const_for_fn)]
+    pub fn is_empty(&self) -> bool {
+        self.element_nodes.is_empty()
+    }
+
+    /// Returns a `Vec` of all sets. Each entry corresponds to one set, and is a `Vec` of its elements. Removed elements are not contained in any set.
+    ///
+    /// The sets are ordered by their smallest contained element. The elements inside each sets are ordered.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::DeletableDisjointSet;
+    ///
+    /// let mut ds = DeletableDisjointSet::with_len(4); // {0}, {1}, {2}, {3}
+    /// ds.join(3, 1); // {0}, {1, 3}, {2}
+    /// ds.remove(0); // {1, 3}, {2}
+    /// assert_eq!(ds.sets(), vec![vec![1, 3], vec![2]]);
+    /// ```
+    #[must_use]
+    #[allow(clippy::missing_inline_in_public_items)]
+    pub fn sets(&self) -> Vec<Vec<usize>> {
+        let mut result = Vec::new();
+        let mut root_to_result_id = HashMap::new();
+
+        for (element, maybe_node) in self.element_nodes.iter().enumerate() {
+            if let Some(node) = *maybe_node {
+                let root = self.nodes.root_of(node);
+                let &mut result_id = root_to_result_id.entry(root).or_insert_with(|| {
+                    let id = result.len();
+                    result.push(Vec::with_capacity(1));
+                    id
+                });
+                result[result_id].push(element);
+            }
+        }
+
+        result
+    }
+
+    /// Rebuilds the forest without vacant nodes, once there are as many vacant nodes as elements.
+    fn rebuild_if_sparse(&mut self) {
+        if self.nodes.len() < 2 * self.len() {
+            return;
+        }
+
+        // Removed elements keep an unused singleton node, so that node `i` can represent element `i`.
+        let mut rebuilt = DisjointSet::with_len(self.len());
+        let mut root_to_element = HashMap::new();
+        // For the first element of each tree, the representative of that tree.
+        let mut first_representatives = vec![None; self.len()];
+
+        for (element, first_representative) in first_representatives.iter_mut().enumerate() {
+            if let Some(node) = self.element_nodes[element] {
+                match root_to_element.entry(self.nodes.root_of(node)) {
+                    Entry::Occupied(entry) => {
+                        rebuilt.join(*entry.get(), element);
+                    }
+                    Entry::Vacant(entry) => {
+                        *first_representative = Some(self.representatives[*entry.key()]);
+                        entry.insert(element);
+                    }
+                }
+                self.element_nodes[element] = Some(element);
+            }
+        }
+
+        let mut live_sizes = vec![0; self.len()];
+        for node in self.element_nodes.iter().filter_map(|&node| node) {
+            live_sizes[rebuilt.root_of(node)] += 1;
+        }
+
+        let mut representatives: Vec<_> = (0..self.len()).collect();
+        for (element, maybe_representative) in first_representatives.into_iter().enumerate() {
+            if let Some(representative) = maybe_representative {
+                representatives[rebuilt.root_of(element)] = representative;
+            }
+        }
+
+        self.nodes = rebuilt;
+        self.live_sizes = live_sizes;
+        self.representatives = representatives;
+    }
+}
+
+impl From<DisjointSet> for DeletableDisjointSet {
+    #[inline]
+    fn from(disjoint_set: DisjointSet) -> Self {
+        let len = disjoint_set.len();
+        let mut next_members: Vec<_> = (0..len).collect();
+        let mut previous_members: Vec<_> = (0..len).collect();
+
+        // Inserts every element into the cycle of its root, right after the root.
+        for element in 0..len {
+            let root = disjoint_set.root_of(element);
+            if root != element {
+                let next = next_members[root];
+                next_members[root] = element;
+                previous_members[element] = root;
+                next_members[element] = next;
+                previous_members[next] = element;
+            }
+        }
+
+        Self {
+            element_nodes: (0..len).map(Some).collect(),
+            live_sizes: (0..len).map(|node| disjoint_set.size_of(node)).collect(),
+            representatives: (0..len).collect(),
+            next_members,
+            previous_members,
+            nodes: disjoint_set,
+        }
+    }
+}
+
+impl PartialEq for DeletableDisjointSet {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.sets() == other.sets()
+    }
+}
+
+impl Eq for DeletableDisjointSet {}
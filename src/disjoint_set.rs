@@ -1,6 +1,20 @@
+// The `alloc` crate is not available on old Rust versions.
+#![allow(clippy::std_instead_of_alloc)]
+
 use std::{
     cell::Cell,
-    collections::{hash_map::Entry, HashMap},
+    cmp::Ordering,
+    collections::{hash_map::Entry, BTreeMap, HashMap},
+    fmt::{self, Debug, Formatter},
+    hash::{Hash, Hasher},
+    io::{self, Read, Write},
+    iter::FromIterator,
+};
+
+use crate::{
+    binary_format, display, invalid_partition, journal, ByRank, ElementIndex, FindStrategy,
+    IndexOverflow, InvalidPartition, InvariantViolation, JoinObserver, LinkStrategy, NoObserver,
+    NoTracking, PartitionDiff, PathSplitting, Tracking, TracksMembers,
 };
 
 /// A disjoint-set data structure for tracking which elements are joined, without managing any additional data associated to the elements.
@@ -11,6 +25,30 @@ use std::{
 /// [`is_joined`]: DisjointSet::is_joined
 /// [`len`]: DisjointSet::len
 ///
+/// The type parameter `O` is a [`JoinObserver`] that is notified about each join, see [`with_len_and_observer`]. By default, it is [`NoObserver`], which costs nothing.
+///
+/// [`with_len_and_observer`]: DisjointSet::with_len_and_observer
+///
+/// The type parameters `F` and `L` are the [`FindStrategy`] of [`root_of`] and the [`LinkStrategy`] of [`join`], see [`with_len_and_strategies`].
+/// By default, they are [`PathSplitting`] and [`ByRank`].
+///
+/// [`root_of`]: DisjointSet::root_of
+/// [`with_len_and_strategies`]: DisjointSet::with_len_and_strategies
+///
+/// The type parameter `I` is the [`ElementIndex`] in which the elements are stored, see [`try_with_len`] and [`CompactDisjointSet`]. By default, it is `usize`.
+/// A smaller type like `u32` saves memory, but limits the number of elements.
+/// Each element takes one `I` for its parent, one `I` for the size of its set and one byte for its rank, so 9 bytes with `u32` instead of 17 bytes with `usize` on 64-bit targets.
+/// Member links take one more `I` per element, see below.
+///
+/// [`try_with_len`]: DisjointSet::try_with_len
+///
+/// The type parameter `S` is the [`Tracking`] of the members of each set, see [`with_len_and_tracking`]. By default, it is [`NoTracking`].
+/// [`MemberTracking`] enables [`members`], for one more index per element.
+///
+/// [`with_len_and_tracking`]: DisjointSet::with_len_and_tracking
+/// [`MemberTracking`]: crate::MemberTracking
+/// [`members`]: DisjointSet::members
+///
 /// # Examples
 ///
 /// ```
@@ -34,89 +72,118 @@ use std::{
 /// For a real word application example, see [the crate examples].
 ///
 /// [the crate examples]: crate#examples
-#[allow(clippy::missing_inline_in_public_items)]
-#[derive(Debug, Clone)]
-pub struct DisjointSet {
-    parents: Vec<Cell<usize>>,
+pub struct DisjointSet<O = NoObserver, F = PathSplitting, L = ByRank, I = usize, S = NoTracking> {
+    parents: Vec<Cell<I>>,
     ranks: Vec<u8>,
+    sizes: Vec<I>,
+    /// The next member in the cycle through each set, if members are tracked. Otherwise empty.
+    nexts: Vec<I>,
+    number_sets: usize,
+    largest_set: Option<usize>,
+    observer: O,
+    find: F,
+    link: L,
+    tracking: S,
 }
 
-impl Default for DisjointSet {
-    #[inline]
-    fn default() -> Self {
-        Self::new()
+/// A [`DisjointSet`] with the default observer and strategies that stores its elements in the [`ElementIndex`] `I`, see [`try_with_len`].
+///
+/// [`try_with_len`]: DisjointSet::try_with_len
+///
+/// # Examples
+///
+/// ```
+/// use disjoint::CompactDisjointSet;
+///
+/// let mut ds = CompactDisjointSet::<u32>::try_with_len(4).unwrap();
+/// ds.join(0, 3);
+/// assert_eq!(ds.sets(), vec![vec![0, 3], vec![1], vec![2]]);
+/// ```
+pub type CompactDisjointSet<I> = DisjointSet<NoObserver, PathSplitting, ByRank, I>;
+
+impl<O, F, L, I, S> Debug for DisjointSet<O, F, L, I, S>
+where
+    O: Debug,
+    F: Debug,
+    L: Debug,
+    I: ElementIndex,
+    S: Debug,
+{
+    #[allow(clippy::missing_inline_in_public_items)]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DisjointSet")
+            .field("parents", &self.parents)
+            .field("ranks", &self.ranks)
+            .field("sizes", &self.sizes)
+            .field("nexts", &self.nexts)
+            .field("number_sets", &self.number_sets)
+            .field("largest_set", &self.largest_set)
+            .field("observer", &self.observer)
+            .field("find", &self.find)
+            .field("link", &self.link)
+            .field("tracking", &self.tracking)
+            .finish()
     }
 }
 
-impl DisjointSet {
+impl<O, F, L, I, S> Clone for DisjointSet<O, F, L, I, S>
+where
+    O: Clone,
+    F: Clone,
+    L: Clone,
+    I: ElementIndex,
+    S: Clone,
+{
     #[inline]
-    #[must_use]
-    fn get_parent(&self, id: usize) -> usize {
-        self.parents[id].get()
+    fn clone(&self) -> Self {
+        Self {
+            parents: self.parents.clone(),
+            ranks: self.ranks.clone(),
+            sizes: self.sizes.clone(),
+            nexts: self.nexts.clone(),
+            number_sets: self.number_sets,
+            largest_set: self.largest_set,
+            observer: self.observer.clone(),
+            find: self.find.clone(),
+            link: self.link.clone(),
+            tracking: self.tracking.clone(),
+        }
     }
+}
 
-    #[inline]
-    fn set_parent(&self, id: usize, new: usize) {
-        self.parents[id].set(new);
-    }
+/// Iterator over the members of one set, following the circular `next` links.
+struct Members<'a, I> {
+    nexts: &'a [I],
+    start: usize,
+    current: Option<usize>,
+}
 
-    #[inline]
-    #[must_use]
-    fn get_mut_rank(&mut self, id: usize) -> &mut u8 {
-        &mut self.ranks[id]
-    }
+impl<I> Iterator for Members<'_, I>
+where
+    I: ElementIndex,
+{
+    type Item = usize;
 
-    /// Returns an element of the subset containing `child`.
-    /// This exact element is returned for every member of the subset.
-    ///
-    /// # Important
-    ///
-    /// The specific choice of the returned element is an implementation detail.
-    /// There are no further guarantees beyond what is documented here.
-    /// If you just want to check if two elements are in the same subset, use [`is_joined`].
-    ///
-    /// # Examples
-    ///
-    /// ```
-    /// use disjoint::DisjointSet;
-    ///
-    /// let mut ds = DisjointSet::with_len(3); // {0}, {1}, {2}
-    /// assert_eq!(ds.root_of(0), 0);
-    /// assert_eq!(ds.root_of(1), 1);
-    /// assert_eq!(ds.root_of(2), 2);
-    ///
-    ///
-    /// ds.join(0, 1); // {0, 1}, {2}
-    /// assert_eq!(ds.root_of(0), ds.root_of(1));
-    /// assert_ne!(ds.root_of(0), ds.root_of(2));
-    ///
-    /// ds.join(1, 2); // {0, 1, 2}
-    /// assert_eq!(ds.root_of(0), ds.root_of(1));
-    /// assert_eq!(ds.root_of(0), ds.root_of(2));
-    /// ```
-    ///
-    /// [`is_joined`]: DisjointSet::is_joined
     #[inline]
-    #[must_use]
-    pub fn root_of(&self, mut child: usize) -> usize {
-        let mut parent = self.get_parent(child);
-
-        if child == parent {
-            return child;
+    fn next(&mut self) -> Option<usize> {
+        let current = match self.current {
+            Some(current) => current,
+            None => return None,
         };
+        let next = self.nexts[current].to_usize();
+        self.current = if next == self.start { None } else { Some(next) };
+        Some(current)
+    }
+}
 
-        loop {
-            let grandparent = self.get_parent(parent);
-            if parent == grandparent {
-                return parent;
-            }
-
-            self.set_parent(child, grandparent);
-            child = parent;
-            parent = grandparent;
-        }
+impl Default for DisjointSet {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
     }
+}
 
+impl DisjointSet {
     /// Constructs a new `DisjointSet` with `len` elements, named `0` to `n - 1`, each in its own set.
     ///
     /// # Examples
@@ -137,10 +204,7 @@ impl DisjointSet {
     #[inline]
     #[must_use]
     pub fn with_len(len: usize) -> Self {
-        Self {
-            parents: (0..len).map(Cell::new).collect(),
-            ranks: vec![0; len],
-        }
+        Self::with_len_and_observer(len, NoObserver)
     }
 
     /// Constructs a new, empty `DisjointSet` with at least the specified capacity.
@@ -180,275 +244,1876 @@ impl DisjointSet {
         Self {
             parents: Vec::with_capacity(capacity),
             ranks: Vec::with_capacity(capacity),
+            sizes: Vec::new(),
+            nexts: Vec::new(),
+            number_sets: 0,
+            largest_set: None,
+            observer: NoObserver,
+            find: PathSplitting,
+            link: ByRank,
+            tracking: NoTracking,
         }
     }
 
-    /// Adds a new element, not joined to any other element. Returns the index
-    /// of the new element.
-    ///
-    /// # Panics
+    /// Constructs a new, empty `DisjointSet`.
     ///
-    /// Panics if the new capacity exceeds `isize::MAX` bytes.
+    /// The disjoint set will not allocate until elements are added to it.
     ///
     /// # Examples
     ///
     /// ```
-    /// use disjoint::DisjointSet;
-    ///
-    /// let mut ds = DisjointSet::with_len(1);
-    /// assert_eq!(ds.add_singleton(), 1);
-    /// assert_eq!(ds.len(), 2);
-    /// assert!(!ds.is_joined(0, 1));
+    /// # #![allow(unused_mut)]
+    /// let mut vec: Vec<i32> = Vec::new();
     /// ```
+    #[must_use]
     #[inline]
-    pub fn add_singleton(&mut self) -> usize {
-        let id = self.len();
-        self.parents.push(Cell::new(id));
-        self.ranks.push(0);
-        id
+    #[allow(clippy::missing_const_for_fn)]
+    pub fn new() -> Self {
+        Self {
+            parents: Vec::new(),
+            ranks: Vec::new(),
+            sizes: Vec::new(),
+            nexts: Vec::new(),
+            number_sets: 0,
+            largest_set: None,
+            observer: NoObserver,
+            find: PathSplitting,
+            link: ByRank,
+            tracking: NoTracking,
+        }
     }
 
-    /// If `first_element` and `second_element` are in different sets, joins them together and returns `true`.
-    ///
-    /// Otherwise, does nothing and returns `false`.
+    /// Creates a `DisjointSet` with one element per entry of `labels`, where two elements are joined if and only if they have the same label.
     ///
-    /// # Panics
+    /// The labels can be arbitrary, like the ones returned by [`labels`].
     ///
-    /// Panics if `first_element` or `second_element` is out of bounds.
+    /// [`labels`]: DisjointSet::labels
     ///
     /// # Examples
     ///
     /// ```
     /// use disjoint::DisjointSet;
     ///
-    /// // Initially, each element is in its own set.
-    /// let mut ds = DisjointSet::with_len(4); // {0}, {1}, {2}, {3}
-    /// assert!(!ds.is_joined(0, 3));
-    ///
-    /// // By joining 0 to 1 and 2 to 3, we get two sets of two elements each.
-    /// ds.join(0, 1); // {0, 1}, {2}, {3}
-    /// ds.join(2, 3); // {0, 1}, {2, 3}
-    /// assert!(ds.is_joined(0, 1));
-    /// assert!(ds.is_joined(2, 3));
-    /// assert!(!ds.is_joined(0, 3));
-    ///
-    /// // By further joining 2 to 3, all elements are now in the same set.
-    /// ds.join(1, 2); // {0, 1, 2, 3}
-    /// assert!(ds.is_joined(0, 3));
+    /// let ds = DisjointSet::from_labels(&["red", "blue", "red", "green"]);
+    /// assert_eq!(ds.sets(), vec![vec![0, 2], vec![1], vec![3]]);
+    /// assert_eq!(DisjointSet::from_labels(&ds.labels().0), ds);
     /// ```
-    #[inline]
-    pub fn join(&mut self, first_element: usize, second_element: usize) -> bool {
-        fn slow_path(ds: &mut DisjointSet, first_element: usize, second_element: usize) -> bool {
-            let root_first = ds.root_of(first_element);
-            let root_second = ds.root_of(second_element);
-
-            if root_first == root_second {
-                return false;
-            }
-
-            let rank_second = *ds.get_mut_rank(root_second);
-            let rank_first = ds.get_mut_rank(root_first);
+    #[must_use]
+    #[allow(clippy::missing_inline_in_public_items)]
+    pub fn from_labels<L>(labels: &[L]) -> Self
+    where
+        L: Hash + Eq,
+    {
+        let mut result = Self::with_len(labels.len());
+        let mut label_to_element = HashMap::new();
 
-            if *rank_first < rank_second {
-                ds.set_parent(root_first, root_second);
-            } else {
-                if *rank_first == rank_second {
-                    *rank_first += 1;
+        for (element, label) in labels.iter().enumerate() {
+            match label_to_element.entry(label) {
+                Entry::Occupied(entry) => {
+                    result.join(*entry.get(), element);
+                }
+                Entry::Vacant(entry) => {
+                    entry.insert(element);
                 }
-                ds.set_parent(root_second, root_first);
             }
-
-            true
         }
 
-        // Immediate parent check.
-        if self.get_parent(first_element) == self.get_parent(second_element) {
-            return false;
-        }
+        result
+    }
 
-        slow_path(self, first_element, second_element)
+    /// Creates a `DisjointSet` where exactly the elements in each of the `sets` are joined, like returned by [`sets`].
+    ///
+    /// The sets and the elements inside each set may be in any order.
+    ///
+    /// [`sets`]: DisjointSet::sets
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`InvalidPartition`] if `sets` is not a partition of the elements from `0` to `n - 1`, where `n` is the total number of elements in `sets`.
+    /// This is the case if a set is empty, or if an element is out of bounds or appears more than once.
+    ///
+    /// [`InvalidPartition`]: crate::InvalidPartition
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::{DisjointSet, InvalidPartition};
+    ///
+    /// let ds = DisjointSet::from_sets(&[vec![3, 1], vec![0], vec![2, 4]]).unwrap();
+    /// assert_eq!(ds.sets(), vec![vec![0], vec![1, 3], vec![2, 4]]);
+    ///
+    /// let error = DisjointSet::from_sets(&[vec![0, 1], vec![1]]).unwrap_err();
+    /// assert_eq!(error, InvalidPartition::Duplicate { element: 1 });
+    /// ```
+    #[allow(clippy::missing_inline_in_public_items)]
+    pub fn from_sets(sets: &[Vec<usize>]) -> Result<Self, InvalidPartition> {
+        let mut result = Self::with_len(sets.iter().map(Vec::len).sum());
+        let len = result.len();
+        invalid_partition::join_sets(sets, len, |first, second| {
+            result.join(first, second);
+        })
+        .map(|()| result)
     }
 
-    /// Returns `true` if `first_element` and `second_element` are in the same subset.
+    /// Reads a partition from `reader`, in the format written by [`write_to`].
     ///
-    /// # Panics
+    /// The data is read incrementally, and exactly up to the end of the written data.
+    /// Reading byte by byte can be slow, so consider wrapping `reader` in a [`BufReader`].
     ///
-    /// Panics if `first_element` or `second_element` is out of bounds.
+    /// [`write_to`]: DisjointSet::write_to
+    /// [`BufReader`]: std::io::BufReader
+    ///
+    /// # Errors
+    ///
+    /// Returns an error with [`ErrorKind::InvalidData`] if the data is malformed, for example if the header, the labels or the checksum are invalid.
+    /// Returns any error that occurs while reading from `reader`, like [`ErrorKind::UnexpectedEof`] for truncated data.
+    ///
+    /// [`ErrorKind::InvalidData`]: std::io::ErrorKind::InvalidData
+    /// [`ErrorKind::UnexpectedEof`]: std::io::ErrorKind::UnexpectedEof
     ///
     /// # Examples
     ///
     /// ```
     /// use disjoint::DisjointSet;
+    /// use std::io::ErrorKind;
     ///
-    /// // Initially, elements are only joined to themselves.
-    /// let mut ds = DisjointSet::with_len(3); // {0}, {1}, {2}
-    /// assert!(ds.is_joined(0, 0));
-    /// assert!(!ds.is_joined(0, 1));
-    /// assert!(!ds.is_joined(0, 2));
+    /// let mut bytes = Vec::new();
+    /// DisjointSet::with_len(3).write_to(&mut bytes).unwrap();
     ///
-    /// // By joining 1 to 0, we implicitely join 0 to 1.
-    /// ds.join(1, 0); // {0, 1}, {2}
-    /// assert!(ds.is_joined(1, 0));
-    /// assert!(ds.is_joined(0, 1));
+    /// assert_eq!(DisjointSet::read_from(&bytes[..]).unwrap(), DisjointSet::with_len(3));
     ///
-    /// // By joining 0 to 1 and 1 to 2, we implicitely join 0 to 2.
-    /// ds.join(1, 2); // {0, 1, 2}
-    /// assert!(ds.is_joined(0, 2));
+    /// // Corrupted data is detected.
+    /// bytes[6] = 1;
+    /// let error = DisjointSet::read_from(&bytes[..]).unwrap_err();
+    /// assert_eq!(error.kind(), ErrorKind::InvalidData);
     /// ```
-    #[must_use]
-    #[inline]
-    pub fn is_joined(&self, first_element: usize, second_element: usize) -> bool {
-        self.root_of(first_element) == self.root_of(second_element)
+    #[allow(clippy::missing_inline_in_public_items)]
+    pub fn read_from<R>(reader: R) -> io::Result<Self>
+    where
+        R: Read,
+    {
+        binary_format::read_disjoint_set(reader)
     }
+}
 
-    /// Returns the number of elements in the disjoint set, regardless of how they are joined together.
+impl<O> DisjointSet<O> {
+    /// Creates a new `DisjointSet` of `len` elements, like [`with_len`], notifying `observer` about each join.
+    ///
+    /// [`with_len`]: DisjointSet::with_len
     ///
     /// # Examples
     ///
     /// ```
     /// use disjoint::DisjointSet;
     ///
-    /// let mut ds = DisjointSet::with_len(4);
-    /// assert_eq!(ds.len(), 4);
-    ///
-    /// ds.join(1, 3);
-    /// assert_eq!(ds.len(), 4);
+    /// let mut count = 0;
+    /// let mut ds = DisjointSet::with_len_and_observer(4, |_, _, _| count += 1);
+    /// ds.join(0, 1);
+    /// ds.join(2, 3);
+    /// ds.join(1, 0);
+    /// drop(ds);
+    /// assert_eq!(count, 2);
     /// ```
     #[inline]
     #[must_use]
-    pub fn len(&self) -> usize {
-        self.parents.len()
+    pub fn with_len_and_observer(len: usize, observer: O) -> Self {
+        Self::with_len_observer_and_strategies(len, observer, PathSplitting, ByRank)
     }
+}
 
-    /// Returns `true` if the disjoint set contains no elements.
+impl<F, L> DisjointSet<NoObserver, F, L> {
+    /// Creates a new `DisjointSet` of `len` elements, like [`with_len`], using the [`FindStrategy`] `find` and the [`LinkStrategy`] `link`.
+    ///
+    /// [`with_len`]: DisjointSet::with_len
     ///
     /// # Examples
     ///
     /// ```
-    /// use disjoint::DisjointSet;
+    /// use disjoint::{BySize, DisjointSet, PathHalving};
     ///
-    /// assert!(DisjointSet::new().is_empty());
-    /// assert!(DisjointSet::with_len(0).is_empty());
-    /// assert!(!DisjointSet::with_len(10).is_empty());
+    /// let mut ds = DisjointSet::with_len_and_strategies(4, PathHalving, BySize);
+    /// ds.join(0, 1);
+    /// ds.join(2, 1);
+    /// assert_eq!(ds.sets(), vec![vec![0, 1, 2], vec![3]]);
     /// ```
-    #[must_use]
     #[inline]
-    pub fn is_empty(&self) -> bool {
-        self.parents.is_empty()
+    #[must_use]
+    pub fn with_len_and_strategies(len: usize, find: F, link: L) -> Self {
+        Self::with_len_observer_and_strategies(len, NoObserver, find, link)
     }
+}
 
-    /// Constructs a new, empty `DisjointSet`.
+impl<O, F, L> DisjointSet<O, F, L> {
+    /// Creates a new `DisjointSet` of `len` elements, like [`with_len`], notifying `observer` about each join and using the [`FindStrategy`] `find` and the [`LinkStrategy`] `link`.
     ///
-    /// The disjoint set will not allocate until elements are added to it.
+    /// [`with_len`]: DisjointSet::with_len
     ///
     /// # Examples
     ///
     /// ```
-    /// # #![allow(unused_mut)]
-    /// let mut vec: Vec<i32> = Vec::new();
+    /// use disjoint::{ByIndex, DisjointSet, FullCompression};
+    ///
+    /// let mut roots = Vec::new();
+    /// let mut ds = DisjointSet::with_len_observer_and_strategies(
+    ///     3,
+    ///     |root, _, _| roots.push(root),
+    ///     FullCompression,
+    ///     ByIndex,
+    /// );
+    /// ds.join(2, 1);
+    /// ds.join(1, 0);
+    /// drop(ds);
+    /// assert_eq!(roots, vec![1, 0]);
     /// ```
-    #[must_use]
     #[inline]
-    #[allow(clippy::missing_const_for_fn)]
-    pub fn new() -> Self {
-        Self {
-            parents: Vec::new(),
-            ranks: Vec::new(),
-        }
+    #[must_use]
+    pub fn with_len_observer_and_strategies(len: usize, observer: O, find: F, link: L) -> Self {
+        // Every `len` fits into `usize`.
+        Self::with_len_in_bounds(len, observer, find, link, NoTracking)
     }
+}
 
-    /// Clears the `DisjointSet`.
+impl<S> DisjointSet<NoObserver, PathSplitting, ByRank, usize, S>
+where
+    S: Tracking,
+{
+    /// Creates a new `DisjointSet` of `len` elements, like [`with_len`], linking the members of each set if the [`Tracking`] `tracking` does.
     ///
-    /// The disjoint set will retain its capacity, so adding elements will not
-    /// allocate.
+    /// [`with_len`]: DisjointSet::with_len
     ///
     /// # Examples
     ///
     /// ```
-    /// use disjoint::DisjointSet;
+    /// use disjoint::{DisjointSet, MemberTracking};
     ///
-    /// let mut set = DisjointSet::new();
-    /// set.add_singleton();
-    /// set.add_singleton();
-    /// set.clear();
-    /// assert_eq!(set.len(), 0);
-    /// // Does not allocate!
-    /// set.add_singleton();
+    /// let mut ds = DisjointSet::with_len_and_tracking(4, MemberTracking);
+    /// ds.join(0, 2);
+    /// ds.join(3, 2);
+    /// assert_eq!(ds.size_of(0), 3);
+    /// assert_eq!(ds.members(1).collect::<Vec<_>>(), vec![1]);
     /// ```
     #[inline]
-    pub fn clear(&mut self) {
-        self.parents.clear();
-        self.ranks.clear();
+    #[must_use]
+    pub fn with_len_and_tracking(len: usize, tracking: S) -> Self {
+        Self::with_len_in_bounds(len, NoObserver, PathSplitting, ByRank, tracking)
     }
+}
 
-    /// Returns a `Vec` of all sets. Each entry corresponds to one set, and is a `Vec` of its elements.
+impl<I> DisjointSet<NoObserver, PathSplitting, ByRank, I>
+where
+    I: ElementIndex,
+{
+    /// Creates a new `DisjointSet` of `len` elements, like [`with_len`], storing the elements in the [`ElementIndex`] `I`.
     ///
-    /// The sets are ordered by their smallest contained element. The elements inside each sets are ordered.
+    /// [`with_len`]: DisjointSet::with_len
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`IndexOverflow`] if `len` exceeds [`MAX`](ElementIndex::MAX) of `I`.
     ///
     /// # Examples
     ///
     /// ```
-    /// use disjoint::DisjointSet;
+    /// use disjoint::CompactDisjointSet;
     ///
-    /// let mut ds = DisjointSet::with_len(4); // {0}, {1}, {2}, {3}
-    /// ds.join(3, 1); // {0}, {1, 3}, {2}
-    /// assert_eq!(ds.sets(), vec![vec![0], vec![1, 3], vec![2]]);
+    /// let mut ds = CompactDisjointSet::<u32>::try_with_len(4).unwrap();
+    /// ds.join(0, 3);
+    /// assert_eq!(ds.sets(), vec![vec![0, 3], vec![1], vec![2]]);
+    ///
+    /// assert!(CompactDisjointSet::<u16>::try_with_len(1 << 16).is_err());
     /// ```
-    #[must_use]
-    #[allow(clippy::missing_inline_in_public_items)]
-    pub fn sets(&self) -> Vec<Vec<usize>> {
-        let mut result = Vec::new();
-        let mut root_to_result_id = HashMap::new();
-
-        for index in 0..self.len() {
+    #[inline]
+    pub fn try_with_len(len: usize) -> Result<Self, IndexOverflow> {
+        Self::try_with_len_observer_and_strategies(len, NoObserver, PathSplitting, ByRank)
+    }
+}
+
+impl<O, F, L, I> DisjointSet<O, F, L, I>
+where
+    I: ElementIndex,
+{
+    /// Creates a new `DisjointSet` of `len` elements, like [`with_len_observer_and_strategies`], storing the elements in the [`ElementIndex`] `I`.
+    ///
+    /// [`with_len_observer_and_strategies`]: DisjointSet::with_len_observer_and_strategies
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`IndexOverflow`] if `len` exceeds [`MAX`](ElementIndex::MAX) of `I`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::{BySize, DisjointSet, FullCompression};
+    ///
+    /// let mut sizes = Vec::new();
+    /// let mut ds: DisjointSet<_, _, _, u16> = DisjointSet::try_with_len_observer_and_strategies(
+    ///     3,
+    ///     |_, _, size| sizes.push(size),
+    ///     FullCompression,
+    ///     BySize,
+    /// )
+    /// .unwrap();
+    /// ds.join(0, 1);
+    /// ds.join(1, 2);
+    /// drop(ds);
+    /// assert_eq!(sizes, vec![2, 3]);
+    /// ```
+    #[inline]
+    pub fn try_with_len_observer_and_strategies(
+        len: usize,
+        observer: O,
+        find: F,
+        link: L,
+    ) -> Result<Self, IndexOverflow> {
+        Self::try_with_len_observer_strategies_and_tracking(len, observer, find, link, NoTracking)
+    }
+}
+
+impl<O, F, L, I, S> DisjointSet<O, F, L, I, S>
+where
+    I: ElementIndex,
+    S: Tracking,
+{
+    /// Creates a new `DisjointSet` of `len` elements, like [`try_with_len_observer_and_strategies`], linking the members of each set if the [`Tracking`] `tracking` does.
+    ///
+    /// [`try_with_len_observer_and_strategies`]: DisjointSet::try_with_len_observer_and_strategies
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`IndexOverflow`] if `len` exceeds [`MAX`](ElementIndex::MAX) of `I`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::{ByIndex, DisjointSet, MemberTracking, NoObserver, PathHalving};
+    ///
+    /// let mut ds: DisjointSet<_, _, _, u32, _> =
+    ///     DisjointSet::try_with_len_observer_strategies_and_tracking(
+    ///         3,
+    ///         NoObserver,
+    ///         PathHalving,
+    ///         ByIndex,
+    ///         MemberTracking,
+    ///     )
+    ///     .unwrap();
+    /// ds.join(2, 1);
+    /// assert_eq!(ds.root_of(2), 1);
+    /// assert_eq!(ds.members(1).count(), 2);
+    /// ```
+    #[inline]
+    pub fn try_with_len_observer_strategies_and_tracking(
+        len: usize,
+        observer: O,
+        find: F,
+        link: L,
+        tracking: S,
+    ) -> Result<Self, IndexOverflow> {
+        if len > I::MAX {
+            return Err(IndexOverflow::new(I::MAX));
+        }
+        Ok(Self::with_len_in_bounds(
+            len, observer, find, link, tracking,
+        ))
+    }
+
+    /// Creates a new `DisjointSet` of `len` elements, where `len` is at most [`MAX`](ElementIndex::MAX) of `I`.
+    fn with_len_in_bounds(len: usize, observer: O, find: F, link: L, tracking: S) -> Self {
+        Self {
+            parents: (0..len)
+                .map(|element| Cell::new(I::from_usize(element)))
+                .collect(),
+            ranks: vec![0; len],
+            sizes: vec![I::from_usize(1); len],
+            nexts: if S::TRACKS_MEMBERS {
+                (0..len).map(I::from_usize).collect()
+            } else {
+                Vec::new()
+            },
+            number_sets: len,
+            largest_set: if len == 0 { None } else { Some(0) },
+            observer,
+            find,
+            link,
+            tracking,
+        }
+    }
+}
+
+impl<O, F, L, I, S> DisjointSet<O, F, L, I, S> {
+    /// Returns the number of disjoint subsets.
+    ///
+    /// This is tracked while modifying the disjoint set, so it takes constant time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::DisjointSet;
+    ///
+    /// let mut ds = DisjointSet::with_len(4); // {0}, {1}, {2}, {3}
+    /// assert_eq!(ds.num_sets(), 4);
+    ///
+    /// ds.join(0, 1); // {0, 1}, {2}, {3}
+    /// assert_eq!(ds.num_sets(), 3);
+    ///
+    /// ds.add_singleton(); // {0, 1}, {2}, {3}, {4}
+    /// assert_eq!(ds.num_sets(), 4);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn num_sets(&self) -> usize {
+        self.number_sets
+    }
+
+    /// Returns an element of a subset of maximum size, or `None` if the disjoint set is empty.
+    ///
+    /// Use [`size_of`] to get the size of that subset. This is tracked while modifying the disjoint set, so it takes constant time.
+    ///
+    /// # Important
+    ///
+    /// If there are several subsets of maximum size, the specific choice of the subset and of the returned element is an implementation detail.
+    ///
+    /// [`size_of`]: DisjointSet::size_of
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::DisjointSet;
+    ///
+    /// let mut ds = DisjointSet::with_len(5); // {0}, {1}, {2}, {3}, {4}
+    /// ds.join(0, 1); // {0, 1}, {2}, {3}, {4}
+    /// ds.join(2, 3); // {0, 1}, {2, 3}, {4}
+    /// ds.join(3, 4); // {0, 1}, {2, 3, 4}
+    ///
+    /// let largest = ds.largest_set().unwrap();
+    /// assert!(ds.is_joined(largest, 2));
+    /// assert_eq!(ds.size_of(largest), 3);
+    ///
+    /// assert_eq!(DisjointSet::new().largest_set(), None);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn largest_set(&self) -> Option<usize> {
+        self.largest_set
+    }
+
+    /// Returns a reference to the observer, which is notified about each join.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::{DisjointSet, NoObserver};
+    ///
+    /// let ds = DisjointSet::with_len(2);
+    /// assert_eq!(*ds.observer(), NoObserver);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn observer(&self) -> &O {
+        &self.observer
+    }
+
+    /// Returns a mutable reference to the observer, which is notified about each join.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::{DisjointSet, JoinObserver};
+    ///
+    /// #[derive(Default)]
+    /// struct Counter(usize);
+    ///
+    /// impl JoinObserver for Counter {
+    ///     fn on_join(&mut self, _: usize, _: usize, _: usize) {
+    ///         self.0 += 1;
+    ///     }
+    /// }
+    ///
+    /// let mut ds = DisjointSet::with_len_and_observer(3, Counter::default());
+    /// ds.join(0, 2);
+    /// assert_eq!(ds.observer().0, 1);
+    ///
+    /// ds.observer_mut().0 = 0;
+    /// ds.join(1, 2);
+    /// assert_eq!(ds.observer().0, 1);
+    /// ```
+    #[inline]
+    // Mutable references in `const fn` are not available on old Rust versions.
+    #[allow(clippy::missing_const_for_fn)]
+    pub fn observer_mut(&mut self) -> &mut O {
+        &mut self.observer
+    }
+
+    /// Returns a reference to the [`FindStrategy`] of [`root_of`].
+    ///
+    /// [`root_of`]: DisjointSet::root_of
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::{DisjointSet, PathSplitting};
+    ///
+    /// assert_eq!(*DisjointSet::new().find_strategy(), PathSplitting);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn find_strategy(&self) -> &F {
+        &self.find
+    }
+
+    /// Returns a reference to the [`LinkStrategy`] of [`join`].
+    ///
+    /// [`join`]: DisjointSet::join
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::{ByRank, DisjointSet};
+    ///
+    /// assert_eq!(*DisjointSet::new().link_strategy(), ByRank);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn link_strategy(&self) -> &L {
+        &self.link
+    }
+
+    /// Returns a reference to the [`Tracking`] of the members of each set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::{DisjointSet, NoTracking};
+    ///
+    /// assert_eq!(*DisjointSet::new().tracking(), NoTracking);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn tracking(&self) -> &S {
+        &self.tracking
+    }
+}
+
+impl<O, F, L, I, S> DisjointSet<O, F, L, I, S>
+where
+    F: FindStrategy,
+    I: ElementIndex,
+{
+    #[inline]
+    #[must_use]
+    fn get_parent(&self, id: usize) -> usize {
+        self.parents[id].get().to_usize()
+    }
+
+    #[inline]
+    fn set_parent(&self, id: usize, new: usize) {
+        self.parents[id].set(I::from_usize(new));
+    }
+
+    /// Returns an element of the subset containing `child`.
+    /// This exact element is returned for every member of the subset.
+    ///
+    /// # Important
+    ///
+    /// The specific choice of the returned element is an implementation detail.
+    /// There are no further guarantees beyond what is documented here.
+    /// If you just want to check if two elements are in the same subset, use [`is_joined`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::DisjointSet;
+    ///
+    /// let mut ds = DisjointSet::with_len(3); // {0}, {1}, {2}
+    /// assert_eq!(ds.root_of(0), 0);
+    /// assert_eq!(ds.root_of(1), 1);
+    /// assert_eq!(ds.root_of(2), 2);
+    ///
+    ///
+    /// ds.join(0, 1); // {0, 1}, {2}
+    /// assert_eq!(ds.root_of(0), ds.root_of(1));
+    /// assert_ne!(ds.root_of(0), ds.root_of(2));
+    ///
+    /// ds.join(1, 2); // {0, 1, 2}
+    /// assert_eq!(ds.root_of(0), ds.root_of(1));
+    /// assert_eq!(ds.root_of(0), ds.root_of(2));
+    /// ```
+    ///
+    /// [`is_joined`]: DisjointSet::is_joined
+    #[inline]
+    #[must_use]
+    pub fn root_of(&self, child: usize) -> usize {
+        self.find.find(&self.parents, child)
+    }
+
+    /// Adds a new element, not joined to any other element. Returns the index
+    /// of the new element.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new capacity exceeds `isize::MAX` bytes, or if the new element does not fit into the index type `I`. Use [`try_add_singleton`] to handle the latter.
+    ///
+    /// [`try_add_singleton`]: DisjointSet::try_add_singleton
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::DisjointSet;
+    ///
+    /// let mut ds = DisjointSet::with_len(1);
+    /// assert_eq!(ds.add_singleton(), 1);
+    /// assert_eq!(ds.len(), 2);
+    /// assert!(!ds.is_joined(0, 1));
+    /// ```
+    #[inline]
+    pub fn add_singleton(&mut self) -> usize
+    where
+        S: Tracking,
+    {
+        let id = self.len();
+        assert!(id < I::MAX, "too many elements for the index type");
+        self.parents.push(Cell::new(I::from_usize(id)));
+        self.ranks.push(0);
+        self.sizes.push(I::from_usize(1));
+        if S::TRACKS_MEMBERS {
+            self.nexts.push(I::from_usize(id));
+        }
+        self.number_sets += 1;
+        if self.largest_set.is_none() {
+            self.largest_set = Some(id);
+        }
+        id
+    }
+
+    /// Adds a new element like [`add_singleton`], unless it does not fit into the index type `I`. Returns the index of the new element.
+    ///
+    /// [`add_singleton`]: DisjointSet::add_singleton
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`IndexOverflow`] if there are already [`MAX`](ElementIndex::MAX) elements, leaving `self` unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::CompactDisjointSet;
+    ///
+    /// let mut ds = CompactDisjointSet::<u16>::try_with_len(65_534).unwrap();
+    /// assert_eq!(ds.try_add_singleton(), Ok(65_534));
+    ///
+    /// let error = ds.try_add_singleton().unwrap_err();
+    /// assert_eq!(error.max_len(), 65_535);
+    /// assert_eq!(ds.len(), 65_535);
+    /// ```
+    #[inline]
+    pub fn try_add_singleton(&mut self) -> Result<usize, IndexOverflow>
+    where
+        S: Tracking,
+    {
+        if self.len() >= I::MAX {
+            return Err(IndexOverflow::new(I::MAX));
+        }
+        Ok(self.add_singleton())
+    }
+
+    /// Moves all elements of `other` into `self`, keeping their sets. Returns the offset that was added to the indices of the elements of `other`, which is the previous [`len`] of `self`.
+    ///
+    /// The elements of `other` are not joined to any element of `self`. This takes time linear in the length of `other`.
+    ///
+    /// [`len`]: DisjointSet::len
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new capacity exceeds `isize::MAX` bytes, or if the elements do not fit into the index type `I`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::{disjoint_set, DisjointSet};
+    ///
+    /// let mut ds = disjoint_set![[0, 1], [2]];
+    /// let offset = ds.append(disjoint_set![[0, 2], [1]]);
+    /// assert_eq!(offset, 3);
+    /// assert_eq!(ds, disjoint_set![[0, 1], [2], [3, 5], [4]]);
+    /// ```
+    #[allow(clippy::missing_inline_in_public_items)]
+    // `Option::is_none_or` is not available on old Rust versions.
+    #[allow(clippy::unnecessary_map_or)]
+    pub fn append<P, Q>(&mut self, other: DisjointSet<P, Q, L, I, S>) -> usize
+    where
+        Q: FindStrategy,
+    {
+        let offset = self.len();
+        assert!(
+            other.len() <= I::MAX - offset,
+            "too many elements for the index type"
+        );
+
+        if let Some(other_largest_set) = other.largest_set {
+            let is_larger = self.largest_set.map_or(true, |largest_set| {
+                other.size_of(other_largest_set) > self.size_of(largest_set)
+            });
+            if is_larger {
+                self.largest_set = Some(other_largest_set + offset);
+            }
+        }
+
+        self.parents.extend(
+            other
+                .parents
+                .into_iter()
+                .map(|parent| Cell::new(I::from_usize(parent.into_inner().to_usize() + offset))),
+        );
+        self.ranks.extend(other.ranks);
+        self.sizes.extend(other.sizes);
+        self.nexts.extend(
+            other
+                .nexts
+                .into_iter()
+                .map(|next| I::from_usize(next.to_usize() + offset)),
+        );
+        self.number_sets += other.number_sets;
+
+        offset
+    }
+
+    /// Returns `true` if `first_element` and `second_element` are in the same subset.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `first_element` or `second_element` is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::DisjointSet;
+    ///
+    /// // Initially, elements are only joined to themselves.
+    /// let mut ds = DisjointSet::with_len(3); // {0}, {1}, {2}
+    /// assert!(ds.is_joined(0, 0));
+    /// assert!(!ds.is_joined(0, 1));
+    /// assert!(!ds.is_joined(0, 2));
+    ///
+    /// // By joining 1 to 0, we implicitely join 0 to 1.
+    /// ds.join(1, 0); // {0, 1}, {2}
+    /// assert!(ds.is_joined(1, 0));
+    /// assert!(ds.is_joined(0, 1));
+    ///
+    /// // By joining 0 to 1 and 1 to 2, we implicitely join 0 to 2.
+    /// ds.join(1, 2); // {0, 1, 2}
+    /// assert!(ds.is_joined(0, 2));
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn is_joined(&self, first_element: usize, second_element: usize) -> bool {
+        self.root_of(first_element) == self.root_of(second_element)
+    }
+
+    /// Returns the number of elements in the disjoint set, regardless of how they are joined together.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::DisjointSet;
+    ///
+    /// let mut ds = DisjointSet::with_len(4);
+    /// assert_eq!(ds.len(), 4);
+    ///
+    /// ds.join(1, 3);
+    /// assert_eq!(ds.len(), 4);
+    /// ```
+    #[inline]
+    #[must_use]
+    #[allow(clippy::missing_const_for_fn)]
+    pub fn len(&self) -> usize {
+        self.parents.len()
+    }
+
+    /// Returns `true` if the disjoint set contains no elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::DisjointSet;
+    ///
+    /// assert!(DisjointSet::new().is_empty());
+    /// assert!(DisjointSet::with_len(0).is_empty());
+    /// assert!(!DisjointSet::with_len(10).is_empty());
+    /// ```
+    #[must_use]
+    #[inline]
+    #[allow(clippy::missing_const_for_fn)]
+    pub fn is_empty(&self) -> bool {
+        self.parents.is_empty()
+    }
+
+    /// Clears the `DisjointSet`.
+    ///
+    /// The disjoint set will retain its capacity, so adding elements will not
+    /// allocate.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::DisjointSet;
+    ///
+    /// let mut set = DisjointSet::new();
+    /// set.add_singleton();
+    /// set.add_singleton();
+    /// set.clear();
+    /// assert_eq!(set.len(), 0);
+    /// // Does not allocate!
+    /// set.add_singleton();
+    /// ```
+    #[inline]
+    pub fn clear(&mut self) {
+        self.parents.clear();
+        self.ranks.clear();
+        self.sizes.clear();
+        self.nexts.clear();
+        self.number_sets = 0;
+        self.largest_set = None;
+    }
+
+    /// Returns a `Vec` of all sets. Each entry corresponds to one set, and is a `Vec` of its elements.
+    ///
+    /// The sets are ordered by their smallest contained element. The elements inside each sets are ordered.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::DisjointSet;
+    ///
+    /// let mut ds = DisjointSet::with_len(4); // {0}, {1}, {2}, {3}
+    /// ds.join(3, 1); // {0}, {1, 3}, {2}
+    /// assert_eq!(ds.sets(), vec![vec![0], vec![1, 3], vec![2]]);
+    /// ```
+    #[must_use]
+    #[allow(clippy::missing_inline_in_public_items)]
+    pub fn sets(&self) -> Vec<Vec<usize>> {
+        let mut result = Vec::new();
+        let mut root_to_result_id = HashMap::new();
+
+        for index in 0..self.len() {
             let root = self.root_of(index);
             let &mut result_id = root_to_result_id.entry(root).or_insert_with(|| {
                 let id = result.len();
                 result.push(Vec::with_capacity(1));
                 id
             });
-            result[result_id].push(index);
+            result[result_id].push(index);
+        }
+
+        result
+    }
+
+    /// Returns the label of each element, and the number of labels.
+    ///
+    /// Two elements have the same label if and only if they are joined. The labels are numbered from `0` to the number of sets minus one, in the same order as the sets returned by [`sets`].
+    /// So the label of an element is the position of its set in [`sets`].
+    ///
+    /// [`sets`]: DisjointSet::sets
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::DisjointSet;
+    ///
+    /// let mut ds = DisjointSet::with_len(5); // {0}, {1}, {2}, {3}, {4}
+    /// ds.join(4, 1); // {0}, {1, 4}, {2}, {3}
+    /// ds.join(3, 0); // {0, 3}, {1, 4}, {2}
+    /// assert_eq!(ds.labels(), (vec![0, 1, 2, 0, 1], 3));
+    /// ```
+    #[must_use]
+    #[allow(clippy::missing_inline_in_public_items)]
+    pub fn labels(&self) -> (Vec<usize>, usize) {
+        let mut labels = Vec::with_capacity(self.len());
+        let mut root_to_label = vec![None; self.len()];
+        let mut number_labels = 0;
+
+        for index in 0..self.len() {
+            let label = root_to_label[self.root_of(index)].get_or_insert_with(|| {
+                number_labels += 1;
+                number_labels - 1
+            });
+            labels.push(*label);
+        }
+
+        (labels, number_labels)
+    }
+
+    /// Returns `true` if every set of `self` is contained in a set of `other`, assuming both have the same length.
+    ///
+    /// This is the case if and only if mapping the root of each element in `self` to its root in `other` is a function.
+    fn refines<P, Q, R, J, T>(&self, other: &DisjointSet<P, Q, R, J, T>) -> bool
+    where
+        Q: FindStrategy,
+        J: ElementIndex,
+    {
+        let mut self_root_to_other_root = HashMap::with_capacity(self.len());
+
+        for i in 0..self.len() {
+            let self_root = self.root_of(i);
+            let other_root = other.root_of(i);
+
+            match self_root_to_other_root.entry(self_root) {
+                Entry::Occupied(entry) => {
+                    if other_root != *entry.get() {
+                        return false;
+                    }
+                }
+                Entry::Vacant(entry) => {
+                    entry.insert(other_root);
+                }
+            }
+        }
+
+        true
+    }
+
+    /// Returns `true` if `self` is a refinement of `other`, meaning that every set of `self` is contained in a set of `other`.
+    ///
+    /// Equivalently, any two elements that are joined in `self` are also joined in `other`. Every partition is a refinement of itself.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `self` and `other` have different lengths.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::disjoint_set;
+    ///
+    /// let fine = disjoint_set![[0, 1], [2], [3]];
+    /// let coarse = disjoint_set![[0, 1, 3], [2]];
+    /// assert!(fine.is_refinement_of(&coarse));
+    /// assert!(!coarse.is_refinement_of(&fine));
+    /// assert!(fine.is_refinement_of(&fine));
+    /// ```
+    #[must_use]
+    #[allow(clippy::missing_inline_in_public_items)]
+    pub fn is_refinement_of<P, Q, R, J, T>(&self, other: &DisjointSet<P, Q, R, J, T>) -> bool
+    where
+        Q: FindStrategy,
+        J: ElementIndex,
+    {
+        assert_eq!(self.len(), other.len(), "lengths differ");
+        self.refines(other)
+    }
+
+    /// Returns the difference between the partitions `old` and `new`, reporting which sets were merged and which elements moved.
+    ///
+    /// `new` may contain more elements than `old`, like when taking a snapshot before adding elements via [`add_singleton`]. See [`PartitionDiff`] for details.
+    ///
+    /// [`add_singleton`]: DisjointSet::add_singleton
+    /// [`PartitionDiff`]: crate::PartitionDiff
+    ///
+    /// # Panics
+    ///
+    /// Panics if `new` has fewer elements than `old`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::DisjointSet;
+    ///
+    /// let old = DisjointSet::with_len(4); // {0}, {1}, {2}, {3}
+    /// let mut new = old.clone();
+    /// new.join(0, 2); // {0, 2}, {1}, {3}
+    ///
+    /// let diff = DisjointSet::diff(&old, &new);
+    /// let merge = diff.merges().next().unwrap();
+    /// assert_eq!(merge.sources(), &[vec![0], vec![2]]);
+    /// assert_eq!(merge.target(), &[0, 2]);
+    /// assert_eq!(diff.to_string(), "merged {0} and {2} into {0, 2}");
+    /// ```
+    #[must_use]
+    #[allow(clippy::missing_inline_in_public_items)]
+    pub fn diff(old: &Self, new: &Self) -> PartitionDiff {
+        PartitionDiff::new(old, new)
+    }
+
+    /// Returns the coarsest common refinement of `self` and `other`.
+    ///
+    /// In the result, two elements are joined if and only if they are joined both in `self` and in `other`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `self` and `other` have different lengths.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::disjoint_set;
+    ///
+    /// let first = disjoint_set![[0, 1, 2], [3, 4]];
+    /// let second = disjoint_set![[0, 1], [2, 3, 4]];
+    /// assert_eq!(first.intersection(&second), disjoint_set![[0, 1], [2], [3, 4]]);
+    /// ```
+    #[must_use]
+    #[allow(clippy::missing_inline_in_public_items)]
+    pub fn intersection<P, Q, R, J, T>(&self, other: &DisjointSet<P, Q, R, J, T>) -> DisjointSet
+    where
+        Q: FindStrategy,
+        J: ElementIndex,
+    {
+        assert_eq!(self.len(), other.len(), "lengths differ");
+
+        let mut result = DisjointSet::with_len(self.len());
+        let mut roots_to_element = HashMap::new();
+
+        for element in 0..self.len() {
+            match roots_to_element.entry((self.root_of(element), other.root_of(element))) {
+                Entry::Occupied(entry) => {
+                    result.join(*entry.get(), element);
+                }
+                Entry::Vacant(entry) => {
+                    entry.insert(element);
+                }
+            }
+        }
+
+        result
+    }
+
+    /// Returns the partition induced on `elements`, where the element `i` of the result corresponds to `elements[i]`.
+    ///
+    /// Two elements of the result are joined if and only if the corresponding elements are joined in `self`.
+    /// `elements` may contain duplicates, which are joined to each other.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any of `elements` is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::disjoint_set;
+    ///
+    /// let ds = disjoint_set![[0, 3], [1, 4], [2]];
+    /// assert_eq!(ds.restrict(&[4, 3, 0]), disjoint_set![[0], [1, 2]]);
+    /// ```
+    #[must_use]
+    #[allow(clippy::missing_inline_in_public_items)]
+    pub fn restrict(&self, elements: &[usize]) -> DisjointSet {
+        let roots: Vec<_> = elements
+            .iter()
+            .map(|&element| self.root_of(element))
+            .collect();
+        DisjointSet::from_labels(&roots)
+    }
+
+    /// Returns the partition with the elements renamed by `permutation`, so the element `i` becomes `permutation[i]`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `permutation` does not have the same length as `self`, or if it is not a permutation of the elements from `0` to `n - 1`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::disjoint_set;
+    ///
+    /// let ds = disjoint_set![[0, 1], [2], [3]];
+    /// assert_eq!(ds.permute(&[2, 3, 0, 1]), disjoint_set![[0], [1], [2, 3]]);
+    /// ```
+    #[must_use]
+    #[allow(clippy::missing_inline_in_public_items)]
+    pub fn permute(&self, permutation: &[usize]) -> DisjointSet {
+        assert_eq!(
+            permutation.len(),
+            self.len(),
+            "permutation has wrong length"
+        );
+        self.restrict(&inverse_permutation(permutation))
+    }
+
+    /// Returns the quotient of the partition under `map`, which maps each element into a universe of `len` elements.
+    ///
+    /// In the result, `map(x)` and `map(y)` are joined for all elements `x` and `y` that are joined in `self`, and this is the finest such partition.
+    /// Elements of the new universe that are not mapped to are singletons. Mapping several sets to the same element merges them.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `map` returns an element that is not smaller than `len`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::disjoint_set;
+    ///
+    /// let ds = disjoint_set![[0, 2], [1], [3], [4, 5]];
+    /// let halved = ds.quotient(3, |element| element / 2);
+    /// assert_eq!(halved, disjoint_set![[0, 1], [2]]);
+    /// ```
+    #[must_use]
+    #[allow(clippy::missing_inline_in_public_items)]
+    pub fn quotient<M>(&self, len: usize, mut map: M) -> DisjointSet
+    where
+        M: FnMut(usize) -> usize,
+    {
+        let mut result = DisjointSet::with_len(len);
+        let mut root_to_image = HashMap::new();
+
+        for element in 0..self.len() {
+            let image = map(element);
+            assert!(image < len, "image out of bounds");
+            match root_to_image.entry(self.root_of(element)) {
+                Entry::Occupied(entry) => {
+                    result.join(*entry.get(), image);
+                }
+                Entry::Vacant(entry) => {
+                    entry.insert(image);
+                }
+            }
+        }
+
+        result
+    }
+
+    /// Returns an iterator over all elements in the subset containing `element`, including `element` itself.
+    ///
+    /// The iterator starts with `element`. The order of the remaining elements is an implementation detail.
+    /// It runs in time proportional to the size of the subset, unlike filtering [`sets`], but requires [`MemberTracking`].
+    ///
+    /// [`sets`]: DisjointSet::sets
+    /// [`MemberTracking`]: crate::MemberTracking
+    ///
+    /// # Panics
+    ///
+    /// Panics if `element` is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::{DisjointSet, MemberTracking};
+    ///
+    /// let mut ds = DisjointSet::with_len_and_tracking(5, MemberTracking); // {0}, {1}, {2}, {3}, {4}
+    /// ds.join(0, 3); // {0, 3}, {1}, {2}, {4}
+    /// ds.join(4, 3); // {0, 3, 4}, {1}, {2}
+    ///
+    /// let mut members: Vec<_> = ds.members(4).collect();
+    /// members.sort();
+    /// assert_eq!(members, vec![0, 3, 4]);
+    /// assert_eq!(ds.members(2).collect::<Vec<_>>(), vec![2]);
+    /// ```
+    #[inline]
+    pub fn members(&self, element: usize) -> impl Iterator<Item = usize> + '_
+    where
+        S: TracksMembers,
+    {
+        assert!(element < self.len(), "element out of bounds");
+        Members {
+            nexts: &self.nexts,
+            start: element,
+            current: Some(element),
+        }
+    }
+
+    /// Returns the number of elements in the subset containing `element`, including `element` itself.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `element` is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::DisjointSet;
+    ///
+    /// let mut ds = DisjointSet::with_len(4); // {0}, {1}, {2}, {3}
+    /// assert_eq!(ds.size_of(0), 1);
+    ///
+    /// ds.join(0, 1); // {0, 1}, {2}, {3}
+    /// ds.join(1, 3); // {0, 1, 3}, {2}
+    /// assert_eq!(ds.size_of(0), 3);
+    /// assert_eq!(ds.size_of(3), 3);
+    /// assert_eq!(ds.size_of(2), 1);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn size_of(&self, element: usize) -> usize {
+        self.sizes[self.root_of(element)].to_usize()
+    }
+
+    /// Returns how many subsets there are of each size, as a map from the size to the number of subsets with that size.
+    ///
+    /// Only sizes of at least one subset are contained in the map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::DisjointSet;
+    ///
+    /// let mut ds = DisjointSet::with_len(6); // {0}, {1}, {2}, {3}, {4}, {5}
+    /// ds.join(0, 1); // {0, 1}, {2}, {3}, {4}, {5}
+    /// ds.join(2, 3); // {0, 1}, {2, 3}, {4}, {5}
+    /// ds.join(3, 4); // {0, 1}, {2, 3, 4}, {5}
+    ///
+    /// let histogram = ds.size_histogram();
+    /// assert_eq!(histogram.into_iter().collect::<Vec<_>>(), vec![(1, 1), (2, 1), (3, 1)]);
+    /// ```
+    #[must_use]
+    #[allow(clippy::missing_inline_in_public_items)]
+    pub fn size_histogram(&self) -> BTreeMap<usize, usize> {
+        let mut result = BTreeMap::new();
+
+        for (index, size) in self.sizes.iter().enumerate() {
+            if self.get_parent(index) == index {
+                *result.entry(size.to_usize()).or_insert(0) += 1;
+            }
+        }
+
+        result
+    }
+
+    /// Returns the parent of `element` in the internal forest, or `element` itself if it is a root.
+    ///
+    /// Unlike [`root_of`], this does not compress any paths.
+    ///
+    /// # Important
+    ///
+    /// The forest is an implementation detail and only meant for debugging and benchmarking.
+    /// Its shape is not guaranteed to stay the same between versions.
+    ///
+    /// [`root_of`]: DisjointSet::root_of
+    ///
+    /// # Panics
+    ///
+    /// Panics if `element` is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::DisjointSet;
+    ///
+    /// let mut ds = DisjointSet::with_len(3); // {0}, {1}, {2}
+    /// assert_eq!(ds.parent_of(1), 1);
+    ///
+    /// ds.join(0, 1); // {0, 1}, {2}
+    /// let root = ds.root_of(1);
+    /// assert_eq!(ds.parent_of(root), root);
+    /// assert_eq!(ds.parent_of(0), root);
+    /// assert_eq!(ds.parent_of(1), root);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn parent_of(&self, element: usize) -> usize {
+        self.get_parent(element)
+    }
+
+    /// Returns the rank of `element`, an upper bound for the height of its subtree in the internal forest.
+    ///
+    /// # Important
+    ///
+    /// The forest is an implementation detail and only meant for debugging and benchmarking.
+    /// Ranks of elements that are no longer roots are not updated, and only link strategies like [`ByRank`] maintain ranks at all.
+    ///
+    /// [`ByRank`]: crate::ByRank
+    ///
+    /// # Panics
+    ///
+    /// Panics if `element` is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::DisjointSet;
+    ///
+    /// let mut ds = DisjointSet::with_len(3); // {0}, {1}, {2}
+    /// assert_eq!(ds.rank_of(0), 0);
+    ///
+    /// ds.join(0, 1); // {0, 1}, {2}
+    /// assert_eq!(ds.rank_of(ds.root_of(0)), 1);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn rank_of(&self, element: usize) -> u8 {
+        self.ranks[element]
+    }
+
+    /// Returns the number of parent links from `element` to its root in the internal forest, without compressing any paths.
+    ///
+    /// # Important
+    ///
+    /// The forest is an implementation detail and only meant for debugging and benchmarking.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `element` is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::DisjointSet;
+    ///
+    /// let mut ds = DisjointSet::with_len(3); // {0}, {1}, {2}
+    /// ds.join(0, 1); // {0, 1}, {2}
+    ///
+    /// let root = ds.root_of(0);
+    /// assert_eq!(ds.depth_of(root), 0);
+    /// assert_eq!(ds.depth_of(if root == 0 { 1 } else { 0 }), 1);
+    /// assert_eq!(ds.depth_of(2), 0);
+    /// ```
+    #[must_use]
+    #[allow(clippy::missing_inline_in_public_items)]
+    pub fn depth_of(&self, mut element: usize) -> usize {
+        let mut depth = 0;
+        loop {
+            let parent = self.get_parent(element);
+            if parent == element {
+                return depth;
+            }
+            element = parent;
+            depth += 1;
         }
+    }
+
+    /// Returns the maximum [`depth_of`] any element, the height of the highest tree in the internal forest, or `0` if the disjoint set is empty.
+    ///
+    /// # Important
+    ///
+    /// The forest is an implementation detail and only meant for debugging and benchmarking.
+    ///
+    /// [`depth_of`]: DisjointSet::depth_of
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::DisjointSet;
+    ///
+    /// let mut ds = DisjointSet::with_len(4); // {0}, {1}, {2}, {3}
+    /// assert_eq!(ds.max_height(), 0);
+    ///
+    /// ds.join(0, 1); // {0, 1}, {2}, {3}
+    /// ds.join(2, 3); // {0, 1}, {2, 3}
+    /// ds.join(1, 3); // {0, 1, 2, 3}
+    /// assert_eq!(ds.max_height(), 2);
+    /// ```
+    #[must_use]
+    #[allow(clippy::missing_inline_in_public_items)]
+    pub fn max_height(&self) -> usize {
+        (0..self.len())
+            .map(|element| self.depth_of(element))
+            .max()
+            .unwrap_or(0)
+    }
 
-        result
+    /// Returns the internal forest in the DOT language of Graphviz, with an edge from each non-root element to its parent.
+    ///
+    /// Roots are drawn as double circles.
+    ///
+    /// # Important
+    ///
+    /// The forest is an implementation detail and only meant for debugging and benchmarking.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::DisjointSet;
+    ///
+    /// let mut ds = DisjointSet::with_len(3); // {0}, {1}, {2}
+    /// ds.join(0, 1); // {0, 1}, {2}
+    ///
+    /// assert_eq!(
+    ///     ds.to_dot(),
+    ///     "digraph {\n    0 [shape=doublecircle];\n    1 -> 0;\n    2 [shape=doublecircle];\n}\n"
+    /// );
+    /// ```
+    #[must_use]
+    #[allow(clippy::missing_inline_in_public_items)]
+    pub fn to_dot(&self) -> String {
+        display::forest_to_dot(self)
+    }
+
+    /// Writes the partition into `writer`, using a compact, versioned binary format.
+    ///
+    /// The format consists of a magic header and version, the number of elements, the canonical label of each element, and a checksum.
+    /// Integers are encoded as varints, and the canonical label of an element is the position of its set in [`sets`].
+    /// So the output only depends on the partition, not on the internal forest.
+    ///
+    /// The data is streamed into `writer` in small chunks. Apart from these, a map from each root to its label is held in memory, so the extra memory grows with the number of sets, not the number of elements.
+    ///
+    /// [`sets`]: DisjointSet::sets
+    ///
+    /// # Errors
+    ///
+    /// Returns any error that occurs while writing to `writer`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::DisjointSet;
+    ///
+    /// let mut ds = DisjointSet::with_len(4);
+    /// ds.join(3, 1);
+    ///
+    /// let mut bytes = Vec::new();
+    /// ds.write_to(&mut bytes).unwrap();
+    ///
+    /// let read = DisjointSet::read_from(&bytes[..]).unwrap();
+    /// assert_eq!(read, ds);
+    /// ```
+    #[allow(clippy::missing_inline_in_public_items)]
+    pub fn write_to<W>(&self, writer: W) -> io::Result<()>
+    where
+        W: Write,
+    {
+        binary_format::write_disjoint_set(self, writer)
+    }
+
+    /// Replaces the partition with the one of `other`, keeping the observer, the strategies and the tracking.
+    pub(crate) fn replace_partition<P, Q, T>(&mut self, other: DisjointSet<P, Q, L, I, T>)
+    where
+        S: Tracking,
+    {
+        self.parents = other.parents;
+        self.ranks = other.ranks;
+        self.sizes = other.sizes;
+        self.number_sets = other.number_sets;
+        self.largest_set = other.largest_set;
+
+        // `other` might not link its members, so the links are recomputed from the forest.
+        self.nexts.clear();
+        if S::TRACKS_MEMBERS {
+            let len = self.len();
+            self.nexts = (0..len).map(I::from_usize).collect();
+            for element in 0..len {
+                // Inserts `element` into the cycle of its root, right after the root.
+                let root = self.root_of(element);
+                self.nexts.swap(element, root);
+            }
+        }
     }
 }
 
-impl PartialEq for DisjointSet {
-    #[must_use]
+impl<O, F, L, I, S> DisjointSet<O, F, L, I, S>
+where
+    F: FindStrategy,
+    L: LinkStrategy,
+    I: ElementIndex,
+{
+    /// Checks that the internal forest is consistent, returning the first [`InvariantViolation`] found.
+    ///
+    /// This verifies that there is one rank per element, that every parent is in bounds, that walking the parent links from every element reaches a root, and that ranks strictly increase along parent links.
+    /// The ranks are only checked if the [`LinkStrategy`] [maintains them](LinkStrategy::MAINTAINS_RANKS).
+    /// It is meant for tests and fuzzing, since all methods of `DisjointSet` uphold these invariants.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`InvariantViolation`] describing the violated invariant and where it was violated.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::DisjointSet;
+    ///
+    /// let mut ds = DisjointSet::with_len(4);
+    /// ds.join(0, 1);
+    /// ds.join(3, 1);
+    /// assert_eq!(ds.check_invariants(), Ok(()));
+    /// ```
     #[allow(clippy::missing_inline_in_public_items)]
-    fn eq(&self, other: &Self) -> bool {
-        if self.len() != other.len() {
-            return false;
+    pub fn check_invariants(&self) -> Result<(), InvariantViolation> {
+        /// The state of an element while searching for cycles.
+        #[derive(Clone, Copy, PartialEq, Eq)]
+        enum Visit {
+            New,
+            OnPath,
+            Done,
         }
 
-        let mut self_root_to_other_root = HashMap::with_capacity(self.len());
+        let len = self.parents.len();
+        if self.ranks.len() != len {
+            return Err(InvariantViolation::LengthMismatch {
+                parents: len,
+                ranks: self.ranks.len(),
+            });
+        }
 
-        for i in 0..self.len() {
-            let self_root = self.root_of(i);
-            let other_root = other.root_of(i);
+        for element in 0..len {
+            let parent = self.get_parent(element);
+            if parent >= len {
+                return Err(InvariantViolation::ParentOutOfBounds {
+                    element,
+                    parent,
+                    len,
+                });
+            }
+        }
 
-            match self_root_to_other_root.entry(self_root) {
-                Entry::Occupied(entry) => {
-                    if other_root != *entry.get() {
-                        return false;
-                    }
+        let mut visits = vec![Visit::New; len];
+        for start in 0..len {
+            let mut path = Vec::new();
+            let mut element = start;
+            while visits[element] == Visit::New {
+                visits[element] = Visit::OnPath;
+                path.push(element);
+                let parent = self.get_parent(element);
+                if parent == element {
+                    break;
                 }
-                Entry::Vacant(entry) => {
-                    entry.insert(other_root);
+                element = parent;
+            }
+            if visits[element] == Visit::OnPath && self.get_parent(element) != element {
+                return Err(InvariantViolation::Cycle { element });
+            }
+            for on_path in path {
+                visits[on_path] = Visit::Done;
+            }
+        }
+
+        for element in 0..len {
+            let parent = self.get_parent(element);
+            if L::MAINTAINS_RANKS && parent != element && self.ranks[element] >= self.ranks[parent]
+            {
+                return Err(InvariantViolation::RankNotIncreasing { element, parent });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<O, F, L, I, S> DisjointSet<O, F, L, I, S>
+where
+    O: JoinObserver,
+    F: FindStrategy,
+    L: LinkStrategy,
+    I: ElementIndex,
+    S: Tracking,
+{
+    /// If `first_element` and `second_element` are in different sets, joins them together and returns `true`.
+    ///
+    /// Otherwise, does nothing and returns `false`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `first_element` or `second_element` is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::DisjointSet;
+    ///
+    /// // Initially, each element is in its own set.
+    /// let mut ds = DisjointSet::with_len(4); // {0}, {1}, {2}, {3}
+    /// assert!(!ds.is_joined(0, 3));
+    ///
+    /// // By joining 0 to 1 and 2 to 3, we get two sets of two elements each.
+    /// ds.join(0, 1); // {0, 1}, {2}, {3}
+    /// ds.join(2, 3); // {0, 1}, {2, 3}
+    /// assert!(ds.is_joined(0, 1));
+    /// assert!(ds.is_joined(2, 3));
+    /// assert!(!ds.is_joined(0, 3));
+    ///
+    /// // By further joining 2 to 3, all elements are now in the same set.
+    /// ds.join(1, 2); // {0, 1, 2, 3}
+    /// assert!(ds.is_joined(0, 3));
+    /// ```
+    #[inline]
+    pub fn join(&mut self, first_element: usize, second_element: usize) -> bool {
+        fn slow_path<O, F, L, I, S>(
+            ds: &mut DisjointSet<O, F, L, I, S>,
+            first_element: usize,
+            second_element: usize,
+        ) -> bool
+        where
+            O: JoinObserver,
+            F: FindStrategy,
+            L: LinkStrategy,
+            I: ElementIndex,
+            S: Tracking,
+        {
+            let root_first = ds.root_of(first_element);
+            let root_second = ds.root_of(second_element);
+
+            if root_first == root_second {
+                return false;
+            }
+
+            let (child, parent) =
+                if ds
+                    .link
+                    .keeps_first(root_first, root_second, &mut ds.ranks, &ds.sizes)
+                {
+                    (root_second, root_first)
+                } else {
+                    (root_first, root_second)
+                };
+            ds.set_parent(child, parent);
+            if S::TRACKS_MEMBERS {
+                // Splice the two circular member lists together.
+                ds.nexts.swap(child, parent);
+            }
+
+            let size = ds.sizes[parent].to_usize() + ds.sizes[child].to_usize();
+            ds.sizes[parent] = I::from_usize(size);
+            ds.number_sets -= 1;
+            if let Some(largest_set) = ds.largest_set {
+                if size > ds.size_of(largest_set) {
+                    ds.largest_set = Some(parent);
                 }
             }
+            ds.observer.on_join(parent, child, size);
+
+            true
         }
 
-        true
+        // Immediate parent check.
+        if self.get_parent(first_element) == self.get_parent(second_element) {
+            return false;
+        }
+
+        slow_path(self, first_element, second_element)
+    }
+
+    /// Joins all elements that are joined in `other`, so `self` becomes the finest common coarsening of both partitions.
+    ///
+    /// Afterwards, two elements are joined if and only if they are connected by a chain of elements, where each consecutive pair is joined in `self` or in `other`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `self` and `other` have different lengths.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::disjoint_set;
+    ///
+    /// let mut ds = disjoint_set![[0, 1], [2, 3], [4]];
+    /// ds.union_with(&disjoint_set![[0], [1, 2], [3], [4]]);
+    /// assert_eq!(ds, disjoint_set![[0, 1, 2, 3], [4]]);
+    /// ```
+    #[allow(clippy::missing_inline_in_public_items)]
+    pub fn union_with<P, Q, R, J, T>(&mut self, other: &DisjointSet<P, Q, R, J, T>)
+    where
+        Q: FindStrategy,
+        J: ElementIndex,
+    {
+        assert_eq!(self.len(), other.len(), "lengths differ");
+        for element in 0..self.len() {
+            self.join(element, other.root_of(element));
+        }
+    }
+
+    /// Applies the journal in `reader`, as written by a [`Journal`], and returns the number of records read and the length in bytes of the valid prefix of the journal.
+    ///
+    /// Replaying is idempotent: Each record refers to elements by index, so records that are already reflected in `self` have no effect.
+    /// This allows to replay a journal onto a snapshot of the partition that was taken while the journal was written, or to replay it again after an interruption.
+    ///
+    /// A truncated last record, for example from a crash while writing it, is ignored.
+    /// It does not count towards the valid prefix, so truncate the journal to that length before appending to it via [`Journal::resume`].
+    /// The valid prefix is empty if even the header is truncated.
+    /// Reading byte by byte can be slow, so consider wrapping `reader` in a [`BufReader`].
+    ///
+    /// [`Journal`]: crate::Journal
+    /// [`Journal::resume`]: crate::Journal::resume
+    /// [`BufReader`]: std::io::BufReader
+    ///
+    /// # Errors
+    ///
+    /// Returns an error with [`ErrorKind::InvalidData`] if the journal is malformed, for example if the header or a checksum is invalid, or if a record refers to an element that is neither contained in `self` nor added right before.
+    /// In this case, all records before the malformed one are applied.
+    /// Returns any error that occurs while reading from `reader`, except [`ErrorKind::UnexpectedEof`].
+    ///
+    /// [`ErrorKind::InvalidData`]: std::io::ErrorKind::InvalidData
+    /// [`ErrorKind::UnexpectedEof`]: std::io::ErrorKind::UnexpectedEof
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::{DisjointSet, Journal};
+    ///
+    /// let mut journal = Journal::new(Vec::new()).unwrap();
+    /// journal.add_singleton().unwrap();
+    /// journal.add_singleton().unwrap();
+    /// journal.join(0, 1).unwrap();
+    /// let (_, mut bytes) = journal.into_parts();
+    ///
+    /// // A crash cut off the last record.
+    /// bytes.pop();
+    ///
+    /// let mut ds = DisjointSet::new();
+    /// let (number_records, valid_len) = ds.replay(&bytes[..]).unwrap();
+    /// assert_eq!(number_records, 2);
+    /// assert_eq!(ds, DisjointSet::with_len(2));
+    ///
+    /// // Replaying again changes nothing.
+    /// assert_eq!(ds.replay(&bytes[..]).unwrap(), (2, valid_len));
+    /// assert_eq!(ds, DisjointSet::with_len(2));
+    /// assert_eq!(valid_len, bytes.len() as u64 - 6);
+    /// ```
+    #[allow(clippy::missing_inline_in_public_items)]
+    pub fn replay<R>(&mut self, reader: R) -> io::Result<(usize, u64)>
+    where
+        R: Read,
+    {
+        journal::replay(self, reader)
+    }
+}
+
+/// Returns the inverse of `permutation`, so `inverse[permutation[i]] == i`.
+///
+/// # Panics
+///
+/// Panics if `permutation` is not a permutation of the elements from `0` to `n - 1`.
+// Contradicts `unreachable_pub` and `clippy::pub_without_shorthand`.
+#[allow(clippy::pub_with_shorthand, clippy::redundant_pub_crate)]
+pub(crate) fn inverse_permutation(permutation: &[usize]) -> Vec<usize> {
+    let len = permutation.len();
+    // `len` marks elements without preimage so far.
+    let mut inverse = vec![len; len];
+    for (element, &image) in permutation.iter().enumerate() {
+        assert!(image < len && inverse[image] == len, "not a permutation");
+        inverse[image] = element;
+    }
+    inverse
+}
+
+impl<O, F, L, I, S, P, Q, R, J, T> PartialEq<DisjointSet<P, Q, R, J, T>>
+    for DisjointSet<O, F, L, I, S>
+where
+    F: FindStrategy,
+    I: ElementIndex,
+    Q: FindStrategy,
+    J: ElementIndex,
+{
+    #[allow(clippy::missing_inline_in_public_items)]
+    fn eq(&self, other: &DisjointSet<P, Q, R, J, T>) -> bool {
+        self.len() == other.len() && self.num_sets() == other.num_sets() && self.refines(other)
+    }
+}
+
+impl<O, F, L, I, S> Eq for DisjointSet<O, F, L, I, S>
+where
+    F: FindStrategy,
+    I: ElementIndex,
+{
+}
+
+/// Hashes the partition, consistent with [`PartialEq`], so the hash does not depend on the internal forest.
+///
+/// This hashes the canonical [`labels`](DisjointSet::labels), which takes linear time and allocates.
+/// The path compression performed by shared references does not change the hash, so a `DisjointSet` can be used as key in a `HashSet` or `HashMap`.
+impl<O, F, L, I, S> Hash for DisjointSet<O, F, L, I, S>
+where
+    F: FindStrategy,
+    I: ElementIndex,
+{
+    #[inline]
+    fn hash<H>(&self, state: &mut H)
+    where
+        H: Hasher,
+    {
+        self.labels().0.hash(state);
+    }
+}
+
+/// Compares partitions by refinement, where finer partitions are smaller.
+///
+/// A `DisjointSet` is less than or equal to another one of the same length if it is a [refinement](DisjointSet::is_refinement_of) of it.
+/// Partitions where neither is a refinement of the other, as well as partitions of different lengths, are not comparable.
+///
+/// # Examples
+///
+/// ```
+/// use disjoint::disjoint_set;
+///
+/// let fine = disjoint_set![[0, 1], [2], [3]];
+/// let coarse = disjoint_set![[0, 1, 3], [2]];
+/// let other = disjoint_set![[0], [1, 2], [3]];
+/// assert!(fine < coarse);
+/// assert!(coarse >= fine);
+/// assert_eq!(fine.partial_cmp(&other), None);
+/// ```
+impl<O, F, L, I, S, P, Q, R, J, T> PartialOrd<DisjointSet<P, Q, R, J, T>>
+    for DisjointSet<O, F, L, I, S>
+where
+    F: FindStrategy,
+    I: ElementIndex,
+    Q: FindStrategy,
+    J: ElementIndex,
+{
+    #[allow(clippy::missing_inline_in_public_items)]
+    fn partial_cmp(&self, other: &DisjointSet<P, Q, R, J, T>) -> Option<Ordering> {
+        if self.len() != other.len() {
+            return None;
+        }
+
+        match self.num_sets().cmp(&other.num_sets()) {
+            Ordering::Greater if self.refines(other) => Some(Ordering::Less),
+            Ordering::Less if other.refines(self) => Some(Ordering::Greater),
+            Ordering::Equal if self.refines(other) => Some(Ordering::Equal),
+            Ordering::Less | Ordering::Equal | Ordering::Greater => None,
+        }
+    }
+}
+
+/// Creates a `DisjointSet` by joining each pair of elements, like [`extend`](DisjointSet::extend).
+///
+/// # Examples
+///
+/// ```
+/// use disjoint::DisjointSet;
+///
+/// let ds: DisjointSet = vec![(0, 2), (4, 2)].into_iter().collect();
+/// assert_eq!(ds.sets(), vec![vec![0, 2, 4], vec![1], vec![3]]);
+/// ```
+impl FromIterator<(usize, usize)> for DisjointSet {
+    #[inline]
+    fn from_iter<T>(iter: T) -> Self
+    where
+        T: IntoIterator<Item = (usize, usize)>,
+    {
+        let mut result = Self::new();
+        result.extend(iter);
+        result
     }
 }
 
-impl Eq for DisjointSet {}
+/// Joins each pair of elements, first adding singletons until both elements are in bounds.
+///
+/// Panics like [`add_singleton`](DisjointSet::add_singleton) if an element does not fit into the index type.
+///
+/// # Examples
+///
+/// ```
+/// use disjoint::DisjointSet;
+///
+/// let mut ds = DisjointSet::with_len(2);
+/// ds.extend(vec![(0, 1), (3, 2)]);
+/// assert_eq!(ds.sets(), vec![vec![0, 1], vec![2, 3]]);
+/// ```
+impl<O, F, L, I, S> Extend<(usize, usize)> for DisjointSet<O, F, L, I, S>
+where
+    O: JoinObserver,
+    F: FindStrategy,
+    L: LinkStrategy,
+    I: ElementIndex,
+    S: Tracking,
+{
+    #[inline]
+    fn extend<T>(&mut self, iter: T)
+    where
+        T: IntoIterator<Item = (usize, usize)>,
+    {
+        for (first_element, second_element) in iter {
+            while self.len() <= first_element.max(second_element) {
+                self.add_singleton();
+            }
+            self.join(first_element, second_element);
+        }
+    }
+}
 
 #[cfg(test)]
 mod test {
-    use crate::DisjointSet;
+    use std::{cell::Cell, mem::size_of};
+
+    use crate::{
+        ByRank, BySize, DisjointSet, ElementIndex, InvariantViolation, LinkStrategy,
+        MemberTracking, NoObserver, NoTracking, PathSplitting, Tracking,
+    };
 
     #[test]
     fn join_returns_false_even_if_immediate_parent_check_fails() {
@@ -471,4 +2136,133 @@ mod test {
         set.clear();
         assert_eq!(set.parents.capacity(), capacity);
     }
+
+    fn joined_four() -> DisjointSet {
+        let mut ds = DisjointSet::with_len(4);
+        ds.join(0, 1);
+        ds.join(2, 3);
+        ds.join(1, 3);
+        ds
+    }
+
+    #[test]
+    fn check_invariants_accepts_joined_set() {
+        assert_eq!(joined_four().check_invariants(), Ok(()));
+    }
+
+    #[test]
+    fn check_invariants_reports_length_mismatch() {
+        let mut ds = joined_four();
+        ds.ranks.pop();
+        assert_eq!(
+            ds.check_invariants(),
+            Err(InvariantViolation::LengthMismatch {
+                parents: 4,
+                ranks: 3
+            })
+        );
+    }
+
+    #[test]
+    fn check_invariants_reports_parent_out_of_bounds() {
+        let ds = joined_four();
+        ds.set_parent(2, 7);
+        assert_eq!(
+            ds.check_invariants(),
+            Err(InvariantViolation::ParentOutOfBounds {
+                element: 2,
+                parent: 7,
+                len: 4
+            })
+        );
+    }
+
+    #[test]
+    fn check_invariants_reports_cycle() {
+        let ds = DisjointSet::with_len(4);
+        ds.set_parent(0, 1);
+        ds.set_parent(1, 2);
+        ds.set_parent(2, 1);
+        assert_eq!(
+            ds.check_invariants(),
+            Err(InvariantViolation::Cycle { element: 1 })
+        );
+    }
+
+    #[test]
+    fn check_invariants_reports_rank_not_increasing() {
+        let ds = DisjointSet::with_len(3);
+        ds.set_parent(1, 0);
+        assert_eq!(
+            ds.check_invariants(),
+            Err(InvariantViolation::RankNotIncreasing {
+                element: 1,
+                parent: 0
+            })
+        );
+    }
+
+    /// The number of elements of the disjoint sets built by [`footprint`].
+    const FOOTPRINT_LEN: usize = 6;
+
+    /// Returns the number of bytes stored for the elements of a grown and appended disjoint set, ignoring spare capacity.
+    fn footprint<L, I, S>(link: L, tracking: S) -> usize
+    where
+        L: LinkStrategy + Copy,
+        I: ElementIndex,
+        S: Tracking + Copy,
+    {
+        let mut ds = DisjointSet::<_, _, _, I, _>::with_len_in_bounds(
+            3,
+            NoObserver,
+            PathSplitting,
+            link,
+            tracking,
+        );
+        ds.join(0, 1);
+        ds.add_singleton();
+        let offset = ds.append(DisjointSet::<_, _, _, I, _>::with_len_in_bounds(
+            2,
+            NoObserver,
+            PathSplitting,
+            link,
+            tracking,
+        ));
+        ds.join(offset, 0);
+        assert_eq!(ds.len(), FOOTPRINT_LEN);
+
+        ds.parents.len() * size_of::<Cell<I>>()
+            + ds.ranks.len() * size_of::<u8>()
+            + ds.sizes.len() * size_of::<I>()
+            + ds.nexts.len() * size_of::<I>()
+    }
+
+    #[test]
+    fn footprint_per_element() {
+        assert_eq!(
+            footprint::<_, usize, _>(ByRank, NoTracking),
+            FOOTPRINT_LEN * (2 * size_of::<usize>() + 1)
+        );
+        assert_eq!(
+            footprint::<_, u64, _>(ByRank, NoTracking),
+            FOOTPRINT_LEN * 17
+        );
+        assert_eq!(
+            footprint::<_, u32, _>(ByRank, NoTracking),
+            FOOTPRINT_LEN * 9
+        );
+        assert_eq!(
+            footprint::<_, u16, _>(ByRank, NoTracking),
+            FOOTPRINT_LEN * 5
+        );
+
+        assert_eq!(
+            footprint::<_, u32, _>(ByRank, MemberTracking),
+            FOOTPRINT_LEN * 13
+        );
+        assert_eq!(
+            footprint::<_, u32, _>(BySize, NoTracking),
+            FOOTPRINT_LEN * 9
+        );
+    }
 }
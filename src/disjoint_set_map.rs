@@ -0,0 +1,484 @@
+// Underscore imports are not available on old Rust versions.
+#[allow(clippy::unused_trait_names)]
+use std::{
+    borrow::Borrow,
+    collections::{
+        hash_map::{Entry, RandomState},
+        HashMap,
+    },
+    hash::{BuildHasher, Hash, Hasher},
+};
+
+use crate::DisjointSet;
+
+/// A disjoint-set data structure for arbitrary hashable keys, instead of indices.
+///
+/// Internally, every key is assigned an index in insertion order, and a [`DisjointSet`] keeps track of which indices are joined.
+/// Each key is stored once, in [`keys`], and looked up via a map from its hash to its index, so keys need not be [`Clone`].
+/// Keys are inserted with [`insert`], or implicitly as singletons when passed to [`join`] for the first time.
+///
+/// [`keys`]: DisjointSetMap::keys
+/// [`insert`]: DisjointSetMap::insert
+/// [`join`]: DisjointSetMap::join
+///
+/// # Examples
+///
+/// ```
+/// use disjoint::DisjointSetMap;
+///
+/// let mut dsm: DisjointSetMap<String> = DisjointSetMap::new();
+///
+/// // Unknown keys are inserted on the fly.
+/// dsm.join("alice", "bob"); // {"alice", "bob"}
+/// dsm.join("carol", "dave"); // {"alice", "bob"}, {"carol", "dave"}
+/// dsm.insert("eve".to_owned()); // {"alice", "bob"}, {"carol", "dave"}, {"eve"}
+///
+/// assert!(dsm.is_joined("alice", "bob"));
+/// assert!(!dsm.is_joined("bob", "carol"));
+/// assert_eq!(
+///     dsm.sets(),
+///     vec![vec!["alice", "bob"], vec!["carol", "dave"], vec!["eve"]]
+/// );
+/// ```
+#[allow(clippy::missing_inline_in_public_items)]
+#[derive(Debug, Clone)]
+pub struct DisjointSetMap<K> {
+    keys: Vec<K>,
+    /// The index of each key by its hash. A key whose hash is taken by another key is stored under the next free hash.
+    hash_to_index: HashMap<u64, usize>,
+    hasher: RandomState,
+    indices: DisjointSet,
+}
+
+impl<K> Default for DisjointSetMap<K>
+where
+    K: Hash + Eq,
+{
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K> DisjointSetMap<K> {
+    /// Returns a `&DisjointSet` of all indices and the information of how they are joined.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::DisjointSetMap;
+    ///
+    /// let mut dsm = DisjointSetMap::new();
+    /// dsm.join(&"x", &"y");
+    /// dsm.insert("z");
+    ///
+    /// let indices = dsm.indices();
+    /// assert!(indices.is_joined(0, 1));
+    /// assert!(!indices.is_joined(0, 2));
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn indices(&self) -> &DisjointSet {
+        &self.indices
+    }
+}
+
+impl<K> DisjointSetMap<K>
+where
+    K: Hash + Eq,
+{
+    /// Constructs a new, empty `DisjointSetMap<K>`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::DisjointSetMap;
+    ///
+    /// let dsm: DisjointSetMap<String> = DisjointSetMap::new();
+    /// assert!(dsm.is_empty());
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            keys: Vec::new(),
+            hash_to_index: HashMap::new(),
+            hasher: RandomState::new(),
+            indices: DisjointSet::new(),
+        }
+    }
+
+    /// Constructs a new, empty `DisjointSetMap<K>` with at least the specified capacity.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new capacity exceeds `isize::MAX` bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::DisjointSetMap;
+    ///
+    /// let mut dsm = DisjointSetMap::with_capacity(10);
+    /// assert_eq!(dsm.len(), 0);
+    /// dsm.insert('a');
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            keys: Vec::with_capacity(capacity),
+            hash_to_index: HashMap::with_capacity(capacity),
+            hasher: RandomState::new(),
+            indices: DisjointSet::with_capacity(capacity),
+        }
+    }
+
+    /// Returns the index of `key`, or the hash under which it can be inserted if it is not contained.
+    // `BuildHasher::hash_one` is not available on old Rust versions.
+    #[allow(clippy::manual_hash_one)]
+    fn find<Q>(&self, key: &Q) -> Result<usize, u64>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let mut hasher = self.hasher.build_hasher();
+        key.hash(&mut hasher);
+        let mut hash = hasher.finish();
+        loop {
+            match self.hash_to_index.get(&hash) {
+                Some(&index) => {
+                    let candidate: &Q = self.keys[index].borrow();
+                    if candidate == key {
+                        return Ok(index);
+                    }
+                    hash = hash.wrapping_add(1);
+                }
+                None => return Err(hash),
+            }
+        }
+    }
+
+    /// Returns the index assigned to `key`, or `None` if `key` is not contained.
+    ///
+    /// Indices are assigned in insertion order, starting from `0`. They are the indices of the [`DisjointSet`] returned by [`indices`].
+    ///
+    /// [`indices`]: DisjointSetMap::indices
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::DisjointSetMap;
+    ///
+    /// let mut dsm = DisjointSetMap::new();
+    /// dsm.insert("x");
+    /// dsm.insert("y");
+    /// assert_eq!(dsm.index_of(&"y"), Some(1));
+    /// assert_eq!(dsm.index_of(&"z"), None);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn index_of<Q>(&self, key: &Q) -> Option<usize>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.find(key).ok()
+    }
+
+    /// Returns `true` if `key` is contained.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::DisjointSetMap;
+    ///
+    /// let mut dsm = DisjointSetMap::new();
+    /// dsm.insert(3);
+    /// assert!(dsm.contains(&3));
+    /// assert!(!dsm.contains(&4));
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn contains<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.find(key).is_ok()
+    }
+
+    /// Returns a slice of all keys, in insertion order. The key at position `i` has index `i`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::DisjointSetMap;
+    ///
+    /// let mut dsm = DisjointSetMap::new();
+    /// dsm.join(&'b', &'a');
+    /// dsm.insert('c');
+    /// assert_eq!(dsm.keys(), ['b', 'a', 'c']);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn keys(&self) -> &[K] {
+        &self.keys
+    }
+
+    /// Returns the number of keys, regardless of how they are joined together.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::DisjointSetMap;
+    ///
+    /// let mut dsm = DisjointSetMap::new();
+    /// dsm.join(&1, &2);
+    /// dsm.join(&2, &3);
+    /// assert_eq!(dsm.len(), 3);
+    /// ```
+    #[must_use]
+    #[inline]
+    #[allow(clippy::missing_const_for_fn)]
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    /// Returns `true` if there are no keys.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::DisjointSetMap;
+    ///
+    /// let mut dsm = DisjointSetMap::new();
+    /// assert!(dsm.is_empty());
+    /// dsm.insert("a");
+    /// assert!(!dsm.is_empty());
+    /// ```
+    #[must_use]
+    #[inline]
+    #[allow(clippy::missing_const_for_fn)]
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// Clears the `DisjointSetMap<K>`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::DisjointSetMap;
+    ///
+    /// let mut dsm = DisjointSetMap::new();
+    /// dsm.join(&"a", &"b");
+    /// dsm.clear();
+    /// assert!(dsm.is_empty());
+    /// assert!(!dsm.contains(&"a"));
+    /// ```
+    #[inline]
+    pub fn clear(&mut self) {
+        self.keys.clear();
+        self.hash_to_index.clear();
+        self.indices.clear();
+    }
+
+    /// Returns the key representing the subset containing `key`, or `None` if `key` is not contained.
+    /// This exact key is returned for every member of the subset.
+    ///
+    /// # Important
+    ///
+    /// The specific choice of the returned key is an implementation detail.
+    /// There are no further guarantees beyond what is documented here.
+    /// If you just want to check if two keys are in the same subset, use [`is_joined`].
+    ///
+    /// [`is_joined`]: DisjointSetMap::is_joined
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::DisjointSetMap;
+    ///
+    /// let mut dsm: DisjointSetMap<String> = DisjointSetMap::new();
+    /// dsm.join("a", "b");
+    /// dsm.insert("c".to_owned());
+    ///
+    /// assert_eq!(dsm.root_of("a"), dsm.root_of("b"));
+    /// assert_eq!(dsm.root_of("c").unwrap(), "c");
+    /// assert_eq!(dsm.root_of("d"), None);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn root_of<Q>(&self, key: &Q) -> Option<&K>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.index_of(key)
+            .map(|index| &self.keys[self.indices.root_of(index)])
+    }
+
+    /// Returns `true` if `first_key` and `second_key` are in the same subset.
+    ///
+    /// Keys that are not contained are treated like singletons, so they are only joined to themselves.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::DisjointSetMap;
+    ///
+    /// let mut dsm: DisjointSetMap<String> = DisjointSetMap::new();
+    /// dsm.join("a", "b");
+    /// dsm.insert("c".to_owned());
+    ///
+    /// assert!(dsm.is_joined("a", "b"));
+    /// assert!(!dsm.is_joined("a", "c"));
+    /// assert!(!dsm.is_joined("a", "unknown"));
+    /// assert!(dsm.is_joined("unknown", "unknown"));
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn is_joined<Q>(&self, first_key: &Q, second_key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        match (self.index_of(first_key), self.index_of(second_key)) {
+            (Some(first_index), Some(second_index)) => {
+                self.indices.is_joined(first_index, second_index)
+            }
+            _ => first_key == second_key,
+        }
+    }
+
+    /// Returns a `Vec` of all sets. Each entry corresponds to one set, and is a `Vec` of references to its keys.
+    ///
+    /// The sets are ordered by the insertion order of their first inserted key. The keys inside each set are ordered by insertion order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::DisjointSetMap;
+    ///
+    /// let mut dsm = DisjointSetMap::new();
+    /// dsm.insert('z');
+    /// dsm.join(&'x', &'y');
+    /// dsm.join(&'y', &'z');
+    /// dsm.insert('w');
+    /// assert_eq!(dsm.sets(), vec![vec![&'z', &'x', &'y'], vec![&'w']]);
+    /// ```
+    #[must_use]
+    #[allow(clippy::missing_inline_in_public_items)]
+    pub fn sets(&self) -> Vec<Vec<&K>> {
+        self.indices
+            .sets()
+            .into_iter()
+            .map(|set| set.into_iter().map(|index| &self.keys[index]).collect())
+            .collect()
+    }
+
+    /// Inserts `key` as a new singleton, not joined to any other key, if it is not already contained. Returns the index of `key`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::DisjointSetMap;
+    ///
+    /// let mut dsm = DisjointSetMap::new();
+    /// assert_eq!(dsm.insert("a"), 0);
+    /// assert_eq!(dsm.insert("b"), 1);
+    /// assert_eq!(dsm.insert("a"), 0);
+    /// assert!(!dsm.is_joined(&"a", &"b"));
+    /// ```
+    #[allow(clippy::missing_inline_in_public_items)]
+    pub fn insert(&mut self, key: K) -> usize {
+        match self.find(&key) {
+            Ok(index) => index,
+            Err(hash) => {
+                let index = self.indices.add_singleton();
+                self.keys.push(key);
+                self.hash_to_index.insert(hash, index);
+                index
+            }
+        }
+    }
+
+    /// If `first_key` and `second_key` are in different sets, joins them together and returns `true`.
+    ///
+    /// Otherwise, does nothing and returns `false`.
+    ///
+    /// Keys that are not contained yet are inserted as singletons first. Only those keys are converted via [`ToOwned`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::DisjointSetMap;
+    ///
+    /// let mut dsm: DisjointSetMap<String> = DisjointSetMap::new();
+    /// assert!(dsm.join("a", "b"));
+    /// assert!(!dsm.join("b", "a"));
+    /// assert!(dsm.join("b", "c"));
+    /// assert!(dsm.is_joined("a", "c"));
+    /// assert_eq!(dsm.len(), 3);
+    /// ```
+    #[inline]
+    pub fn join<Q>(&mut self, first_key: &Q, second_key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ToOwned<Owned = K> + ?Sized,
+    {
+        let first_index = self.index_or_insert(first_key);
+        let second_index = self.index_or_insert(second_key);
+        self.indices.join(first_index, second_index)
+    }
+
+    fn index_or_insert<Q>(&mut self, key: &Q) -> usize
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ToOwned<Owned = K> + ?Sized,
+    {
+        self.index_of(key)
+            .unwrap_or_else(|| self.insert(key.to_owned()))
+    }
+}
+
+impl<K> PartialEq for DisjointSetMap<K>
+where
+    K: Hash + Eq,
+{
+    /// Two `DisjointSetMap`s are equal if they contain the same keys, joined in the same way. The insertion order does not matter.
+    #[allow(clippy::missing_inline_in_public_items)]
+    fn eq(&self, other: &Self) -> bool {
+        if self.len() != other.len() || self.indices.num_sets() != other.indices.num_sets() {
+            return false;
+        }
+
+        let mut self_root_to_other_root = HashMap::with_capacity(self.len());
+
+        for (index, key) in self.keys.iter().enumerate() {
+            let other_index = match other.index_of(key) {
+                Some(other_index) => other_index,
+                None => return false,
+            };
+
+            let self_root = self.indices.root_of(index);
+            let other_root = other.indices.root_of(other_index);
+
+            match self_root_to_other_root.entry(self_root) {
+                Entry::Occupied(entry) => {
+                    if other_root != *entry.get() {
+                        return false;
+                    }
+                }
+                Entry::Vacant(entry) => {
+                    entry.insert(other_root);
+                }
+            }
+        }
+
+        true
+    }
+}
+
+impl<K> Eq for DisjointSetMap<K> where K: Hash + Eq {}
@@ -1,6 +1,16 @@
-use std::ops::{Deref, DerefMut};
+use std::{
+    cmp::Ordering,
+    fmt::Display,
+    hash::{Hash, Hasher},
+    io::{self, Read, Write},
+    iter::FromIterator,
+    ops::{Deref, DerefMut},
+};
 
-use crate::DisjointSet;
+use crate::{
+    disjoint_set, display, ByRank, DisjointSet, JoinObserver, NoObserver, NoTracking,
+    PathSplitting, Tracking, TracksMembers,
+};
 
 /// A data structure for managing a `Vec<T>` of data together with a [`DisjointSet`] to keep track of which data entries are joined.
 ///
@@ -15,6 +25,8 @@ use crate::DisjointSet;
 /// [`is_joined`]: DisjointSetVec::is_joined
 /// [`disjoint_set_vec!`]: crate::disjoint_set_vec!
 ///
+/// The type parameters `O` and `S` are the [`JoinObserver`] and the [`Tracking`] of the indices, see [`DisjointSet`]. By default, they are [`NoObserver`] and [`NoTracking`].
+///
 /// # Examples
 ///
 /// ```
@@ -40,15 +52,14 @@ use crate::DisjointSet;
 /// assert!(!dsv.is_joined(0, 2));
 /// ```
 #[allow(clippy::missing_inline_in_public_items)]
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub struct DisjointSetVec<T> {
+#[derive(Debug, Clone)]
+pub struct DisjointSetVec<T, O = NoObserver, S = NoTracking> {
     data: Vec<T>,
-    indices: DisjointSet,
+    indices: DisjointSet<O, PathSplitting, ByRank, usize, S>,
 }
 
 impl<T> Default for DisjointSetVec<T> {
     #[inline]
-    #[must_use]
     fn default() -> Self {
         Self::new()
     }
@@ -58,7 +69,6 @@ impl<IntoVec, T> From<IntoVec> for DisjointSetVec<T>
 where
     Vec<T>: From<IntoVec>,
 {
-    #[must_use]
     #[inline]
     fn from(value: IntoVec) -> Self {
         let data = Vec::from(value);
@@ -71,52 +81,6 @@ where
 }
 
 impl<T> DisjointSetVec<T> {
-    /// Returns a `&Vec<T>` of all values.
-    ///
-    /// # Examples
-    ///
-    /// ```
-    /// use disjoint::disjoint_set_vec;
-    ///
-    /// let dsv = disjoint_set_vec![
-    ///     ("a", true),
-    ///     ("b", false),
-    ///     ("c", true),
-    /// ];
-    ///
-    /// assert_eq!(*dsv.values(), [
-    ///     ("a", true),
-    ///     ("b", false),
-    ///     ("c", true),
-    /// ]);
-    /// ```
-    #[must_use]
-    #[inline]
-    pub const fn values(&self) -> &Vec<T> {
-        &self.data
-    }
-
-    /// Returns a `&DisjointSet` of all indices and the information of how they are joined.
-    ///
-    /// # Examples
-    ///
-    /// ```
-    /// use disjoint::disjoint_set_vec;
-    ///
-    /// let mut dsv = disjoint_set_vec![3; 10];
-    ///
-    /// dsv.join(2, 4);
-    /// let indices = dsv.indices();
-    ///
-    /// assert!(indices.is_joined(2, 4));
-    /// assert!(!indices.is_joined(3, 4));
-    /// ```
-    #[must_use]
-    #[inline]
-    pub const fn indices(&self) -> &DisjointSet {
-        &self.indices
-    }
-
     /// Constructs a new, empty `DisjointSetVec<T>` with at least the specified capacity.
     ///
     /// It will be able to hold at least `capacity` elements without
@@ -177,6 +141,112 @@ impl<T> DisjointSetVec<T> {
             indices: DisjointSet::new(),
         }
     }
+}
+
+impl<T, O> DisjointSetVec<T, O> {
+    /// Creates a new `DisjointSetVec<T>` containing `values` as separate singletons, notifying `observer` about each join.
+    ///
+    /// See [`DisjointSet::with_len_and_observer`] for details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::DisjointSetVec;
+    ///
+    /// let mut joins = Vec::new();
+    /// let mut dsv = DisjointSetVec::with_values_and_observer(vec!['a', 'b', 'c'], |root, absorbed_root, _| {
+    ///     joins.push((root, absorbed_root));
+    /// });
+    /// dsv.join(2, 1);
+    /// drop(dsv);
+    /// assert_eq!(joins, vec![(2, 1)]);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn with_values_and_observer(values: Vec<T>, observer: O) -> Self {
+        let len = values.len();
+        Self {
+            data: values,
+            indices: DisjointSet::with_len_and_observer(len, observer),
+        }
+    }
+}
+
+impl<T, S> DisjointSetVec<T, NoObserver, S>
+where
+    S: Tracking,
+{
+    /// Creates a new `DisjointSetVec<T>` containing `values` as separate singletons, linking the members of each set if the [`Tracking`] `tracking` does.
+    ///
+    /// See [`DisjointSet::with_len_and_tracking`] for details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::{DisjointSetVec, MemberTracking};
+    ///
+    /// let mut dsv = DisjointSetVec::with_values_and_tracking(vec!['a', 'b', 'c'], MemberTracking);
+    /// dsv.join(2, 1);
+    /// assert_eq!(dsv.size_of(1), 2);
+    /// assert_eq!(dsv.members(0).collect::<Vec<_>>(), vec![&'a']);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn with_values_and_tracking(values: Vec<T>, tracking: S) -> Self {
+        let len = values.len();
+        Self {
+            data: values,
+            indices: DisjointSet::with_len_and_tracking(len, tracking),
+        }
+    }
+}
+
+impl<T, O, S> DisjointSetVec<T, O, S> {
+    /// Returns a `&Vec<T>` of all values.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::disjoint_set_vec;
+    ///
+    /// let dsv = disjoint_set_vec![
+    ///     ("a", true),
+    ///     ("b", false),
+    ///     ("c", true),
+    /// ];
+    ///
+    /// assert_eq!(*dsv.values(), [
+    ///     ("a", true),
+    ///     ("b", false),
+    ///     ("c", true),
+    /// ]);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn values(&self) -> &Vec<T> {
+        &self.data
+    }
+
+    /// Returns a `&DisjointSet` of all indices and the information of how they are joined.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::disjoint_set_vec;
+    ///
+    /// let mut dsv = disjoint_set_vec![3; 10];
+    ///
+    /// dsv.join(2, 4);
+    /// let indices = dsv.indices();
+    ///
+    /// assert!(indices.is_joined(2, 4));
+    /// assert!(!indices.is_joined(3, 4));
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn indices(&self) -> &DisjointSet<O, PathSplitting, ByRank, usize, S> {
+        &self.indices
+    }
 
     /// Clears the `DisjointSetVec`.
     ///
@@ -223,11 +293,45 @@ impl<T> DisjointSetVec<T> {
     /// assert!(!dsv.is_joined(0, 1));
     /// ```
     #[inline]
-    pub fn push(&mut self, value: T) -> usize {
+    pub fn push(&mut self, value: T) -> usize
+    where
+        S: Tracking,
+    {
         self.data.push(value);
         self.indices.add_singleton()
     }
 
+    /// Moves all values of `other` into `self`, keeping their sets. Returns the offset that was added to the indices of the values of `other`, which is the previous [`len`] of `self`.
+    ///
+    /// The values of `other` are not joined to any value of `self`. This takes time linear in the length of `other`.
+    ///
+    /// [`len`]: slice::len
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new capacity exceeds `isize::MAX` bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::disjoint_set_vec;
+    ///
+    /// let mut dsv = disjoint_set_vec!['a', 'b'];
+    /// let mut other = disjoint_set_vec!['c', 'd', 'e'];
+    /// other.join(0, 2);
+    ///
+    /// let offset = dsv.append(other);
+    /// assert_eq!(offset, 2);
+    /// assert_eq!(*dsv.values(), ['a', 'b', 'c', 'd', 'e']);
+    /// assert!(dsv.is_joined(2, 4));
+    /// assert!(!dsv.is_joined(1, 2));
+    /// ```
+    #[allow(clippy::missing_inline_in_public_items)]
+    pub fn append<P>(&mut self, other: DisjointSetVec<T, P, S>) -> usize {
+        self.data.extend(other.data);
+        self.indices.append(other.indices)
+    }
+
     /// Returns the index of an element of the subset containing the element at `child_index`.
     /// This exact index is returned for all indices of elements of the subset.
     ///
@@ -296,6 +400,189 @@ impl<T> DisjointSetVec<T> {
         self.indices.is_joined(first_index, second_index)
     }
 
+    /// Returns the number of elements in the subset containing the element at `index`, including that element itself.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::disjoint_set_vec;
+    ///
+    /// let mut dsv = disjoint_set_vec!['a', 'b', 'c']; // {'a'}, {'b'}, {'c'}
+    /// dsv.join(0, 2); // {'a', 'c'}, {'b'}
+    /// assert_eq!(dsv.size_of(2), 2);
+    /// assert_eq!(dsv.size_of(1), 1);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn size_of(&self, index: usize) -> usize {
+        self.indices.size_of(index)
+    }
+
+    /// Returns an iterator over the values of all elements in the subset containing the element at `index`, including that element itself.
+    ///
+    /// The iterator starts with the value at `index`. The order of the remaining values is an implementation detail.
+    /// It runs in time proportional to the size of the subset.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::{DisjointSetVec, MemberTracking};
+    ///
+    /// let mut dsv = DisjointSetVec::with_values_and_tracking(vec!['a', 'b', 'c', 'd'], MemberTracking); // {'a'}, {'b'}, {'c'}, {'d'}
+    /// dsv.join(0, 2); // {'a', 'c'}, {'b'}, {'d'}
+    /// dsv.join(3, 0); // {'a', 'c', 'd'}, {'b'}
+    ///
+    /// let mut members: Vec<_> = dsv.members(2).collect();
+    /// members.sort();
+    /// assert_eq!(members, vec![&'a', &'c', &'d']);
+    /// assert_eq!(dsv.members(1).collect::<Vec<_>>(), vec![&'b']);
+    /// ```
+    #[inline]
+    pub fn members(&self, index: usize) -> impl Iterator<Item = &T>
+    where
+        S: TracksMembers,
+    {
+        let data = &self.data;
+        self.indices.members(index).map(move |member| &data[member])
+    }
+
+    /// Writes the partition of the indices into `writer`, using the binary format of [`DisjointSet::write_to`].
+    ///
+    /// The values are not written.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error that occurs while writing to `writer`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::disjoint_set_vec;
+    ///
+    /// let mut dsv = disjoint_set_vec!['a', 'b', 'c'];
+    /// dsv.join(0, 2);
+    ///
+    /// let mut bytes = Vec::new();
+    /// dsv.write_indices_to(&mut bytes).unwrap();
+    ///
+    /// let mut other = disjoint_set_vec!['x', 'y', 'z'];
+    /// other.read_indices_from(&bytes[..]).unwrap();
+    /// assert!(other.is_joined(0, 2));
+    /// ```
+    #[allow(clippy::missing_inline_in_public_items)]
+    pub fn write_indices_to<W>(&self, writer: W) -> io::Result<()>
+    where
+        W: Write,
+    {
+        self.indices.write_to(writer)
+    }
+
+    /// Replaces the partition of the indices with one read from `reader`, in the binary format of [`DisjointSet::write_to`].
+    ///
+    /// The values are kept as they are. On error, `self` is not modified.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`DisjointSet::read_from`].
+    /// Additionally returns an error with [`ErrorKind::InvalidData`] if the number of read elements differs from the number of values.
+    ///
+    /// [`ErrorKind::InvalidData`]: std::io::ErrorKind::InvalidData
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::{disjoint_set_vec, DisjointSet};
+    /// use std::io::ErrorKind;
+    ///
+    /// let mut bytes = Vec::new();
+    /// DisjointSet::with_len(2).write_to(&mut bytes).unwrap();
+    ///
+    /// let mut dsv = disjoint_set_vec!['a', 'b', 'c'];
+    /// let error = dsv.read_indices_from(&bytes[..]).unwrap_err();
+    /// assert_eq!(error.kind(), ErrorKind::InvalidData);
+    /// ```
+    #[allow(clippy::missing_inline_in_public_items)]
+    pub fn read_indices_from<R>(&mut self, reader: R) -> io::Result<()>
+    where
+        R: Read,
+        S: Tracking,
+    {
+        DisjointSet::read_from(reader).and_then(|indices| {
+            if indices.len() == self.len() {
+                self.indices.replace_partition(indices);
+                Ok(())
+            } else {
+                Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "number of elements does not match",
+                ))
+            }
+        })
+    }
+
+    /// Returns the number of disjoint subsets.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::disjoint_set_vec;
+    ///
+    /// let mut dsv = disjoint_set_vec!['a', 'b', 'c']; // {'a'}, {'b'}, {'c'}
+    /// dsv.join(0, 2); // {'a', 'c'}, {'b'}
+    /// assert_eq!(dsv.num_sets(), 2);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn num_sets(&self) -> usize {
+        self.indices.num_sets()
+    }
+
+    /// Returns a mutable reference to the observer, which is notified about each join.
+    ///
+    /// The observer can be read via [`indices`].
+    ///
+    /// [`indices`]: DisjointSetVec::indices
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::{DisjointSetVec, JoinObserver};
+    ///
+    /// struct Counter(usize);
+    ///
+    /// impl JoinObserver for Counter {
+    ///     fn on_join(&mut self, _: usize, _: usize, _: usize) {
+    ///         self.0 += 1;
+    ///     }
+    /// }
+    ///
+    /// let mut dsv = DisjointSetVec::with_values_and_observer(vec!['a', 'b', 'c'], Counter(0));
+    /// dsv.join(0, 1);
+    /// dsv.observer_mut().0 = 10;
+    /// dsv.join(1, 2);
+    /// assert_eq!(dsv.indices().observer().0, 11);
+    /// ```
+    #[inline]
+    // Mutable references in `const fn` are not available on old Rust versions.
+    #[allow(clippy::missing_const_for_fn)]
+    pub fn observer_mut(&mut self) -> &mut O {
+        self.indices.observer_mut()
+    }
+}
+
+impl<T, O, S> DisjointSetVec<T, O, S>
+where
+    O: JoinObserver,
+    S: Tracking,
+{
     /// If elements at `first_index` and `second_index` are in different sets, joins them together and returns `true`.
     ///
     /// Otherwise, does nothing and returns `false`.
@@ -330,40 +617,279 @@ impl<T> DisjointSetVec<T> {
     }
 }
 
-impl<T> IntoIterator for DisjointSetVec<T> {
+impl<T, O, S> DisjointSetVec<T, O, S>
+where
+    T: Clone,
+{
+    /// Returns the values at `indices` with the partition induced on them, like [`DisjointSet::restrict`].
+    ///
+    /// The index `i` of the result corresponds to `indices[i]`, and its value is cloned from there.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any of `indices` is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::disjoint_set_vec;
+    ///
+    /// let mut dsv = disjoint_set_vec!['a', 'b', 'c', 'd'];
+    /// dsv.join(0, 3);
+    ///
+    /// let restricted = dsv.restrict(&[3, 1, 0]);
+    /// assert_eq!(*restricted.values(), ['d', 'b', 'a']);
+    /// assert!(restricted.is_joined(0, 2));
+    /// assert!(!restricted.is_joined(0, 1));
+    /// ```
+    #[must_use]
+    #[allow(clippy::missing_inline_in_public_items)]
+    pub fn restrict(&self, indices: &[usize]) -> DisjointSetVec<T> {
+        DisjointSetVec {
+            data: indices
+                .iter()
+                .map(|&index| self.data[index].clone())
+                .collect(),
+            indices: self.indices.restrict(indices),
+        }
+    }
+
+    /// Returns the values and their partition with the indices renamed by `permutation`, like [`DisjointSet::permute`].
+    ///
+    /// The value at index `i` is moved to index `permutation[i]`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `permutation` does not have the same length as `self`, or if it is not a permutation of the indices.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::disjoint_set_vec;
+    ///
+    /// let mut dsv = disjoint_set_vec!['a', 'b', 'c'];
+    /// dsv.join(0, 1);
+    ///
+    /// let permuted = dsv.permute(&[2, 0, 1]);
+    /// assert_eq!(*permuted.values(), ['b', 'c', 'a']);
+    /// assert!(permuted.is_joined(0, 2));
+    /// ```
+    #[must_use]
+    #[allow(clippy::missing_inline_in_public_items)]
+    pub fn permute(&self, permutation: &[usize]) -> DisjointSetVec<T> {
+        assert_eq!(
+            permutation.len(),
+            self.len(),
+            "permutation has wrong length"
+        );
+        self.restrict(&disjoint_set::inverse_permutation(permutation))
+    }
+
+    /// Returns the quotient of the partition under `map`, like [`DisjointSet::quotient`], with the values collected at their new index.
+    ///
+    /// The value at index `j` of the result contains clones of the values at all indices `i` with `map(i) == j`, in ascending order of `i`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `map` returns an index that is not smaller than `len`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::disjoint_set_vec;
+    ///
+    /// let mut dsv = disjoint_set_vec!['a', 'b', 'c', 'd'];
+    /// dsv.join(1, 2);
+    ///
+    /// let quotient = dsv.quotient(3, |index| index % 3);
+    /// assert_eq!(*quotient.values(), [vec!['a', 'd'], vec!['b'], vec!['c']]);
+    /// assert!(quotient.is_joined(1, 2));
+    /// ```
+    #[must_use]
+    #[allow(clippy::missing_inline_in_public_items)]
+    pub fn quotient<F>(&self, len: usize, map: F) -> DisjointSetVec<Vec<T>>
+    where
+        F: FnMut(usize) -> usize,
+    {
+        let images: Vec<_> = (0..self.len()).map(map).collect();
+        let indices = self.indices.quotient(len, |index| images[index]);
+
+        let mut data = vec![Vec::new(); len];
+        for (value, &image) in self.data.iter().zip(&images) {
+            data[image].push(value.clone());
+        }
+
+        DisjointSetVec { data, indices }
+    }
+}
+
+impl<T, O, S> DisjointSetVec<T, O, S>
+where
+    T: Display,
+{
+    /// Returns the sets in the DOT language of Graphviz, with one cluster per set, ordered like [`DisjointSet::sets`] of the indices.
+    ///
+    /// Each node is named by its index and labeled with its value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::disjoint_set_vec;
+    ///
+    /// let mut dsv = disjoint_set_vec!['a', 'b', 'c'];
+    /// dsv.join(2, 0);
+    ///
+    /// assert_eq!(
+    ///     dsv.to_dot(),
+    ///     "graph {\n    subgraph cluster_0 {\n        0 [label=\"a\"];\n        2 [label=\"c\"];\n    }\n    subgraph cluster_1 {\n        1 [label=\"b\"];\n    }\n}\n"
+    /// );
+    /// ```
+    #[must_use]
+    #[allow(clippy::missing_inline_in_public_items)]
+    pub fn to_dot(&self) -> String {
+        display::sets_to_dot(self)
+    }
+}
+
+impl<T, O, S> PartialEq for DisjointSetVec<T, O, S>
+where
+    T: PartialEq,
+{
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.data == other.data && self.indices == other.indices
+    }
+}
+
+impl<T, O, S> Eq for DisjointSetVec<T, O, S> where T: Eq {}
+
+impl<T, O, S> Hash for DisjointSetVec<T, O, S>
+where
+    T: Hash,
+{
+    #[inline]
+    fn hash<H>(&self, state: &mut H)
+    where
+        H: Hasher,
+    {
+        self.data.hash(state);
+        self.indices.hash(state);
+    }
+}
+
+/// Compares the partitions of the indices by refinement, like [`DisjointSet`], if the values are equal.
+///
+/// `DisjointSetVec<T>`s with different values are not comparable.
+///
+/// # Examples
+///
+/// ```
+/// use disjoint::disjoint_set_vec;
+///
+/// let mut fine = disjoint_set_vec!['a', 'b', 'c'];
+/// fine.join(0, 1);
+/// let mut coarse = fine.clone();
+/// coarse.join(1, 2);
+/// assert!(fine < coarse);
+///
+/// let mut other = disjoint_set_vec!['x', 'b', 'c'];
+/// other.join(0, 1);
+/// assert_eq!(fine.partial_cmp(&other), None);
+/// ```
+impl<T, O, S> PartialOrd for DisjointSetVec<T, O, S>
+where
+    T: PartialEq,
+{
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        if self.data == other.data {
+            self.indices.partial_cmp(&other.indices)
+        } else {
+            None
+        }
+    }
+}
+
+/// Creates a `DisjointSetVec<T>` containing the values as separate singletons, not joined to each other.
+///
+/// # Examples
+///
+/// ```
+/// use disjoint::DisjointSetVec;
+///
+/// let dsv: DisjointSetVec<_> = "abc".chars().collect();
+/// assert_eq!(*dsv.values(), ['a', 'b', 'c']);
+/// assert_eq!(dsv.num_sets(), 3);
+/// ```
+impl<T> FromIterator<T> for DisjointSetVec<T> {
+    #[inline]
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+    {
+        Self::from(iter.into_iter().collect::<Vec<_>>())
+    }
+}
+
+/// Adds the values as separate singletons, like [`push`](DisjointSetVec::push).
+///
+/// # Examples
+///
+/// ```
+/// use disjoint::disjoint_set_vec;
+///
+/// let mut dsv = disjoint_set_vec!['a', 'b'];
+/// dsv.join(0, 1);
+/// dsv.extend(vec!['c', 'd']);
+/// assert_eq!(*dsv.values(), ['a', 'b', 'c', 'd']);
+/// assert_eq!(dsv.indices().sets(), vec![vec![0, 1], vec![2], vec![3]]);
+/// ```
+impl<T, O, S> Extend<T> for DisjointSetVec<T, O, S>
+where
+    S: Tracking,
+{
+    #[inline]
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = T>,
+    {
+        for value in iter {
+            self.push(value);
+        }
+    }
+}
+
+impl<T, O, S> IntoIterator for DisjointSetVec<T, O, S> {
     type Item = <Vec<T> as IntoIterator>::Item;
     type IntoIter = <Vec<T> as IntoIterator>::IntoIter;
 
     #[inline]
-    #[must_use]
     fn into_iter(self) -> Self::IntoIter {
         IntoIterator::into_iter(self.data)
     }
 }
 
-impl<'a, T> IntoIterator for &'a DisjointSetVec<T> {
+impl<'a, T, O, S> IntoIterator for &'a DisjointSetVec<T, O, S> {
     type Item = <&'a Vec<T> as IntoIterator>::Item;
     type IntoIter = <&'a Vec<T> as IntoIterator>::IntoIter;
 
     #[inline]
-    #[must_use]
     fn into_iter(self) -> Self::IntoIter {
         IntoIterator::into_iter(&self.data)
     }
 }
 
-impl<'a, T> IntoIterator for &'a mut DisjointSetVec<T> {
+impl<'a, T, O, S> IntoIterator for &'a mut DisjointSetVec<T, O, S> {
     type Item = <&'a mut Vec<T> as IntoIterator>::Item;
     type IntoIter = <&'a mut Vec<T> as IntoIterator>::IntoIter;
 
     #[inline]
-    #[must_use]
     fn into_iter(self) -> Self::IntoIter {
         IntoIterator::into_iter(&mut self.data)
     }
 }
 
-impl<T> Deref for DisjointSetVec<T> {
+impl<T, O, S> Deref for DisjointSetVec<T, O, S> {
     type Target = [T];
 
     #[inline]
@@ -372,7 +898,7 @@ impl<T> Deref for DisjointSetVec<T> {
     }
 }
 
-impl<T> DerefMut for DisjointSetVec<T> {
+impl<T, O, S> DerefMut for DisjointSetVec<T, O, S> {
     #[inline]
     fn deref_mut(&mut self) -> &mut [T] {
         &mut self.data
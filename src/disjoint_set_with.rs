@@ -0,0 +1,424 @@
+use crate::DisjointSet;
+
+/// Data that can be combined when the sets it belongs to are joined, used by [`DisjointSetWith<S>`].
+///
+/// # Examples
+///
+/// ```
+/// use disjoint::Merge;
+///
+/// #[derive(Debug, PartialEq)]
+/// struct Extent {
+///     min: i32,
+///     max: i32,
+/// }
+///
+/// impl Merge for Extent {
+///     fn merge(&mut self, other: Self) {
+///         self.min = self.min.min(other.min);
+///         self.max = self.max.max(other.max);
+///     }
+/// }
+///
+/// let mut extent = Extent { min: 3, max: 5 };
+/// extent.merge(Extent { min: 4, max: 9 });
+/// assert_eq!(extent, Extent { min: 3, max: 9 });
+/// ```
+pub trait Merge {
+    /// Merges `other` into `self`.
+    fn merge(&mut self, other: Self);
+}
+
+impl<T> Merge for Vec<T> {
+    /// Appends all values of `other` to `self`.
+    #[inline]
+    fn merge(&mut self, mut other: Self) {
+        self.append(&mut other);
+    }
+}
+
+/// A [`DisjointSet`] that stores one value `S` per set, instead of per element.
+///
+/// Every new element starts out in its own set, with its own value. Whenever two sets are joined, their values are combined into one, either by [`Merge`] in [`join`], or by a custom function in [`join_by`].
+/// The value of a set can be accessed through any of its elements via [`data_of`] and [`data_of_mut`].
+///
+/// This is different from [`DisjointSetVec<T>`], which stores one value per element and never combines them.
+///
+/// [`join`]: DisjointSetWith::join
+/// [`join_by`]: DisjointSetWith::join_by
+/// [`data_of`]: DisjointSetWith::data_of
+/// [`data_of_mut`]: DisjointSetWith::data_of_mut
+/// [`DisjointSetVec<T>`]: crate::DisjointSetVec
+///
+/// # Examples
+///
+/// ```
+/// use disjoint::DisjointSetWith;
+///
+/// // Keep track of the tags of each set.
+/// let mut dsw = DisjointSetWith::from(vec![vec!["red"], vec!["round"], vec!["small"]]);
+///
+/// dsw.join(0, 1);
+/// assert_eq!(dsw.data_of(1), &vec!["red", "round"]);
+/// assert_eq!(dsw.data_of(2), &vec!["small"]);
+///
+/// dsw.data_of_mut(0).push("shiny");
+/// assert_eq!(dsw.data_of(1), &vec!["red", "round", "shiny"]);
+/// ```
+#[allow(clippy::missing_inline_in_public_items)]
+#[derive(Debug, Clone)]
+pub struct DisjointSetWith<S> {
+    data: Vec<Option<S>>,
+    indices: DisjointSet,
+}
+
+impl<S> Default for DisjointSetWith<S> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<IntoVec, S> From<IntoVec> for DisjointSetWith<S>
+where
+    Vec<S>: From<IntoVec>,
+{
+    /// Constructs a `DisjointSetWith<S>` with one singleton set per value.
+    #[inline]
+    fn from(value: IntoVec) -> Self {
+        let data: Vec<_> = Vec::from(value).into_iter().map(Some).collect();
+        let len = data.len();
+        Self {
+            data,
+            indices: DisjointSet::with_len(len),
+        }
+    }
+}
+
+impl<S> DisjointSetWith<S> {
+    /// Constructs a new, empty `DisjointSetWith<S>`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::DisjointSetWith;
+    ///
+    /// let dsw: DisjointSetWith<u32> = DisjointSetWith::new();
+    /// assert!(dsw.is_empty());
+    /// ```
+    #[must_use]
+    #[inline]
+    #[allow(clippy::missing_const_for_fn)]
+    pub fn new() -> Self {
+        Self {
+            data: Vec::new(),
+            indices: DisjointSet::new(),
+        }
+    }
+
+    /// Returns a `&DisjointSet` of all elements and the information of how they are joined.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::DisjointSetWith;
+    ///
+    /// let mut dsw = DisjointSetWith::from(vec![vec![1], vec![2], vec![3]]);
+    /// dsw.join(0, 2);
+    ///
+    /// assert!(dsw.indices().is_joined(0, 2));
+    /// assert!(!dsw.indices().is_joined(0, 1));
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn indices(&self) -> &DisjointSet {
+        &self.indices
+    }
+
+    /// Adds a new element with the value `value`, not joined to any other element. Returns the index of the new element.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new capacity exceeds `isize::MAX` bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::DisjointSetWith;
+    ///
+    /// let mut dsw = DisjointSetWith::new();
+    /// assert_eq!(dsw.push(vec![4]), 0);
+    /// assert_eq!(dsw.push(vec![7]), 1);
+    /// assert_eq!(dsw.data_of(1), &vec![7]);
+    /// ```
+    #[inline]
+    pub fn push(&mut self, value: S) -> usize {
+        self.data.push(Some(value));
+        self.indices.add_singleton()
+    }
+
+    /// Returns the value of the set containing `element`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `element` is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::DisjointSetWith;
+    ///
+    /// let mut dsw = DisjointSetWith::from(vec![vec![1], vec![2]]);
+    /// assert_eq!(dsw.data_of(0), &vec![1]);
+    ///
+    /// dsw.join(1, 0);
+    /// assert_eq!(dsw.data_of(0), &vec![2, 1]);
+    /// ```
+    #[must_use]
+    #[inline]
+    #[allow(clippy::expect_used)]
+    pub fn data_of(&self, element: usize) -> &S {
+        self.data[self.indices.root_of(element)]
+            .as_ref()
+            .expect("every root has a value")
+    }
+
+    /// Returns the value of the set containing `element`, mutably.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `element` is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::DisjointSetWith;
+    ///
+    /// let mut dsw = DisjointSetWith::from(vec![vec![1], vec![2]]);
+    /// dsw.join(0, 1);
+    /// dsw.data_of_mut(1).push(3);
+    /// assert_eq!(dsw.data_of(0), &vec![1, 2, 3]);
+    /// ```
+    #[must_use]
+    #[inline]
+    #[allow(clippy::expect_used)]
+    pub fn data_of_mut(&mut self, element: usize) -> &mut S {
+        let root = self.indices.root_of(element);
+        self.data[root].as_mut().expect("every root has a value")
+    }
+
+    /// If `first_element` and `second_element` are in different sets, joins them together and returns `true`.
+    /// The value of the joined set is the value of the set of `first_element`, after `merge` was called with it and the value of the set of `second_element`.
+    ///
+    /// Otherwise, does nothing and returns `false`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `first_element` or `second_element` is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::DisjointSetWith;
+    ///
+    /// // Keep track of the sum of each set.
+    /// let mut dsw = DisjointSetWith::from(vec![1, 2, 3]);
+    ///
+    /// assert!(dsw.join_by(0, 2, |sum, other_sum| *sum += other_sum));
+    /// assert_eq!(*dsw.data_of(2), 4);
+    ///
+    /// assert!(!dsw.join_by(2, 0, |sum, other_sum| *sum += other_sum));
+    /// assert_eq!(*dsw.data_of(0), 4);
+    /// ```
+    #[allow(clippy::missing_inline_in_public_items, clippy::expect_used)]
+    pub fn join_by<F>(&mut self, first_element: usize, second_element: usize, merge: F) -> bool
+    where
+        F: FnOnce(&mut S, S),
+    {
+        let root_first = self.indices.root_of(first_element);
+        let root_second = self.indices.root_of(second_element);
+
+        if !self.indices.join(root_first, root_second) {
+            return false;
+        }
+
+        let mut value = self.data[root_first]
+            .take()
+            .expect("every root has a value");
+        let other_value = self.data[root_second]
+            .take()
+            .expect("every root has a value");
+        merge(&mut value, other_value);
+        self.data[self.indices.root_of(root_first)] = Some(value);
+
+        true
+    }
+
+    /// Returns `true` if `first_element` and `second_element` are in the same subset.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `first_element` or `second_element` is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::DisjointSetWith;
+    ///
+    /// let mut dsw = DisjointSetWith::from(vec![vec!['a'], vec!['b'], vec!['c']]);
+    /// dsw.join(0, 1);
+    /// assert!(dsw.is_joined(1, 0));
+    /// assert!(!dsw.is_joined(0, 2));
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn is_joined(&self, first_element: usize, second_element: usize) -> bool {
+        self.indices.is_joined(first_element, second_element)
+    }
+
+    /// Returns an element of the subset containing `child`.
+    /// This exact element is returned for every member of the subset.
+    ///
+    /// # Important
+    ///
+    /// The specific choice of the returned element is an implementation detail.
+    /// There are no further guarantees beyond what is documented here.
+    /// If you just want to check if two elements are in the same subset, use [`is_joined`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `child` is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::DisjointSetWith;
+    ///
+    /// let mut dsw = DisjointSetWith::from(vec![vec![0], vec![1], vec![2]]);
+    /// dsw.join(0, 1);
+    /// assert_eq!(dsw.root_of(0), dsw.root_of(1));
+    /// assert_eq!(dsw.root_of(2), 2);
+    /// ```
+    ///
+    /// [`is_joined`]: DisjointSetWith::is_joined
+    #[must_use]
+    #[inline]
+    pub fn root_of(&self, child: usize) -> usize {
+        self.indices.root_of(child)
+    }
+
+    /// Returns the number of elements, regardless of how they are joined together.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::DisjointSetWith;
+    ///
+    /// let mut dsw = DisjointSetWith::from(vec![vec![0], vec![1], vec![2]]);
+    /// dsw.join(0, 1);
+    /// assert_eq!(dsw.len(), 3);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.indices.len()
+    }
+
+    /// Returns `true` if there are no elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::DisjointSetWith;
+    ///
+    /// let mut dsw = DisjointSetWith::new();
+    /// assert!(dsw.is_empty());
+    /// dsw.push(vec![0]);
+    /// assert!(!dsw.is_empty());
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.indices.is_empty()
+    }
+
+    /// Returns the number of disjoint subsets, which is also the number of stored values.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::DisjointSetWith;
+    ///
+    /// let mut dsw = DisjointSetWith::from(vec![vec![0], vec![1], vec![2]]);
+    /// dsw.join(0, 1);
+    /// assert_eq!(dsw.num_sets(), 2);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn num_sets(&self) -> usize {
+        self.indices.num_sets()
+    }
+
+    /// Returns a `Vec` of all sets together with their values. Each entry corresponds to one set, and is a `Vec` of its elements and a reference to its value.
+    ///
+    /// The sets are ordered by their smallest contained element. The elements inside each sets are ordered.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::DisjointSetWith;
+    ///
+    /// let mut dsw = DisjointSetWith::from(vec![vec!['a'], vec!['b'], vec!['c']]);
+    /// dsw.join(2, 0);
+    /// assert_eq!(
+    ///     dsw.sets(),
+    ///     vec![(vec![0, 2], &vec!['c', 'a']), (vec![1], &vec!['b'])]
+    /// );
+    /// ```
+    #[must_use]
+    #[allow(clippy::missing_inline_in_public_items)]
+    pub fn sets(&self) -> Vec<(Vec<usize>, &S)> {
+        self.indices
+            .sets()
+            .into_iter()
+            .map(|set| {
+                let value = self.data_of(set[0]);
+                (set, value)
+            })
+            .collect()
+    }
+}
+
+impl<S> DisjointSetWith<S>
+where
+    S: Merge,
+{
+    /// If `first_element` and `second_element` are in different sets, joins them together and returns `true`.
+    /// The value of the joined set is the value of the set of `first_element`, after the value of the set of `second_element` was [merged] into it.
+    ///
+    /// Otherwise, does nothing and returns `false`.
+    ///
+    /// [merged]: Merge::merge
+    ///
+    /// # Panics
+    ///
+    /// Panics if `first_element` or `second_element` is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::DisjointSetWith;
+    ///
+    /// let mut dsw = DisjointSetWith::from(vec![vec![1], vec![2], vec![3], vec![4]]);
+    ///
+    /// assert!(dsw.join(3, 0));
+    /// assert!(dsw.join(1, 2));
+    /// assert!(dsw.join(0, 1));
+    /// assert!(!dsw.join(2, 3));
+    /// assert_eq!(dsw.data_of(0), &vec![4, 1, 2, 3]);
+    /// ```
+    #[inline]
+    pub fn join(&mut self, first_element: usize, second_element: usize) -> bool {
+        self.join_by(first_element, second_element, S::merge)
+    }
+}
@@ -0,0 +1,165 @@
+// Propagating formatting errors by hand would obscure the output.
+#![allow(clippy::question_mark_used)]
+// Inlined format arguments are not available on old Rust versions.
+#![allow(clippy::uninlined_format_args)]
+// The `alloc` crate is not available on old Rust versions.
+#![allow(clippy::std_instead_of_alloc)]
+
+use std::fmt::{self, Display, Formatter};
+
+use crate::{DisjointSet, DisjointSetVec, ElementIndex, FindStrategy};
+
+/// Writes `elements` like `0, 1, 4`.
+// Contradicts `unreachable_pub` and `clippy::pub_without_shorthand`.
+#[allow(clippy::pub_with_shorthand, clippy::redundant_pub_crate)]
+pub(crate) fn write_list<I>(formatter: &mut Formatter<'_>, elements: I) -> fmt::Result
+where
+    I: IntoIterator,
+    I::Item: Display,
+{
+    let mut separator = "";
+    for element in elements {
+        write!(formatter, "{}{}", separator, element)?;
+        separator = ", ";
+    }
+    Ok(())
+}
+
+/// Writes `set` like `{0, 1, 4}`.
+// Contradicts `unreachable_pub` and `clippy::pub_without_shorthand`.
+#[allow(clippy::pub_with_shorthand, clippy::redundant_pub_crate)]
+pub(crate) fn write_set<I>(formatter: &mut Formatter<'_>, set: I) -> fmt::Result
+where
+    I: IntoIterator,
+    I::Item: Display,
+{
+    write!(formatter, "{{")?;
+    write_list(formatter, set)?;
+    write!(formatter, "}}")
+}
+
+/// Writes `sets` like `{0, 3}, {1}, {2}`.
+fn write_sets<S>(formatter: &mut Formatter<'_>, sets: S) -> fmt::Result
+where
+    S: IntoIterator,
+    S::Item: IntoIterator,
+    <S::Item as IntoIterator>::Item: Display,
+{
+    let mut separator = "";
+    for set in sets {
+        write!(formatter, "{}", separator)?;
+        write_set(formatter, set)?;
+        separator = ", ";
+    }
+    Ok(())
+}
+
+/// Writes `text` as a quoted DOT string.
+fn write_quoted(formatter: &mut Formatter<'_>, text: &str) -> fmt::Result {
+    write!(formatter, "\"")?;
+    for character in text.chars() {
+        match character {
+            '"' | '\\' => write!(formatter, "\\{}", character)?,
+            '\n' => write!(formatter, "\\n")?,
+            _ => write!(formatter, "{}", character)?,
+        }
+    }
+    write!(formatter, "\"")
+}
+
+/// Lists the sets like `{0, 3}, {1}, {2}`, in the order of [`DisjointSet::sets`].
+///
+/// An empty `DisjointSet` is displayed as the empty string.
+impl<O, F, L, I, S> Display for DisjointSet<O, F, L, I, S>
+where
+    F: FindStrategy,
+    I: ElementIndex,
+{
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write_sets(f, self.sets())
+    }
+}
+
+/// Lists the sets of values like `{a, d}, {b}, {c}`, in the order of [`DisjointSet::sets`] of the indices.
+///
+/// An empty `DisjointSetVec<T>` is displayed as the empty string.
+impl<T, O, S> Display for DisjointSetVec<T, O, S>
+where
+    T: Display,
+{
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let sets = self
+            .indices()
+            .sets()
+            .into_iter()
+            .map(|set| set.into_iter().map(move |index| &self[index]));
+        write_sets(f, sets)
+    }
+}
+
+/// Returns the parent forest of `ds` in the DOT language, see [`DisjointSet::to_dot`].
+// Contradicts `unreachable_pub` and `clippy::pub_without_shorthand`.
+#[allow(clippy::pub_with_shorthand, clippy::redundant_pub_crate)]
+pub(crate) fn forest_to_dot<O, F, L, I, S>(ds: &DisjointSet<O, F, L, I, S>) -> String
+where
+    F: FindStrategy,
+    I: ElementIndex,
+{
+    ForestDot(ds).to_string()
+}
+
+/// Returns the sets of values of `dsv` in the DOT language, see [`DisjointSetVec::to_dot`].
+// Contradicts `unreachable_pub` and `clippy::pub_without_shorthand`.
+#[allow(clippy::pub_with_shorthand, clippy::redundant_pub_crate)]
+pub(crate) fn sets_to_dot<T, O, S>(dsv: &DisjointSetVec<T, O, S>) -> String
+where
+    T: Display,
+{
+    SetsDot(dsv).to_string()
+}
+
+/// Displays the parent forest of a `DisjointSet` in the DOT language.
+struct ForestDot<'a, O, F, L, I, S>(&'a DisjointSet<O, F, L, I, S>);
+
+impl<O, F, L, I, S> Display for ForestDot<'_, O, F, L, I, S>
+where
+    F: FindStrategy,
+    I: ElementIndex,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        writeln!(f, "digraph {{")?;
+        for element in 0..self.0.len() {
+            let parent = self.0.parent_of(element);
+            if parent == element {
+                writeln!(f, "    {} [shape=doublecircle];", element)?;
+            } else {
+                writeln!(f, "    {} -> {};", element, parent)?;
+            }
+        }
+        writeln!(f, "}}")
+    }
+}
+
+/// Displays the sets of values of a `DisjointSetVec<T>` in the DOT language.
+struct SetsDot<'a, T, O, S>(&'a DisjointSetVec<T, O, S>);
+
+impl<T, O, S> Display for SetsDot<'_, T, O, S>
+where
+    T: Display,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        writeln!(f, "graph {{")?;
+        for (position, set) in self.0.indices().sets().into_iter().enumerate() {
+            writeln!(f, "    subgraph cluster_{} {{", position)?;
+            for index in set {
+                write!(f, "        {} [label=", index)?;
+                write_quoted(f, &self.0[index].to_string())?;
+                writeln!(f, "];")?;
+            }
+            writeln!(f, "    }}")?;
+        }
+        writeln!(f, "}}")
+    }
+}
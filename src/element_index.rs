@@ -0,0 +1,102 @@
+use std::fmt::Debug;
+
+/// An integer type in which a [`DisjointSet`] stores its elements, to save memory if there are few elements.
+///
+/// This is implemented for `u16`, `u32`, `u64` and `usize`, the default. Implement it for a newtype to use it as index type.
+/// A `DisjointSet` with index type `I`, like a [`CompactDisjointSet<I>`], holds at most [`I::MAX`] elements, see [`DisjointSet::try_with_len`] and [`DisjointSet::try_add_singleton`].
+/// The methods of `DisjointSet` still take and return elements as `usize`.
+///
+/// By default, a `DisjointSet` takes `2 * size_of::<I>() + 1` bytes per element, for the parent, the size of its set and the rank.
+/// So `u32` needs 9 bytes per element, compared to 17 bytes for `usize` on 64-bit targets.
+/// [`MemberTracking`] adds `size_of::<I>()` bytes per element for the member links.
+///
+/// [`MemberTracking`]: crate::MemberTracking
+///
+/// [`DisjointSet`]: crate::DisjointSet
+/// [`CompactDisjointSet<I>`]: crate::CompactDisjointSet
+/// [`I::MAX`]: ElementIndex::MAX
+/// [`DisjointSet::try_with_len`]: crate::DisjointSet::try_with_len
+/// [`DisjointSet::try_add_singleton`]: crate::DisjointSet::try_add_singleton
+///
+/// # Examples
+///
+/// ```
+/// use disjoint::{CompactDisjointSet, ElementIndex};
+///
+/// #[derive(Debug, Clone, Copy)]
+/// struct Vertex(u32);
+///
+/// impl ElementIndex for Vertex {
+///     const MAX: usize = <u32 as ElementIndex>::MAX;
+///
+///     fn from_usize(index: usize) -> Self {
+///         Vertex(u32::from_usize(index))
+///     }
+///
+///     fn to_usize(self) -> usize {
+///         self.0.to_usize()
+///     }
+/// }
+///
+/// let mut ds = CompactDisjointSet::<Vertex>::try_with_len(3).unwrap();
+/// ds.join(0, 2);
+/// assert_eq!(ds.sets(), vec![vec![0, 2], vec![1]]);
+/// ```
+pub trait ElementIndex: Copy + Debug {
+    /// The largest `usize` that can be converted into this type, which is also the maximum number of elements.
+    const MAX: usize;
+
+    /// Converts `index` into this type.
+    ///
+    /// This is only called for `index` of at most [`MAX`](ElementIndex::MAX).
+    fn from_usize(index: usize) -> Self;
+
+    /// Converts this back into the `usize` it was created from by [`from_usize`](ElementIndex::from_usize).
+    fn to_usize(self) -> usize;
+}
+
+impl ElementIndex for usize {
+    const MAX: Self = !0;
+
+    #[inline]
+    fn from_usize(index: usize) -> Self {
+        index
+    }
+
+    #[inline]
+    fn to_usize(self) -> usize {
+        self
+    }
+}
+
+/// Implements [`ElementIndex`] for an unsigned integer type, given its maximum value.
+macro_rules! impl_element_index {
+    ($index:ty, $max:expr) => {
+        // `TryFrom` is not available on old Rust versions, and `MAX` is clamped to `usize`.
+        #[allow(
+            clippy::as_conversions,
+            clippy::cast_possible_truncation,
+            clippy::cast_lossless
+        )]
+        // Contradicts `clippy::unseparated_literal_suffix`.
+        #[allow(clippy::separated_literal_suffix)]
+        impl ElementIndex for $index {
+            // Truncating the maximum value keeps all bits set, so this is `usize::MAX` if the type is wider than `usize`.
+            const MAX: usize = $max as usize;
+
+            #[inline]
+            fn from_usize(index: usize) -> Self {
+                index as $index
+            }
+
+            #[inline]
+            fn to_usize(self) -> usize {
+                self as usize
+            }
+        }
+    };
+}
+
+impl_element_index!(u16, !0_u16);
+impl_element_index!(u32, !0_u32);
+impl_element_index!(u64, !0_u64);
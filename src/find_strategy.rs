@@ -0,0 +1,148 @@
+use std::cell::Cell;
+
+use crate::ElementIndex;
+
+/// A strategy for finding the root of an element of a [`DisjointSet`], deciding how paths are compressed along the way.
+///
+/// The strategy is a type parameter, so the default [`PathSplitting`] costs nothing.
+/// Use [`DisjointSet::with_len_and_strategies`] to choose another one.
+///
+/// [`DisjointSet`]: crate::DisjointSet
+/// [`DisjointSet::with_len_and_strategies`]: crate::DisjointSet::with_len_and_strategies
+///
+/// # Examples
+///
+/// ```
+/// use disjoint::{ByRank, DisjointSet, FullCompression};
+///
+/// let mut ds = DisjointSet::with_len_and_strategies(4, FullCompression, ByRank);
+/// ds.join(0, 1);
+/// ds.join(2, 3);
+/// ds.join(1, 3);
+///
+/// // Finding the root of 3 makes it a direct child of the root.
+/// let root = ds.root_of(3);
+/// assert_eq!(ds.parent_of(3), root);
+/// ```
+pub trait FindStrategy {
+    /// Returns the root of `element` in the forest given by `parents`, where each root is its own parent.
+    ///
+    /// Paths may be compressed by setting the parent of any visited element to one of its ancestors.
+    fn find<I>(&self, parents: &[Cell<I>], element: usize) -> usize
+    where
+        I: ElementIndex;
+}
+
+/// A [`FindStrategy`] that sets the parent of each visited element to the root.
+///
+/// This flattens the path completely, but needs a second pass over it.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[allow(clippy::exhaustive_structs)]
+pub struct FullCompression;
+
+impl FindStrategy for FullCompression {
+    #[inline]
+    fn find<I>(&self, parents: &[Cell<I>], element: usize) -> usize
+    where
+        I: ElementIndex,
+    {
+        let mut root = element;
+        loop {
+            let parent = parents[root].get().to_usize();
+            if parent == root {
+                break;
+            }
+            root = parent;
+        }
+
+        let mut child = element;
+        while child != root {
+            let parent = parents[child].get().to_usize();
+            parents[child].set(I::from_usize(root));
+            child = parent;
+        }
+
+        root
+    }
+}
+
+/// A [`FindStrategy`] that sets the parent of every other visited element to its grandparent, skipping the elements in between.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[allow(clippy::exhaustive_structs)]
+pub struct PathHalving;
+
+impl FindStrategy for PathHalving {
+    #[inline]
+    fn find<I>(&self, parents: &[Cell<I>], element: usize) -> usize
+    where
+        I: ElementIndex,
+    {
+        let mut child = element;
+        loop {
+            let parent = parents[child].get().to_usize();
+            if parent == child {
+                return child;
+            }
+
+            let grandparent = parents[parent].get().to_usize();
+            parents[child].set(I::from_usize(grandparent));
+            child = grandparent;
+        }
+    }
+}
+
+/// A [`FindStrategy`] that sets the parent of each visited element to its grandparent, the default for [`DisjointSet`].
+///
+/// [`DisjointSet`]: crate::DisjointSet
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[allow(clippy::exhaustive_structs)]
+pub struct PathSplitting;
+
+impl FindStrategy for PathSplitting {
+    #[inline]
+    fn find<I>(&self, parents: &[Cell<I>], element: usize) -> usize
+    where
+        I: ElementIndex,
+    {
+        let mut child = element;
+        let mut parent = parents[child].get().to_usize();
+
+        if child == parent {
+            return child;
+        }
+
+        loop {
+            let grandparent = parents[parent].get().to_usize();
+            if parent == grandparent {
+                return parent;
+            }
+
+            parents[child].set(I::from_usize(grandparent));
+            child = parent;
+            parent = grandparent;
+        }
+    }
+}
+
+/// A [`FindStrategy`] that does not change any parent.
+///
+/// Without path compression, finding a root can take time proportional to the height of the tree.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[allow(clippy::exhaustive_structs)]
+pub struct NoCompression;
+
+impl FindStrategy for NoCompression {
+    #[inline]
+    fn find<I>(&self, parents: &[Cell<I>], mut element: usize) -> usize
+    where
+        I: ElementIndex,
+    {
+        loop {
+            let parent = parents[element].get().to_usize();
+            if parent == element {
+                return element;
+            }
+            element = parent;
+        }
+    }
+}
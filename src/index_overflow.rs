@@ -0,0 +1,54 @@
+// The `alloc` crate is not available on old Rust versions.
+#![allow(clippy::std_instead_of_alloc)]
+
+use std::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+};
+
+/// The error returned by [`DisjointSet::try_with_len`], [`DisjointSet::try_add_singleton`] and similar methods if the elements would not fit into the [`ElementIndex`].
+///
+/// [`DisjointSet::try_with_len`]: crate::DisjointSet::try_with_len
+/// [`DisjointSet::try_add_singleton`]: crate::DisjointSet::try_add_singleton
+/// [`ElementIndex`]: crate::ElementIndex
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IndexOverflow {
+    max_len: usize,
+}
+
+impl IndexOverflow {
+    pub(crate) const fn new(max_len: usize) -> Self {
+        Self { max_len }
+    }
+
+    /// Returns the maximum number of elements, which is [`MAX`](crate::ElementIndex::MAX) of the index type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::CompactDisjointSet;
+    ///
+    /// let result = CompactDisjointSet::<u16>::try_with_len(70_000);
+    /// assert_eq!(result.unwrap_err().max_len(), 65_535);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn max_len(&self) -> usize {
+        self.max_len
+    }
+}
+
+impl Display for IndexOverflow {
+    #[inline]
+    // Inlined format arguments are not available on old Rust versions.
+    #[allow(clippy::uninlined_format_args)]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "the index type can hold at most {} elements",
+            self.max_len
+        )
+    }
+}
+
+impl Error for IndexOverflow {}
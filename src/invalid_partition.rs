@@ -0,0 +1,91 @@
+// The `alloc` crate is not available on old Rust versions.
+#![allow(clippy::std_instead_of_alloc)]
+
+use std::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+};
+
+/// The error returned by [`DisjointSet::from_sets`] if the sets do not form a partition.
+///
+/// [`DisjointSet::from_sets`]: crate::DisjointSet::from_sets
+// `#[non_exhaustive]` is not available on old Rust versions.
+#[allow(clippy::exhaustive_enums)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InvalidPartition {
+    /// One of the sets is empty.
+    EmptySet,
+    /// An element is not smaller than the number of elements `len`.
+    OutOfBounds {
+        /// The out of bounds element.
+        element: usize,
+        /// The number of elements.
+        len: usize,
+    },
+    /// An element appears in more than one set, or more than once in the same set.
+    Duplicate {
+        /// The duplicate element.
+        element: usize,
+    },
+    /// An element does not appear in any set.
+    Missing {
+        /// The missing element.
+        element: usize,
+    },
+}
+
+impl Display for InvalidPartition {
+    #[inline]
+    // Inlined format arguments and `Self` in patterns are not available on old Rust versions.
+    #[allow(clippy::uninlined_format_args, clippy::use_self)]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match *self {
+            InvalidPartition::EmptySet => write!(f, "sets must not be empty"),
+            InvalidPartition::OutOfBounds { element, len } => write!(
+                f,
+                "element {} is out of bounds for {} elements",
+                element, len
+            ),
+            InvalidPartition::Duplicate { element } => {
+                write!(f, "element {} appears more than once", element)
+            }
+            InvalidPartition::Missing { element } => write!(f, "element {} is missing", element),
+        }
+    }
+}
+
+impl Error for InvalidPartition {}
+
+/// Calls `join` for the elements `0` to `len - 1` as described by `sets`, validating that `sets` is a partition of exactly these elements.
+// Contradicts `unreachable_pub` and `clippy::pub_without_shorthand`.
+#[allow(clippy::pub_with_shorthand, clippy::redundant_pub_crate)]
+pub(crate) fn join_sets<F>(
+    sets: &[Vec<usize>],
+    len: usize,
+    mut join: F,
+) -> Result<(), InvalidPartition>
+where
+    F: FnMut(usize, usize),
+{
+    let mut seen = vec![false; len];
+
+    for set in sets {
+        let first = match set.first() {
+            Some(&first) => first,
+            None => return Err(InvalidPartition::EmptySet),
+        };
+
+        for &element in set {
+            match seen.get_mut(element) {
+                None => return Err(InvalidPartition::OutOfBounds { element, len }),
+                Some(&mut true) => return Err(InvalidPartition::Duplicate { element }),
+                Some(was_seen) => *was_seen = true,
+            }
+            join(first, element);
+        }
+    }
+
+    seen.iter()
+        .position(|&was_seen| !was_seen)
+        .map_or(Ok(()), |element| Err(InvalidPartition::Missing { element }))
+}
@@ -0,0 +1,76 @@
+// The `alloc` crate is not available on old Rust versions.
+#![allow(clippy::std_instead_of_alloc)]
+
+use std::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+};
+
+/// The error returned by [`DisjointSet::check_invariants`] if the internal forest is inconsistent.
+///
+/// [`DisjointSet::check_invariants`]: crate::DisjointSet::check_invariants
+// `#[non_exhaustive]` is not available on old Rust versions.
+#[allow(clippy::exhaustive_enums)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InvariantViolation {
+    /// There is not exactly one rank per parent.
+    LengthMismatch {
+        /// The number of parents.
+        parents: usize,
+        /// The number of ranks.
+        ranks: usize,
+    },
+    /// The parent of an element is not smaller than the number of elements `len`.
+    ParentOutOfBounds {
+        /// The element with the out of bounds parent.
+        element: usize,
+        /// The out of bounds parent.
+        parent: usize,
+        /// The number of elements.
+        len: usize,
+    },
+    /// Walking the parent links from an element never reaches a root.
+    Cycle {
+        /// An element on the cycle.
+        element: usize,
+    },
+    /// The rank of an element is not smaller than the rank of its parent.
+    RankNotIncreasing {
+        /// The element that is not a root.
+        element: usize,
+        /// The parent of `element`.
+        parent: usize,
+    },
+}
+
+impl Display for InvariantViolation {
+    #[inline]
+    // Inlined format arguments and `Self` in patterns are not available on old Rust versions.
+    #[allow(clippy::uninlined_format_args, clippy::use_self)]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match *self {
+            InvariantViolation::LengthMismatch { parents, ranks } => {
+                write!(f, "there are {} parents, but {} ranks", parents, ranks)
+            }
+            InvariantViolation::ParentOutOfBounds {
+                element,
+                parent,
+                len,
+            } => write!(
+                f,
+                "parent {} of element {} is out of bounds for {} elements",
+                parent, element, len
+            ),
+            InvariantViolation::Cycle { element } => {
+                write!(f, "element {} is on a cycle of parent links", element)
+            }
+            InvariantViolation::RankNotIncreasing { element, parent } => write!(
+                f,
+                "rank of element {} is not smaller than the rank of its parent {}",
+                element, parent
+            ),
+        }
+    }
+}
+
+impl Error for InvariantViolation {}
@@ -0,0 +1,55 @@
+/// An observer of a [`DisjointSet`] or [`DisjointSetVec<T>`], notified about each join that merges two sets.
+///
+/// This allows to keep external data, like search indices or caches, in sync with the partition without wrapping every call to `join`.
+/// The observer is a type parameter, so the default [`NoObserver`] costs nothing.
+///
+/// This trait is implemented for all closures `FnMut(usize, usize, usize)`, which receive the same arguments as [`on_join`].
+///
+/// [`DisjointSet`]: crate::DisjointSet
+/// [`DisjointSetVec<T>`]: crate::DisjointSetVec
+/// [`on_join`]: JoinObserver::on_join
+///
+/// # Examples
+///
+/// ```
+/// use disjoint::DisjointSet;
+///
+/// let mut joins = Vec::new();
+/// let mut ds = DisjointSet::with_len_and_observer(3, |root, absorbed_root, size| {
+///     joins.push((root, absorbed_root, size));
+/// });
+///
+/// ds.join(0, 1);
+/// ds.join(1, 0); // Already joined, so the observer is not notified.
+/// ds.join(2, 1);
+/// drop(ds);
+///
+/// assert_eq!(joins, vec![(0, 1, 2), (0, 2, 3)]);
+/// ```
+pub trait JoinObserver {
+    /// Called after two sets were joined, with the root of the joined set, the root of the set that was absorbed into it, and the size of the joined set.
+    fn on_join(&mut self, root: usize, absorbed_root: usize, size: usize);
+}
+
+impl<F> JoinObserver for F
+where
+    F: FnMut(usize, usize, usize),
+{
+    #[inline]
+    fn on_join(&mut self, root: usize, absorbed_root: usize, size: usize) {
+        self(root, absorbed_root, size);
+    }
+}
+
+/// A [`JoinObserver`] that does nothing, the default for [`DisjointSet`] and [`DisjointSetVec<T>`].
+///
+/// [`DisjointSet`]: crate::DisjointSet
+/// [`DisjointSetVec<T>`]: crate::DisjointSetVec
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[allow(clippy::exhaustive_structs)]
+pub struct NoObserver;
+
+impl JoinObserver for NoObserver {
+    #[inline]
+    fn on_join(&mut self, _root: usize, _absorbed_root: usize, _size: usize) {}
+}
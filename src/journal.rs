@@ -0,0 +1,297 @@
+// Propagating I/O errors by hand would obscure the format.
+#![allow(clippy::question_mark_used)]
+
+use std::io::{self, ErrorKind, Read, Write};
+
+use crate::{
+    binary_format::{self, invalid_data, Checksum, Decoder, RECORD_CHECKSUM_LEN},
+    DisjointSet, ElementIndex, FindStrategy, JoinObserver, LinkStrategy, Tracking,
+};
+
+/// Identifies the binary format of a journal.
+const MAGIC: [u8; 4] = *b"DJSJ";
+
+/// The current version of the journal format.
+const VERSION: u8 = 1;
+
+/// Tags a record of an added singleton, followed by its element.
+const ADD_SINGLETON: u8 = 0;
+
+/// Tags a record of a join, followed by both joined elements.
+const JOIN: u8 = 1;
+
+/// A [`DisjointSet`] that records each [`add_singleton`] and each successful [`join`] in an append-only journal written to `W`.
+///
+/// The journal can be applied to another [`DisjointSet`] via [`DisjointSet::replay`], for example to mirror a partition in another process or to rebuild it after a crash.
+/// Each record is written as soon as the operation happens and has its own checksum, so appending is cheap and a journal that was cut off in the middle of a record can still be replayed.
+/// Records are only handed to `W`, so consider [`flush`] or wrapping a file in a [`BufWriter`] as appropriate.
+///
+/// If writing a record fails, the operation is not applied, but `W` may have received part of the record.
+/// Records written after such an error can not be replayed.
+///
+/// [`add_singleton`]: Journal::add_singleton
+/// [`join`]: Journal::join
+/// [`flush`]: Journal::flush
+/// [`BufWriter`]: std::io::BufWriter
+///
+/// # Examples
+///
+/// ```
+/// use disjoint::{DisjointSet, Journal};
+///
+/// let mut journal = Journal::new(Vec::new()).unwrap();
+/// for _ in 0..4 {
+///     journal.add_singleton().unwrap();
+/// }
+/// journal.join(0, 2).unwrap();
+/// journal.join(3, 1).unwrap();
+///
+/// let (ds, bytes) = journal.into_parts();
+///
+/// let mut mirror = DisjointSet::new();
+/// mirror.replay(&bytes[..]).unwrap();
+/// assert_eq!(mirror, ds);
+/// ```
+#[derive(Debug)]
+pub struct Journal<W> {
+    ds: DisjointSet,
+    writer: W,
+    record: Vec<u8>,
+}
+
+impl<W> Journal<W>
+where
+    W: Write,
+{
+    /// Starts a new journal of an empty [`DisjointSet`], writing the journal header to `writer`.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error that occurs while writing to `writer`.
+    #[allow(clippy::missing_inline_in_public_items)]
+    pub fn new(writer: W) -> io::Result<Self> {
+        let mut journal = Self::resume(DisjointSet::new(), writer);
+        journal.record.extend_from_slice(&MAGIC);
+        journal.record.push(VERSION);
+        journal.write_record()?;
+        Ok(journal)
+    }
+
+    /// Continues a journal, appending records for operations on `ds` to `writer`.
+    ///
+    /// This is meant for `writer` appending to an existing journal that leads to `ds`, for example after `ds` was rebuilt via [`DisjointSet::replay`].
+    /// No header is written.
+    ///
+    /// If the journal ends with a truncated record, for example after a crash, records appended behind it can not be replayed.
+    /// So truncate the journal to the valid length returned by [`DisjointSet::replay`] first.
+    /// If that length is `0`, the header itself is incomplete, so start over with [`Journal::new`] instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::{DisjointSet, Journal};
+    ///
+    /// let mut journal = Journal::new(Vec::new()).unwrap();
+    /// journal.add_singleton().unwrap();
+    /// journal.add_singleton().unwrap();
+    /// journal.add_singleton().unwrap();
+    /// let (_, mut bytes) = journal.into_parts();
+    ///
+    /// // A crash cut off the last record.
+    /// bytes.pop();
+    ///
+    /// // Recover after a restart.
+    /// let mut ds = DisjointSet::new();
+    /// let (_, valid_len) = ds.replay(&bytes[..]).unwrap();
+    /// bytes.truncate(valid_len as usize);
+    /// let mut journal = Journal::resume(ds, &mut bytes);
+    /// journal.join(0, 1).unwrap();
+    ///
+    /// let mut mirror = DisjointSet::new();
+    /// assert_eq!(mirror.replay(&bytes[..]).unwrap().0, 3);
+    /// assert!(mirror.is_joined(0, 1));
+    /// ```
+    #[allow(clippy::missing_inline_in_public_items)]
+    // `Vec::new` is not const on old Rust versions.
+    #[allow(clippy::missing_const_for_fn)]
+    pub fn resume(ds: DisjointSet, writer: W) -> Self {
+        Self {
+            ds,
+            writer,
+            record: Vec::new(),
+        }
+    }
+
+    /// Writes the current record, followed by its checksum.
+    fn write_record(&mut self) -> io::Result<()> {
+        let mut checksum = Checksum::new();
+        checksum.update(&self.record);
+        self.record
+            .extend_from_slice(&checksum.to_bytes()[..RECORD_CHECKSUM_LEN]);
+        let result = self.writer.write_all(&self.record);
+        self.record.clear();
+        result
+    }
+
+    /// Adds a new element in its own set, records it and returns its index, see [`DisjointSet::add_singleton`].
+    ///
+    /// # Errors
+    ///
+    /// Returns any error that occurs while writing to `W`. In this case, no element is added.
+    #[allow(clippy::missing_inline_in_public_items)]
+    pub fn add_singleton(&mut self) -> io::Result<usize> {
+        let element = self.ds.len();
+        self.record.push(ADD_SINGLETON);
+        binary_format::push_varint(&mut self.record, element);
+        self.write_record()?;
+        Ok(self.ds.add_singleton())
+    }
+
+    /// If `first_element` and `second_element` are in different sets, joins them together, records the join and returns `true`, see [`DisjointSet::join`].
+    ///
+    /// Otherwise, does nothing and returns `false`.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error that occurs while writing to `W`. In this case, the sets are not joined.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `first_element` or `second_element` is out of bounds.
+    #[allow(clippy::missing_inline_in_public_items)]
+    pub fn join(&mut self, first_element: usize, second_element: usize) -> io::Result<bool> {
+        if self.ds.is_joined(first_element, second_element) {
+            return Ok(false);
+        }
+        self.record.push(JOIN);
+        binary_format::push_varint(&mut self.record, first_element);
+        binary_format::push_varint(&mut self.record, second_element);
+        self.write_record()?;
+        Ok(self.ds.join(first_element, second_element))
+    }
+
+    /// Flushes `W`, so all records written so far reach their destination.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error that occurs while flushing `W`.
+    #[allow(clippy::missing_inline_in_public_items)]
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+impl<W> Journal<W> {
+    /// Returns the journaled [`DisjointSet`].
+    #[must_use]
+    #[inline]
+    pub const fn disjoint_set(&self) -> &DisjointSet {
+        &self.ds
+    }
+
+    /// Returns a reference to the writer of the journal.
+    #[must_use]
+    #[inline]
+    pub const fn get_ref(&self) -> &W {
+        &self.writer
+    }
+
+    /// Returns the journaled [`DisjointSet`] and the writer of the journal.
+    #[must_use]
+    #[allow(clippy::missing_inline_in_public_items)]
+    pub fn into_parts(self) -> (DisjointSet, W) {
+        (self.ds, self.writer)
+    }
+}
+
+/// A record read from a journal.
+enum Record {
+    AddSingleton(usize),
+    Join(usize, usize),
+}
+
+/// Turns an [`ErrorKind::UnexpectedEof`] into `None`, as the end of a journal may be truncated.
+fn unless_truncated<T>(result: io::Result<T>) -> io::Result<Option<T>> {
+    match result {
+        Ok(value) => Ok(Some(value)),
+        Err(error) => {
+            if error.kind() == ErrorKind::UnexpectedEof {
+                Ok(None)
+            } else {
+                Err(error)
+            }
+        }
+    }
+}
+
+/// Reads the next record, failing with [`ErrorKind::UnexpectedEof`] at the end of the journal.
+fn read_record<R>(decoder: &mut Decoder<R>) -> io::Result<Record>
+where
+    R: Read,
+{
+    let mut tag = [0];
+    decoder.read_bytes(&mut tag)?;
+    let record = match tag[0] {
+        ADD_SINGLETON => Record::AddSingleton(decoder.read_varint()?),
+        JOIN => Record::Join(decoder.read_varint()?, decoder.read_varint()?),
+        _ => return Err(invalid_data("unknown record")),
+    };
+    decoder.finish_record()?;
+    Ok(record)
+}
+
+/// Applies the journal in `reader` to `ds` and returns the number of records and the length of the valid prefix in bytes, see [`DisjointSet::replay`].
+// Contradicts `unreachable_pub` and `clippy::pub_without_shorthand`.
+#[allow(clippy::pub_with_shorthand, clippy::redundant_pub_crate)]
+pub(crate) fn replay<O, F, L, I, S, R>(
+    ds: &mut DisjointSet<O, F, L, I, S>,
+    reader: R,
+) -> io::Result<(usize, u64)>
+where
+    O: JoinObserver,
+    F: FindStrategy,
+    L: LinkStrategy,
+    I: ElementIndex,
+    S: Tracking,
+    R: Read,
+{
+    let mut decoder = Decoder::new(reader);
+
+    let mut header = [0; 5];
+    if unless_truncated(decoder.read_bytes(&mut header))?.is_none() {
+        return Ok((0, 0));
+    }
+    if header[..4] != MAGIC {
+        return Err(invalid_data("not a journal"));
+    }
+    if header[4] != VERSION {
+        return Err(invalid_data("unsupported version"));
+    }
+    if unless_truncated(decoder.finish_record())?.is_none() {
+        return Ok((0, 0));
+    }
+
+    let mut number_records = 0;
+    let mut valid_len = decoder.position();
+    while let Some(record) = unless_truncated(read_record(&mut decoder))? {
+        match record {
+            Record::AddSingleton(element) => {
+                if element > ds.len() {
+                    return Err(invalid_data("element out of bounds"));
+                }
+                if element == ds.len() && ds.try_add_singleton().is_err() {
+                    return Err(invalid_data("too many elements for the index type"));
+                }
+            }
+            Record::Join(first_element, second_element) => {
+                if first_element >= ds.len() || second_element >= ds.len() {
+                    return Err(invalid_data("element out of bounds"));
+                }
+                ds.join(first_element, second_element);
+            }
+        }
+        number_records += 1;
+        valid_len = decoder.position();
+    }
+    Ok((number_records, valid_len))
+}
@@ -1,4 +1,3 @@
-#![allow(clippy::allow_attributes_without_reason)]
 #![allow(unknown_lints)]
 // Disabled to support old Rust versions.
 // Activate these when developing locally on a recent toolchain.
@@ -46,9 +45,15 @@
     clippy::implicit_return,
     clippy::indexing_slicing,
     clippy::arithmetic_side_effects,
-    clippy::arithmetic_side_effects,
     clippy::missing_trait_methods,
-    clippy::single_call_fn
+    clippy::single_call_fn,
+    // Contradicts `clippy::question_mark_used`.
+    clippy::return_and_then,
+    // `#[expect]` and `reason = ".."` are not available on old Rust versions.
+    clippy::allow_attributes,
+    clippy::allow_attributes_without_reason,
+    clippy::arbitrary_source_item_ordering,
+    clippy::doc_paragraphs_missing_punctuation
 )]
 
 //! [![Tests](https://github.com/jogru0/disjoint/actions/workflows/tests.yml/badge.svg?branch=master)](https://github.com/jogru0/disjoint/actions/workflows/tests.yml?query=branch:master)
@@ -62,6 +67,28 @@
 //!
 //! [`DisjointSetVec<T>`] combines a `DisjointSet` with a `Vec<T>`, so it manages contiguous data entries `T` and keeps track of which entries are joined. Use this if you want the disjoint-set data structure to contain some additional data `T` for each element.
 //!
+//! [`ConcurrentDisjointSet`] is a lock-free variant of `DisjointSet` that can be joined and queried from many threads at once. Use this if you want to share a disjoint set between threads without wrapping it in a `Mutex`.
+//!
+//! [`RollbackDisjointSet`] is a variant of `DisjointSet` that can undo its modifications by rolling back to a snapshot. Use this for backtracking algorithms, instead of cloning a `DisjointSet` to restore it later.
+//!
+//! [`PersistentDisjointSet`] is a fully persistent variant of `DisjointSet`, where joining returns a new version that shares most of its memory with the old one. Use this if you need to keep many versions of a disjoint set around.
+//!
+//! [`DeletableDisjointSet`] is a variant of `DisjointSet` that additionally supports taking elements out of their set again. Use this if joins might have to be revoked for single elements.
+//!
+//! [`DisjointSetMap`] is a disjoint set over arbitrary hashable keys instead of indices. Use this if your elements are not naturally numbered, like strings or coordinates.
+//!
+//! [`WeightedDisjointSet<G>`] is a variant of `DisjointSet` that additionally tracks the difference between joined elements, in some [`Group`] `G`. Use this for consistency checks of equations like `x - y = 5`, or for parity constraints.
+//!
+//! [`DisjointSetWith<S>`] combines a `DisjointSet` with one value `S` per set, which is [merged] whenever two sets are joined. Use this if you want to keep aggregate data per set, like a sum or a bounding box.
+//!
+//! [`RepresentativeDisjointSet<P>`] is a variant of `DisjointSet` where `root_of` returns the representative chosen by a [`RepresentativePolicy`], like the smallest element. Use this if you need canonical identifiers for the sets that do not depend on the order of the joins.
+//!
+//! [merged]: Merge
+//!
+//! # Features
+//!
+//! The optional `serde` feature implements `Serialize` and `Deserialize` for `DisjointSet` and `DisjointSetVec<T>`. Only the partition is serialized, as returned by [`DisjointSet::sets`], not the internal forest.
+//!
 //! # Examples
 //!
 //! Disjoint set data structures can be applied to find the [minimal spanning forest] of an [undirected edge-weighted graph]. Let's assume we work with the following graph interface:
@@ -172,9 +199,75 @@
 //! additional terms or conditions.
 
 mod disjoint_set;
-pub use self::disjoint_set::DisjointSet;
+pub use self::disjoint_set::{CompactDisjointSet, DisjointSet};
+
+mod join_observer;
+pub use self::join_observer::{JoinObserver, NoObserver};
+
+mod find_strategy;
+pub use self::find_strategy::{
+    FindStrategy, FullCompression, NoCompression, PathHalving, PathSplitting,
+};
+
+mod link_strategy;
+pub use self::link_strategy::{ByIndex, ByRank, BySize, LinkStrategy, RandomizedIndex};
+
+mod tracking;
+pub use self::tracking::{MemberTracking, NoTracking, Tracking, TracksMembers};
+
+mod element_index;
+pub use self::element_index::ElementIndex;
+
+mod index_overflow;
+pub use self::index_overflow::IndexOverflow;
 
 mod disjoint_set_vec;
 pub use self::disjoint_set_vec::DisjointSetVec;
 
+mod concurrent_disjoint_set;
+pub use self::concurrent_disjoint_set::ConcurrentDisjointSet;
+
+mod rollback_disjoint_set;
+pub use self::rollback_disjoint_set::{RollbackDisjointSet, RollbackSnapshot};
+
+mod persistent_array;
+mod persistent_disjoint_set;
+pub use self::persistent_disjoint_set::PersistentDisjointSet;
+
+mod deletable_disjoint_set;
+pub use self::deletable_disjoint_set::DeletableDisjointSet;
+
+mod disjoint_set_map;
+pub use self::disjoint_set_map::DisjointSetMap;
+
+mod weighted_disjoint_set;
+pub use self::weighted_disjoint_set::{Contradiction, Group, WeightedDisjointSet};
+
+mod disjoint_set_with;
+pub use self::disjoint_set_with::{DisjointSetWith, Merge};
+
+mod representative_disjoint_set;
+pub use self::representative_disjoint_set::{
+    ByPriority, FirstJoined, MaxIndex, MinIndex, RepresentativeDisjointSet, RepresentativePolicy,
+};
+
+mod binary_format;
+
+mod journal;
+pub use self::journal::Journal;
+
+mod invalid_partition;
+pub use self::invalid_partition::InvalidPartition;
+
+mod invariant_violation;
+pub use self::invariant_violation::InvariantViolation;
+
+mod display;
+
+mod partition_diff;
+pub use self::partition_diff::{MergeGroup, PartitionDiff};
+
+#[cfg(feature = "serde")]
+mod serde_impls;
+
 mod macros;
@@ -0,0 +1,241 @@
+// Underscore imports are not available on old Rust versions.
+#[allow(clippy::unused_trait_names)]
+use std::{
+    collections::hash_map::RandomState,
+    hash::{BuildHasher, Hasher},
+};
+
+use crate::ElementIndex;
+
+/// A strategy for linking the trees of two sets of a [`DisjointSet`] that are joined, deciding which root stays a root.
+///
+/// The strategy is a type parameter, so the default [`ByRank`] costs nothing.
+/// Use [`DisjointSet::with_len_and_strategies`] to choose another one.
+///
+/// [`DisjointSet`]: crate::DisjointSet
+/// [`DisjointSet::with_len_and_strategies`]: crate::DisjointSet::with_len_and_strategies
+///
+/// # Examples
+///
+/// ```
+/// use disjoint::{ByIndex, DisjointSet, PathSplitting};
+///
+/// // The root of each set is its smallest element.
+/// let mut ds = DisjointSet::with_len_and_strategies(4, PathSplitting, ByIndex);
+/// ds.join(3, 1);
+/// ds.join(2, 3);
+/// assert_eq!(ds.root_of(2), 1);
+/// ```
+pub trait LinkStrategy {
+    /// Whether the ranks strictly increase along parent links, which is checked by [`DisjointSet::check_invariants`].
+    ///
+    /// [`DisjointSet::check_invariants`]: crate::DisjointSet::check_invariants
+    const MAINTAINS_RANKS: bool = false;
+
+    /// Returns `true` if `first_root` should stay the root of the joined set, or `false` if `second_root` should.
+    ///
+    /// When two sets are joined, `first_root` is the root of the set of the first argument of `join`, and `second_root` the one of the second argument.
+    /// The entries of `ranks` and `sizes` at the roots hold their rank and the size of their set. Ranks may be updated.
+    fn keeps_first<I>(
+        &mut self,
+        first_root: usize,
+        second_root: usize,
+        ranks: &mut [u8],
+        sizes: &[I],
+    ) -> bool
+    where
+        I: ElementIndex;
+}
+
+/// A [`LinkStrategy`] that links the root of lower rank below the other one, the default for [`DisjointSet`].
+///
+/// If both ranks are equal, the root of the first argument of `join` stays the root and its rank increases.
+/// This keeps the height of each tree logarithmic in the size of its set.
+///
+/// [`DisjointSet`]: crate::DisjointSet
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[allow(clippy::exhaustive_structs)]
+pub struct ByRank;
+
+impl LinkStrategy for ByRank {
+    const MAINTAINS_RANKS: bool = true;
+
+    #[inline]
+    fn keeps_first<I>(
+        &mut self,
+        first_root: usize,
+        second_root: usize,
+        ranks: &mut [u8],
+        _sizes: &[I],
+    ) -> bool
+    where
+        I: ElementIndex,
+    {
+        let rank_second = ranks[second_root];
+        let rank_first = &mut ranks[first_root];
+
+        if *rank_first < rank_second {
+            return false;
+        }
+        if *rank_first == rank_second {
+            *rank_first += 1;
+        }
+        true
+    }
+}
+
+/// A [`LinkStrategy`] that links the root of the smaller set below the other one.
+///
+/// If both sets have the same size, the root of the first argument of `join` stays the root.
+/// This keeps the height of each tree logarithmic in the size of its set.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[allow(clippy::exhaustive_structs)]
+pub struct BySize;
+
+impl LinkStrategy for BySize {
+    #[inline]
+    fn keeps_first<I>(
+        &mut self,
+        first_root: usize,
+        second_root: usize,
+        _ranks: &mut [u8],
+        sizes: &[I],
+    ) -> bool
+    where
+        I: ElementIndex,
+    {
+        sizes[first_root].to_usize() >= sizes[second_root].to_usize()
+    }
+}
+
+/// A [`LinkStrategy`] where the smaller root stays the root, so the root of each set is its smallest element.
+///
+/// Trees are not balanced, so combine this with path compression.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[allow(clippy::exhaustive_structs)]
+pub struct ByIndex;
+
+impl LinkStrategy for ByIndex {
+    #[inline]
+    fn keeps_first<I>(
+        &mut self,
+        first_root: usize,
+        second_root: usize,
+        _ranks: &mut [u8],
+        _sizes: &[I],
+    ) -> bool
+    where
+        I: ElementIndex,
+    {
+        first_root < second_root
+    }
+}
+
+/// A [`LinkStrategy`] where the root with the higher pseudorandom priority stays the root.
+///
+/// The priorities are a well mixed hash of the elements, keyed by a seed. [`RandomizedIndex::new`] picks a random seed for each instance, so no fixed order of joins is bad for all instances.
+/// Use [`RandomizedIndex::with_seed`] for reproducible linking.
+/// Combined with path compression, this keeps the expected cost of finding a root low without storing ranks or sizes.
+///
+/// # Examples
+///
+/// ```
+/// use disjoint::RandomizedIndex;
+///
+/// assert_eq!(RandomizedIndex::with_seed(42).seed(), 42);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RandomizedIndex {
+    seed: u64,
+}
+
+impl RandomizedIndex {
+    /// Constructs a new `RandomizedIndex` with a random seed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::{DisjointSet, PathSplitting, RandomizedIndex};
+    ///
+    /// let mut ds = DisjointSet::with_len_and_strategies(4, PathSplitting, RandomizedIndex::new());
+    /// ds.join(0, 1);
+    /// assert!(ds.root_of(0) < 2);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            seed: RandomState::new().build_hasher().finish(),
+        }
+    }
+
+    /// Constructs a new `RandomizedIndex` with the given `seed`, so that linking is the same for equal seeds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::{DisjointSet, PathSplitting, RandomizedIndex};
+    ///
+    /// let mut first = DisjointSet::with_len_and_strategies(4, PathSplitting, RandomizedIndex::with_seed(7));
+    /// let mut second = DisjointSet::with_len_and_strategies(4, PathSplitting, RandomizedIndex::with_seed(7));
+    /// first.join(0, 1);
+    /// second.join(0, 1);
+    /// assert_eq!(first.root_of(0), second.root_of(0));
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn with_seed(seed: u64) -> Self {
+        Self { seed }
+    }
+
+    /// Returns the seed of the priorities.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::RandomizedIndex;
+    ///
+    /// assert_eq!(RandomizedIndex::with_seed(3).seed(), 3);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Returns the priority of `element`, which is different for all elements.
+    // `u64::try_from` is not available on old Rust versions, and `usize` has at most 64 bits.
+    #[allow(clippy::as_conversions)]
+    // Local variables in `const fn` are not available on old Rust versions.
+    #[allow(clippy::missing_const_for_fn)]
+    fn priority(self, element: usize) -> u64 {
+        // The finalizer of SplitMix64, a bijection on `u64`.
+        let mut hash = element as u64 ^ self.seed;
+        hash = (hash ^ (hash >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        hash = (hash ^ (hash >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        hash ^ (hash >> 31)
+    }
+}
+
+impl Default for RandomizedIndex {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LinkStrategy for RandomizedIndex {
+    #[inline]
+    fn keeps_first<I>(
+        &mut self,
+        first_root: usize,
+        second_root: usize,
+        _ranks: &mut [u8],
+        _sizes: &[I],
+    ) -> bool
+    where
+        I: ElementIndex,
+    {
+        self.priority(first_root) > self.priority(second_root)
+    }
+}
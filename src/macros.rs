@@ -49,3 +49,39 @@ macro_rules! disjoint_set_vec {
     );
     ($($x:expr,)*) => (disjoint_set_vec![$($x),*]);
 }
+
+/// Creates a [`DisjointSet`] from a list of groups, where exactly the elements in each group are joined.
+///
+/// The groups and the elements inside each group may be in any order, like for [`DisjointSet::from_sets`].
+///
+/// ```
+/// use disjoint::disjoint_set;
+///
+/// let ds = disjoint_set![[0, 1], [2], [5, 3, 4]];
+/// assert_eq!(ds.sets(), vec![vec![0, 1], vec![2], vec![3, 4, 5]]);
+/// assert!(disjoint_set![].is_empty());
+/// ```
+///
+/// # Panics
+///
+/// Panics if the groups do not form a partition of the elements from `0` to `n - 1`, where `n` is the total number of elements in all groups.
+///
+/// ```should_panic
+/// use disjoint::disjoint_set;
+///
+/// // Panics, since the element 1 appears twice.
+/// let ds = disjoint_set![[0, 1], [1]];
+/// ```
+///
+/// [`DisjointSet`]: crate::DisjointSet
+/// [`DisjointSet::from_sets`]: crate::DisjointSet::from_sets
+#[macro_export]
+macro_rules! disjoint_set {
+    ($([$($x:expr),* $(,)*]),*) => (
+        match $crate::DisjointSet::from_sets(&[$(vec![$($x),*]),*]) {
+            Ok(disjoint_set) => disjoint_set,
+            Err(error) => panic!("invalid partition: {}", error),
+        }
+    );
+    ($([$($x:expr),* $(,)*],)*) => (disjoint_set![$([$($x),*]),*]);
+}
@@ -0,0 +1,214 @@
+// Propagating formatting errors by hand would obscure the output.
+#![allow(clippy::question_mark_used)]
+// Inlined format arguments are not available on old Rust versions.
+#![allow(clippy::uninlined_format_args)]
+// The `alloc` crate is not available on old Rust versions.
+#![allow(clippy::std_instead_of_alloc)]
+
+use std::{
+    collections::HashMap,
+    fmt::{self, Display, Formatter},
+    ops::Range,
+    slice,
+};
+
+use crate::{
+    display::{write_list, write_set},
+    DisjointSet, ElementIndex, FindStrategy,
+};
+
+/// Several sets of an old partition that were merged into one set of a new partition, as reported by a [`PartitionDiff`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MergeGroup {
+    sources: Vec<Vec<usize>>,
+    target: Vec<usize>,
+}
+
+impl MergeGroup {
+    /// Returns the merged sets of the old partition, ordered by their smallest element. The elements inside each set are ordered.
+    ///
+    /// Only the elements that ended up in the [`target`](MergeGroup::target) are contained. Each element added in the new partition is a singleton source.
+    #[must_use]
+    #[inline]
+    pub fn sources(&self) -> &[Vec<usize>] {
+        &self.sources
+    }
+
+    /// Returns the set of the new partition the [`sources`](MergeGroup::sources) were merged into. The elements are ordered.
+    #[must_use]
+    #[inline]
+    pub fn target(&self) -> &[usize] {
+        &self.target
+    }
+}
+
+impl Display for MergeGroup {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "merged ")?;
+        for (position, source) in self.sources.iter().enumerate() {
+            if position > 0 {
+                let separator = if position + 1 == self.sources.len() {
+                    " and "
+                } else {
+                    ", "
+                };
+                write!(f, "{}", separator)?;
+            }
+            write_set(f, source)?;
+        }
+        write!(f, " into ")?;
+        write_set(f, &self.target)
+    }
+}
+
+/// The difference between two partitions, as returned by [`DisjointSet::diff`].
+///
+/// The old partition is extended by singletons for the elements that were added in the new partition.
+/// A [`MergeGroup`] is reported for each set of the new partition that contains elements of more than one set of the extended old partition.
+/// An element is reported as [`moved`](PartitionDiff::moved) if it is no longer joined with the smallest element of its old set.
+/// If the new partition only arose from the old one by adding and joining elements, no element is moved.
+///
+/// The `Display` implementation lists one change per line.
+///
+/// # Examples
+///
+/// ```
+/// use disjoint::{disjoint_set, DisjointSet};
+///
+/// let old = disjoint_set![[0, 1], [2], [3], [4]];
+/// let mut new = old.clone();
+/// new.join(1, 4);
+/// new.add_singleton();
+/// new.join(5, 3);
+///
+/// let diff = DisjointSet::diff(&old, &new);
+/// let targets: Vec<_> = diff.merges().map(|merge| merge.target()).collect();
+/// assert_eq!(targets, vec![&[0, 1, 4][..], &[3, 5][..]]);
+/// assert_eq!(diff.added(), 5..6);
+/// assert_eq!(
+///     diff.to_string(),
+///     "merged {0, 1} and {4} into {0, 1, 4}\nmerged {3} and {5} into {3, 5}\nadded 5"
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartitionDiff {
+    merges: Vec<MergeGroup>,
+    moved: Vec<usize>,
+    added: Range<usize>,
+}
+
+impl PartitionDiff {
+    pub(crate) fn new<O, F, L, I, S>(
+        old: &DisjointSet<O, F, L, I, S>,
+        new: &DisjointSet<O, F, L, I, S>,
+    ) -> Self
+    where
+        F: FindStrategy,
+        I: ElementIndex,
+    {
+        assert!(
+            old.len() <= new.len(),
+            "new partition has fewer elements than old partition"
+        );
+
+        let mut merges = Vec::new();
+        for target in new.sets() {
+            let mut sources: Vec<Vec<usize>> = Vec::new();
+            let mut old_root_to_source = HashMap::new();
+
+            for &element in &target {
+                if element < old.len() {
+                    let next_source = sources.len();
+                    let &mut source = old_root_to_source
+                        .entry(old.root_of(element))
+                        .or_insert(next_source);
+                    if source == next_source {
+                        sources.push(Vec::with_capacity(1));
+                    }
+                    sources[source].push(element);
+                } else {
+                    sources.push(vec![element]);
+                }
+            }
+
+            if sources.len() > 1 {
+                merges.push(MergeGroup { sources, target });
+            }
+        }
+
+        let mut moved = Vec::new();
+        let mut old_root_to_new_root = HashMap::new();
+        for element in 0..old.len() {
+            let new_root = new.root_of(element);
+            if *old_root_to_new_root
+                .entry(old.root_of(element))
+                .or_insert(new_root)
+                != new_root
+            {
+                moved.push(element);
+            }
+        }
+
+        Self {
+            merges,
+            moved,
+            added: old.len()..new.len(),
+        }
+    }
+
+    /// Returns an iterator over all merge groups, ordered by the smallest element of their [`target`](MergeGroup::target).
+    #[inline]
+    pub fn merges(&self) -> slice::Iter<'_, MergeGroup> {
+        self.merges.iter()
+    }
+
+    /// Returns all moved elements in ascending order.
+    #[must_use]
+    #[inline]
+    pub fn moved(&self) -> &[usize] {
+        &self.moved
+    }
+
+    /// Returns the range of elements that were added in the new partition.
+    #[must_use]
+    #[inline]
+    pub fn added(&self) -> Range<usize> {
+        self.added.clone()
+    }
+
+    /// Returns `true` if both partitions are equal.
+    #[must_use]
+    #[inline]
+    // `Vec::is_empty` is not const on old Rust versions.
+    #[allow(clippy::missing_const_for_fn)]
+    pub fn is_empty(&self) -> bool {
+        self.merges.is_empty() && self.moved.is_empty() && self.added.start == self.added.end
+    }
+}
+
+impl Display for PartitionDiff {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return write!(f, "no changes");
+        }
+
+        let mut separator = "";
+        for merge in &self.merges {
+            write!(f, "{}{}", separator, merge)?;
+            separator = "\n";
+        }
+        if !self.moved.is_empty() {
+            write!(f, "{}moved ", separator)?;
+            write_list(f, &self.moved)?;
+            separator = "\n";
+        }
+        if self.added.start != self.added.end {
+            write!(f, "{}added ", separator)?;
+            write_list(f, self.added())?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,219 @@
+// The `alloc` crate is not available on old Rust versions.
+#![allow(clippy::std_instead_of_alloc)]
+
+use std::sync::Arc;
+
+const BITS: u32 = 5;
+const WIDTH: usize = 1 << BITS;
+const MASK: usize = WIDTH - 1;
+
+// `Self` in type definitions is not available on old Rust versions.
+#[allow(clippy::use_self)]
+#[derive(Debug)]
+enum Node<T> {
+    Branch(Vec<Arc<Node<T>>>),
+    Leaf(Vec<T>),
+}
+
+/// An immutable array, where modifications return a new version that shares most of its memory with the old one.
+///
+/// The values are stored in the leaves of a tree with a branching factor of 32, so all operations take `O(log_32 n)` time.
+/// Modifications only copy the nodes on the path to the modified value.
+#[derive(Debug)]
+// Contradicts `unreachable_pub` and `clippy::pub_without_shorthand`.
+#[allow(clippy::pub_with_shorthand, clippy::redundant_pub_crate)]
+pub(crate) struct PersistentArray<T> {
+    root: Arc<Node<T>>,
+    len: usize,
+    /// The number of index bits consumed above the leaves. `0` means the root is a leaf.
+    shift: u32,
+}
+
+impl<T> Clone for PersistentArray<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            root: Arc::clone(&self.root),
+            len: self.len,
+            shift: self.shift,
+        }
+    }
+}
+
+impl<T> PersistentArray<T> {
+    pub(crate) const fn len(&self) -> usize {
+        self.len
+    }
+}
+
+impl<T: Clone> PersistentArray<T> {
+    pub(crate) fn from_slice(values: &[T]) -> Self {
+        let len = values.len();
+
+        let mut nodes: Vec<Arc<Node<T>>> = values
+            .chunks(WIDTH)
+            .map(|chunk| Arc::new(Node::Leaf(chunk.to_vec())))
+            .collect();
+        let mut shift = 0;
+
+        if nodes.is_empty() {
+            nodes.push(Arc::new(Node::Leaf(Vec::new())));
+        }
+
+        while nodes.len() > 1 {
+            nodes = nodes
+                .chunks(WIDTH)
+                .map(|chunk| Arc::new(Node::Branch(chunk.to_vec())))
+                .collect();
+            shift += BITS;
+        }
+
+        Self {
+            root: Arc::clone(&nodes[0]),
+            len,
+            shift,
+        }
+    }
+
+    // Contradicts `clippy::ref_patterns`.
+    #[allow(clippy::pattern_type_mismatch)]
+    pub(crate) fn get(&self, index: usize) -> &T {
+        assert!(index < self.len, "index out of bounds");
+
+        let mut node = &*self.root;
+        let mut level = self.shift;
+        loop {
+            match node {
+                Node::Branch(children) => {
+                    node = &children[(index >> level) & MASK];
+                    level -= BITS;
+                }
+                Node::Leaf(values) => return &values[index & MASK],
+            }
+        }
+    }
+
+    // Contradicts `clippy::ref_patterns`.
+    #[allow(clippy::pattern_type_mismatch)]
+    pub(crate) fn set(&self, index: usize, value: T) -> Self {
+        fn set_in<T: Clone>(node: &Node<T>, level: u32, index: usize, value: T) -> Node<T> {
+            match node {
+                Node::Branch(children) => {
+                    let mut new_children = children.clone();
+                    let child_id = (index >> level) & MASK;
+                    new_children[child_id] =
+                        Arc::new(set_in(&children[child_id], level - BITS, index, value));
+                    Node::Branch(new_children)
+                }
+                Node::Leaf(values) => {
+                    let mut new_values = values.clone();
+                    new_values[index & MASK] = value;
+                    Node::Leaf(new_values)
+                }
+            }
+        }
+
+        assert!(index < self.len, "index out of bounds");
+
+        Self {
+            root: Arc::new(set_in(&self.root, self.shift, index, value)),
+            len: self.len,
+            shift: self.shift,
+        }
+    }
+
+    // Contradicts `clippy::ref_patterns`.
+    #[allow(clippy::pattern_type_mismatch)]
+    pub(crate) fn push(&self, value: T) -> Self {
+        fn new_path<T>(level: u32, value: T) -> Node<T> {
+            if level == 0 {
+                Node::Leaf(vec![value])
+            } else {
+                Node::Branch(vec![Arc::new(new_path(level - BITS, value))])
+            }
+        }
+
+        fn push_in<T: Clone>(node: &Node<T>, level: u32, index: usize, value: T) -> Node<T> {
+            match node {
+                Node::Branch(children) => {
+                    let mut new_children = children.clone();
+                    let child_id = (index >> level) & MASK;
+                    if child_id < children.len() {
+                        new_children[child_id] =
+                            Arc::new(push_in(&children[child_id], level - BITS, index, value));
+                    } else {
+                        new_children.push(Arc::new(new_path(level - BITS, value)));
+                    }
+                    Node::Branch(new_children)
+                }
+                Node::Leaf(values) => {
+                    let mut new_values = values.clone();
+                    new_values.push(value);
+                    Node::Leaf(new_values)
+                }
+            }
+        }
+
+        let capacity = WIDTH << self.shift;
+
+        if self.len == capacity {
+            let new_root = Node::Branch(vec![
+                Arc::clone(&self.root),
+                Arc::new(new_path(self.shift, value)),
+            ]);
+            Self {
+                root: Arc::new(new_root),
+                len: self.len + 1,
+                shift: self.shift + BITS,
+            }
+        } else {
+            Self {
+                root: Arc::new(push_in(&self.root, self.shift, self.len, value)),
+                len: self.len + 1,
+                shift: self.shift,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::persistent_array::PersistentArray;
+
+    #[test]
+    fn push_grows_beyond_multiple_levels() {
+        let mut array: PersistentArray<usize> = PersistentArray::from_slice(&[]);
+        for value in 0..2000 {
+            array = array.push(value);
+        }
+
+        assert_eq!(array.len(), 2000);
+        for index in 0..2000 {
+            assert_eq!(*array.get(index), index);
+        }
+    }
+
+    #[test]
+    fn set_does_not_modify_old_version() {
+        let values: Vec<usize> = (0..1500).collect();
+        let old = PersistentArray::from_slice(&values);
+        let new = old.set(1234, 0).set(3, 7);
+
+        assert_eq!(*old.get(1234), 1234);
+        assert_eq!(*old.get(3), 3);
+        assert_eq!(*new.get(1234), 0);
+        assert_eq!(*new.get(3), 7);
+        assert_eq!(*new.get(1233), 1233);
+    }
+
+    #[test]
+    fn from_slice_and_push_agree() {
+        let values: Vec<usize> = (0..1025).collect();
+        let from_slice = PersistentArray::from_slice(&values);
+        let pushed = PersistentArray::from_slice(&values[..1024]).push(1024);
+
+        for index in 0..1025 {
+            assert_eq!(from_slice.get(index), pushed.get(index));
+        }
+    }
+}
@@ -0,0 +1,327 @@
+use std::collections::HashMap;
+
+use crate::persistent_array::PersistentArray;
+
+#[derive(Debug, Clone, Copy)]
+struct Entry {
+    parent: usize,
+    rank: u8,
+}
+
+/// A fully persistent disjoint-set data structure: Modifications return a new version, and leave the old version untouched.
+///
+/// All versions share most of their memory, so keeping many versions around is cheap. In particular, [`clone`] takes constant time.
+///
+/// Sets are joined by rank, without path compression, so [`root_of`], [`is_joined`] and [`join`] visit `O(log n)` elements.
+/// Each visit is a lookup in a persistent array with a branching factor of 32, which takes `O(log_32 n)` time.
+/// If you don't need to keep old versions around, use [`DisjointSet`] instead, which is much faster.
+///
+/// [`clone`]: Clone::clone
+/// [`root_of`]: PersistentDisjointSet::root_of
+/// [`is_joined`]: PersistentDisjointSet::is_joined
+/// [`join`]: PersistentDisjointSet::join
+/// [`DisjointSet`]: crate::DisjointSet
+///
+/// # Examples
+///
+/// ```
+/// use disjoint::PersistentDisjointSet;
+///
+/// let base = PersistentDisjointSet::with_len(3); // {0}, {1}, {2}
+///
+/// // Two different branches, both derived from the same base.
+/// let left = base.join(0, 1); // {0, 1}, {2}
+/// let right = base.join(1, 2); // {0}, {1, 2}
+///
+/// assert!(!base.is_joined(0, 1));
+/// assert!(left.is_joined(0, 1));
+/// assert!(!right.is_joined(0, 1));
+/// assert!(right.is_joined(1, 2));
+/// ```
+#[allow(clippy::missing_inline_in_public_items)]
+#[derive(Debug, Clone)]
+pub struct PersistentDisjointSet {
+    entries: PersistentArray<Entry>,
+}
+
+impl Default for PersistentDisjointSet {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PersistentDisjointSet {
+    /// Constructs a new `PersistentDisjointSet` with `len` elements, named `0` to `n - 1`, each in its own set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::PersistentDisjointSet;
+    ///
+    /// let ds = PersistentDisjointSet::with_len(4);
+    ///
+    /// // The disjoint set contains 4 elements.
+    /// assert_eq!(ds.len(), 4);
+    ///
+    /// // Two elements i and j are not joined in the same set, unless i = j.
+    /// assert!(!ds.is_joined(0, 3));
+    /// assert!(ds.is_joined(1, 1));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn with_len(len: usize) -> Self {
+        Self {
+            entries: PersistentArray::from_slice(
+                &(0..len)
+                    .map(|parent| Entry { parent, rank: 0 })
+                    .collect::<Vec<_>>(),
+            ),
+        }
+    }
+
+    /// Constructs a new, empty `PersistentDisjointSet`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::PersistentDisjointSet;
+    ///
+    /// let ds = PersistentDisjointSet::new();
+    /// assert!(ds.is_empty());
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn new() -> Self {
+        Self::with_len(0)
+    }
+
+    /// Returns an element of the subset containing `child`.
+    /// This exact element is returned for every member of the subset.
+    ///
+    /// # Important
+    ///
+    /// The specific choice of the returned element is an implementation detail.
+    /// There are no further guarantees beyond what is documented here.
+    /// If you just want to check if two elements are in the same subset, use [`is_joined`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `child` is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::PersistentDisjointSet;
+    ///
+    /// let ds = PersistentDisjointSet::with_len(3); // {0}, {1}, {2}
+    /// assert_eq!(ds.root_of(0), 0);
+    ///
+    /// let ds = ds.join(0, 1); // {0, 1}, {2}
+    /// assert_eq!(ds.root_of(0), ds.root_of(1));
+    /// assert_ne!(ds.root_of(0), ds.root_of(2));
+    /// ```
+    ///
+    /// [`is_joined`]: PersistentDisjointSet::is_joined
+    #[inline]
+    #[must_use]
+    pub fn root_of(&self, child: usize) -> usize {
+        let mut current = child;
+        loop {
+            let parent = self.entries.get(current).parent;
+            if parent == current {
+                return current;
+            }
+            current = parent;
+        }
+    }
+
+    /// Returns a new version with an additional element, not joined to any other element.
+    ///
+    /// The index of the new element is `self.len()`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::PersistentDisjointSet;
+    ///
+    /// let old = PersistentDisjointSet::with_len(1);
+    /// let new = old.add_singleton();
+    /// assert_eq!(old.len(), 1);
+    /// assert_eq!(new.len(), 2);
+    /// assert!(!new.is_joined(0, 1));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn add_singleton(&self) -> Self {
+        Self {
+            entries: self.entries.push(Entry {
+                parent: self.len(),
+                rank: 0,
+            }),
+        }
+    }
+
+    /// Returns a new version in which the sets of `first_element` and `second_element` are joined together.
+    ///
+    /// If they are already in the same set, the returned version is equal to `self`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `first_element` or `second_element` is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::PersistentDisjointSet;
+    ///
+    /// let ds = PersistentDisjointSet::with_len(4); // {0}, {1}, {2}, {3}
+    ///
+    /// let joined = ds.join(0, 1).join(2, 3); // {0, 1}, {2, 3}
+    /// assert!(joined.is_joined(0, 1));
+    /// assert!(!joined.is_joined(0, 3));
+    ///
+    /// let all = joined.join(1, 2); // {0, 1, 2, 3}
+    /// assert!(all.is_joined(0, 3));
+    ///
+    /// // Previous versions are not affected.
+    /// assert!(!joined.is_joined(0, 3));
+    /// assert!(!ds.is_joined(0, 1));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn join(&self, first_element: usize, second_element: usize) -> Self {
+        let root_first = self.root_of(first_element);
+        let root_second = self.root_of(second_element);
+
+        if root_first == root_second {
+            return self.clone();
+        }
+
+        let rank_first = self.entries.get(root_first).rank;
+        let rank_second = self.entries.get(root_second).rank;
+
+        let (child, parent, parent_rank) = if rank_first < rank_second {
+            (root_first, root_second, rank_second)
+        } else {
+            (root_second, root_first, rank_first)
+        };
+
+        let mut entries = self.entries.set(
+            child,
+            Entry {
+                parent,
+                rank: self.entries.get(child).rank,
+            },
+        );
+        if rank_first == rank_second {
+            entries = entries.set(
+                parent,
+                Entry {
+                    parent,
+                    rank: parent_rank + 1,
+                },
+            );
+        }
+
+        Self { entries }
+    }
+
+    /// Returns `true` if `first_element` and `second_element` are in the same subset.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `first_element` or `second_element` is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::PersistentDisjointSet;
+    ///
+    /// let ds = PersistentDisjointSet::with_len(3); // {0}, {1}, {2}
+    /// assert!(ds.is_joined(0, 0));
+    /// assert!(!ds.is_joined(0, 1));
+    ///
+    /// let ds = ds.join(1, 0); // {0, 1}, {2}
+    /// assert!(ds.is_joined(0, 1));
+    /// assert!(!ds.is_joined(0, 2));
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn is_joined(&self, first_element: usize, second_element: usize) -> bool {
+        self.root_of(first_element) == self.root_of(second_element)
+    }
+
+    /// Returns the number of elements in the disjoint set, regardless of how they are joined together.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::PersistentDisjointSet;
+    ///
+    /// let ds = PersistentDisjointSet::with_len(4);
+    /// assert_eq!(ds.len(), 4);
+    /// assert_eq!(ds.join(1, 3).len(), 4);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if the disjoint set contains no elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::PersistentDisjointSet;
+    ///
+    /// assert!(PersistentDisjointSet::new().is_empty());
+    /// assert!(!PersistentDisjointSet::with_len(10).is_empty());
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns a `Vec` of all sets. Each entry corresponds to one set, and is a `Vec` of its elements.
+    ///
+    /// The sets are ordered by their smallest contained element. The elements inside each sets are ordered.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::PersistentDisjointSet;
+    ///
+    /// let ds = PersistentDisjointSet::with_len(4).join(3, 1); // {0}, {1, 3}, {2}
+    /// assert_eq!(ds.sets(), vec![vec![0], vec![1, 3], vec![2]]);
+    /// ```
+    #[must_use]
+    #[allow(clippy::missing_inline_in_public_items)]
+    pub fn sets(&self) -> Vec<Vec<usize>> {
+        let mut result = Vec::new();
+        let mut root_to_result_id = HashMap::new();
+
+        for index in 0..self.len() {
+            let root = self.root_of(index);
+            let &mut result_id = root_to_result_id.entry(root).or_insert_with(|| {
+                let id = result.len();
+                result.push(Vec::with_capacity(1));
+                id
+            });
+            result[result_id].push(index);
+        }
+
+        result
+    }
+}
+
+impl PartialEq for PersistentDisjointSet {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.sets() == other.sets()
+    }
+}
+
+impl Eq for PersistentDisjointSet {}
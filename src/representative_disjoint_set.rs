@@ -0,0 +1,396 @@
+use crate::{DisjointSet, DisjointSetWith};
+
+/// A policy deciding which element represents a set of a [`RepresentativeDisjointSet<P>`].
+///
+/// Whenever two sets are joined, the policy chooses which of the two current representatives represents the joined set.
+///
+/// # Examples
+///
+/// ```
+/// use disjoint::{RepresentativeDisjointSet, RepresentativePolicy};
+///
+/// // Prefer even elements, and smaller elements among those with the same parity.
+/// #[derive(Debug, Default)]
+/// struct EvenFirst;
+///
+/// impl RepresentativePolicy for EvenFirst {
+///     fn prefers(&self, candidate: usize, current: usize) -> bool {
+///         (candidate % 2, candidate) < (current % 2, current)
+///     }
+/// }
+///
+/// let mut ds = RepresentativeDisjointSet::<EvenFirst>::with_len(5);
+/// ds.join(1, 3);
+/// ds.join(3, 4);
+/// ds.join(0, 2);
+/// assert_eq!(ds.root_of(1), 4);
+/// assert_eq!(ds.root_of(2), 0);
+/// ```
+pub trait RepresentativePolicy {
+    /// Returns `true` if `candidate` should replace `current` as the representative of a set.
+    ///
+    /// When two sets are joined, `current` is the representative of the set of the first argument of `join`, and `candidate` the one of the second argument.
+    fn prefers(&self, candidate: usize, current: usize) -> bool;
+}
+
+/// A [`RepresentativePolicy`] where each set is represented by its smallest element.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[allow(clippy::exhaustive_structs)]
+pub struct MinIndex;
+
+impl RepresentativePolicy for MinIndex {
+    #[inline]
+    fn prefers(&self, candidate: usize, current: usize) -> bool {
+        candidate < current
+    }
+}
+
+/// A [`RepresentativePolicy`] where each set is represented by its largest element.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[allow(clippy::exhaustive_structs)]
+pub struct MaxIndex;
+
+impl RepresentativePolicy for MaxIndex {
+    #[inline]
+    fn prefers(&self, candidate: usize, current: usize) -> bool {
+        candidate > current
+    }
+}
+
+/// A [`RepresentativePolicy`] where joining keeps the representative of the first argument of `join`.
+///
+/// If sets are always joined into existing sets, like `join(existing, new)`, this keeps the element that was joined first as representative.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[allow(clippy::exhaustive_structs)]
+pub struct FirstJoined;
+
+impl RepresentativePolicy for FirstJoined {
+    #[inline]
+    fn prefers(&self, _candidate: usize, _current: usize) -> bool {
+        false
+    }
+}
+
+/// A [`RepresentativePolicy`] where each set is represented by the element with the highest priority, computed by a user function.
+///
+/// If multiple elements have the highest priority, the representative of the first argument of `join` is kept.
+///
+/// # Examples
+///
+/// ```
+/// use disjoint::{ByPriority, RepresentativeDisjointSet};
+///
+/// let weights = [3, 9, 1, 9];
+/// let mut ds = RepresentativeDisjointSet::with_len_and_policy(4, ByPriority(|element| weights[element]));
+///
+/// ds.join(0, 2);
+/// assert_eq!(ds.root_of(2), 0);
+///
+/// ds.join(3, 1);
+/// ds.join(2, 3);
+/// assert_eq!(ds.root_of(0), 3);
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[allow(clippy::exhaustive_structs)]
+pub struct ByPriority<F>(pub F);
+
+impl<F, K> RepresentativePolicy for ByPriority<F>
+where
+    F: Fn(usize) -> K,
+    K: Ord,
+{
+    #[inline]
+    fn prefers(&self, candidate: usize, current: usize) -> bool {
+        (self.0)(candidate) > (self.0)(current)
+    }
+}
+
+/// A disjoint-set data structure where the element returned by [`root_of`] follows a [`RepresentativePolicy`].
+///
+/// For [`DisjointSet`], the representative of a set depends on the order of the joins. Here, the representative is chosen by the policy `P` instead, for example [`MinIndex`], [`MaxIndex`], [`FirstJoined`] or [`ByPriority`].
+/// Internally, it still joins by rank with path compression, so [`root_of`] keeps its near-constant amortized cost.
+///
+/// [`root_of`]: RepresentativeDisjointSet::root_of
+///
+/// # Examples
+///
+/// ```
+/// use disjoint::{MinIndex, RepresentativeDisjointSet};
+///
+/// let mut ds = RepresentativeDisjointSet::<MinIndex>::with_len(6);
+/// ds.join(5, 3);
+/// ds.join(4, 3);
+/// ds.join(1, 0);
+///
+/// assert_eq!(ds.root_of(4), 3);
+/// assert_eq!(ds.root_of(1), 0);
+/// assert_eq!(ds.root_of(2), 2);
+/// ```
+#[allow(clippy::missing_inline_in_public_items)]
+#[derive(Debug, Clone)]
+pub struct RepresentativeDisjointSet<P> {
+    representatives: DisjointSetWith<usize>,
+    policy: P,
+}
+
+impl<P> Default for RepresentativeDisjointSet<P>
+where
+    P: RepresentativePolicy + Default,
+{
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<P> RepresentativeDisjointSet<P>
+where
+    P: RepresentativePolicy + Default,
+{
+    /// Constructs a new `RepresentativeDisjointSet<P>` with `len` elements, named `0` to `n - 1`, each in its own set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::{MaxIndex, RepresentativeDisjointSet};
+    ///
+    /// let ds = RepresentativeDisjointSet::<MaxIndex>::with_len(4);
+    /// assert_eq!(ds.len(), 4);
+    /// assert!(!ds.is_joined(0, 3));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn with_len(len: usize) -> Self {
+        Self::with_len_and_policy(len, P::default())
+    }
+
+    /// Constructs a new, empty `RepresentativeDisjointSet<P>`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::{FirstJoined, RepresentativeDisjointSet};
+    ///
+    /// let ds = RepresentativeDisjointSet::<FirstJoined>::new();
+    /// assert!(ds.is_empty());
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn new() -> Self {
+        Self::with_len(0)
+    }
+}
+
+impl<P> RepresentativeDisjointSet<P> {
+    /// Returns a reference to the [`RepresentativePolicy`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::{MinIndex, RepresentativeDisjointSet};
+    ///
+    /// let ds = RepresentativeDisjointSet::<MinIndex>::new();
+    /// assert_eq!(*ds.policy(), MinIndex);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn policy(&self) -> &P {
+        &self.policy
+    }
+
+    /// Returns a `&DisjointSet` of all elements and the information of how they are joined.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::{MinIndex, RepresentativeDisjointSet};
+    ///
+    /// let mut ds = RepresentativeDisjointSet::<MinIndex>::with_len(3);
+    /// ds.join(2, 1);
+    /// assert!(ds.indices().is_joined(1, 2));
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn indices(&self) -> &DisjointSet {
+        self.representatives.indices()
+    }
+}
+
+impl<P> RepresentativeDisjointSet<P>
+where
+    P: RepresentativePolicy,
+{
+    /// Constructs a new `RepresentativeDisjointSet<P>` with `len` elements, named `0` to `n - 1`, each in its own set, using `policy` to choose representatives.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::{ByPriority, RepresentativeDisjointSet};
+    ///
+    /// let mut ds = RepresentativeDisjointSet::with_len_and_policy(3, ByPriority(|element| element % 2));
+    /// ds.join(0, 1);
+    /// ds.join(2, 0);
+    /// assert_eq!(ds.root_of(2), 1);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn with_len_and_policy(len: usize, policy: P) -> Self {
+        Self {
+            representatives: DisjointSetWith::from((0..len).collect::<Vec<_>>()),
+            policy,
+        }
+    }
+
+    /// Returns the representative of the subset containing `child`, as chosen by the [`RepresentativePolicy`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `child` is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::{MinIndex, RepresentativeDisjointSet};
+    ///
+    /// let mut ds = RepresentativeDisjointSet::<MinIndex>::with_len(4);
+    /// assert_eq!(ds.root_of(3), 3);
+    ///
+    /// ds.join(3, 2);
+    /// ds.join(2, 1);
+    /// assert_eq!(ds.root_of(3), 1);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn root_of(&self, child: usize) -> usize {
+        *self.representatives.data_of(child)
+    }
+
+    /// Adds a new element, not joined to any other element. Returns the index
+    /// of the new element.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new capacity exceeds `isize::MAX` bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::{MinIndex, RepresentativeDisjointSet};
+    ///
+    /// let mut ds = RepresentativeDisjointSet::<MinIndex>::with_len(1);
+    /// assert_eq!(ds.add_singleton(), 1);
+    /// assert_eq!(ds.root_of(1), 1);
+    /// ```
+    #[inline]
+    pub fn add_singleton(&mut self) -> usize {
+        let id = self.len();
+        self.representatives.push(id)
+    }
+
+    /// If `first_element` and `second_element` are in different sets, joins them together and returns `true`.
+    /// The representative of the joined set is chosen by the [`RepresentativePolicy`].
+    ///
+    /// Otherwise, does nothing and returns `false`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `first_element` or `second_element` is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::{FirstJoined, RepresentativeDisjointSet};
+    ///
+    /// let mut ds = RepresentativeDisjointSet::<FirstJoined>::with_len(4);
+    /// assert!(ds.join(2, 0));
+    /// assert!(ds.join(1, 3));
+    /// assert!(ds.join(0, 3));
+    /// assert!(!ds.join(1, 2));
+    /// assert_eq!(ds.root_of(1), 2);
+    /// ```
+    #[inline]
+    pub fn join(&mut self, first_element: usize, second_element: usize) -> bool {
+        let policy = &self.policy;
+        self.representatives
+            .join_by(first_element, second_element, |current, candidate| {
+                if policy.prefers(candidate, *current) {
+                    *current = candidate;
+                }
+            })
+    }
+
+    /// Returns `true` if `first_element` and `second_element` are in the same subset.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `first_element` or `second_element` is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::{MinIndex, RepresentativeDisjointSet};
+    ///
+    /// let mut ds = RepresentativeDisjointSet::<MinIndex>::with_len(3);
+    /// ds.join(0, 2);
+    /// assert!(ds.is_joined(2, 0));
+    /// assert!(!ds.is_joined(0, 1));
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn is_joined(&self, first_element: usize, second_element: usize) -> bool {
+        self.representatives
+            .is_joined(first_element, second_element)
+    }
+
+    /// Returns the number of elements in the disjoint set, regardless of how they are joined together.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::{MinIndex, RepresentativeDisjointSet};
+    ///
+    /// let mut ds = RepresentativeDisjointSet::<MinIndex>::with_len(4);
+    /// ds.join(1, 3);
+    /// assert_eq!(ds.len(), 4);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.representatives.len()
+    }
+
+    /// Returns `true` if the disjoint set contains no elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::{MinIndex, RepresentativeDisjointSet};
+    ///
+    /// assert!(RepresentativeDisjointSet::<MinIndex>::new().is_empty());
+    /// assert!(!RepresentativeDisjointSet::<MinIndex>::with_len(1).is_empty());
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.representatives.is_empty()
+    }
+
+    /// Returns a `Vec` of all sets. Each entry corresponds to one set, and is a `Vec` of its elements.
+    ///
+    /// The sets are ordered by their smallest contained element. The elements inside each sets are ordered.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::{MaxIndex, RepresentativeDisjointSet};
+    ///
+    /// let mut ds = RepresentativeDisjointSet::<MaxIndex>::with_len(4);
+    /// ds.join(3, 1);
+    /// assert_eq!(ds.sets(), vec![vec![0], vec![1, 3], vec![2]]);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn sets(&self) -> Vec<Vec<usize>> {
+        self.indices().sets()
+    }
+}
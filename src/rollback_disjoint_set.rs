@@ -0,0 +1,552 @@
+use std::collections::HashMap;
+
+/// A disjoint-set data structure that can undo its modifications, by rolling back to a previously taken [`RollbackSnapshot`].
+///
+/// The query API is the same as for [`DisjointSet`]. In addition, [`snapshot`] starts recording all modifications, which can later be undone by [`rollback_to`], or kept by [`commit`]. Snapshots can be nested.
+///
+/// To make undoing cheap, sets are joined by rank, but without path compression. Because of that, [`root_of`] and [`is_joined`] take `O(log n)` time instead of the nearly constant amortized time of [`DisjointSet`]. Use this type only if you actually need to undo modifications.
+///
+/// [`DisjointSet`]: crate::DisjointSet
+/// [`snapshot`]: RollbackDisjointSet::snapshot
+/// [`rollback_to`]: RollbackDisjointSet::rollback_to
+/// [`commit`]: RollbackDisjointSet::commit
+/// [`root_of`]: RollbackDisjointSet::root_of
+/// [`is_joined`]: RollbackDisjointSet::is_joined
+///
+/// # Examples
+///
+/// ```
+/// use disjoint::RollbackDisjointSet;
+///
+/// let mut ds = RollbackDisjointSet::with_len(3); // {0}, {1}, {2}
+///
+/// let snapshot = ds.snapshot();
+/// ds.join(0, 1); // {0, 1}, {2}
+/// ds.add_singleton(); // {0, 1}, {2}, {3}
+/// assert!(ds.is_joined(0, 1));
+/// assert_eq!(ds.len(), 4);
+///
+/// // Undo everything that happened since the snapshot was taken.
+/// ds.rollback_to(snapshot); // {0}, {1}, {2}
+/// assert!(!ds.is_joined(0, 1));
+/// assert_eq!(ds.len(), 3);
+/// ```
+#[allow(clippy::missing_inline_in_public_items)]
+#[derive(Debug, Clone, Default)]
+pub struct RollbackDisjointSet {
+    parents: Vec<usize>,
+    ranks: Vec<u8>,
+    undo_log: Vec<UndoEntry>,
+    open_snapshots: usize,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum UndoEntry {
+    AddSingleton,
+    Link { child: usize, rank_increased: bool },
+}
+
+/// A snapshot of a [`RollbackDisjointSet`], created by [`RollbackDisjointSet::snapshot`].
+///
+/// It has to be consumed by either [`RollbackDisjointSet::rollback_to`] or [`RollbackDisjointSet::commit`].
+// Deliberately not `Copy`, so a snapshot can only be rolled back to or committed once.
+#[allow(missing_copy_implementations)]
+#[must_use]
+#[derive(Debug)]
+pub struct RollbackSnapshot {
+    undo_log_len: usize,
+    /// The number of open snapshots, including this one, right after it was taken.
+    depth: usize,
+}
+
+impl RollbackDisjointSet {
+    /// Constructs a new `RollbackDisjointSet` with `len` elements, named `0` to `n - 1`, each in its own set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::RollbackDisjointSet;
+    ///
+    /// let ds = RollbackDisjointSet::with_len(4);
+    ///
+    /// // The disjoint set contains 4 elements.
+    /// assert_eq!(ds.len(), 4);
+    ///
+    /// // Two elements i and j are not joined in the same set, unless i = j.
+    /// assert!(!ds.is_joined(0, 3));
+    /// assert!(ds.is_joined(1, 1));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn with_len(len: usize) -> Self {
+        Self {
+            parents: (0..len).collect(),
+            ranks: vec![0; len],
+            undo_log: Vec::new(),
+            open_snapshots: 0,
+        }
+    }
+
+    /// Constructs a new, empty `RollbackDisjointSet` with at least the specified capacity.
+    ///
+    /// It will be able to hold at least `capacity` elements without
+    /// reallocating. This method is allowed to allocate for more elements than
+    /// `capacity`. If `capacity` is 0, it will not allocate.
+    ///
+    /// It is important to note that although the returned `RollbackDisjointSet` has the
+    /// minimum *capacity* specified, it will have a zero *length*.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new capacity exceeds `isize::MAX` bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::RollbackDisjointSet;
+    ///
+    /// let mut ds = RollbackDisjointSet::with_capacity(10);
+    ///
+    /// // It contains no elements, even though it has capacity for more.
+    /// assert_eq!(ds.len(), 0);
+    ///
+    /// // These are all done without reallocating...
+    /// for _ in 0..10 {
+    ///     ds.add_singleton();
+    /// }
+    ///
+    /// // ...but this may make the disjoint set reallocate.
+    /// ds.add_singleton();
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            parents: Vec::with_capacity(capacity),
+            ranks: Vec::with_capacity(capacity),
+            undo_log: Vec::new(),
+            open_snapshots: 0,
+        }
+    }
+
+    /// Constructs a new, empty `RollbackDisjointSet`.
+    ///
+    /// The disjoint set will not allocate until elements are added to it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::RollbackDisjointSet;
+    ///
+    /// let ds = RollbackDisjointSet::new();
+    /// assert!(ds.is_empty());
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns an element of the subset containing `child`.
+    /// This exact element is returned for every member of the subset.
+    ///
+    /// # Important
+    ///
+    /// The specific choice of the returned element is an implementation detail.
+    /// There are no further guarantees beyond what is documented here.
+    /// If you just want to check if two elements are in the same subset, use [`is_joined`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `child` is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::RollbackDisjointSet;
+    ///
+    /// let mut ds = RollbackDisjointSet::with_len(3); // {0}, {1}, {2}
+    /// assert_eq!(ds.root_of(0), 0);
+    ///
+    /// ds.join(0, 1); // {0, 1}, {2}
+    /// assert_eq!(ds.root_of(0), ds.root_of(1));
+    /// assert_ne!(ds.root_of(0), ds.root_of(2));
+    /// ```
+    ///
+    /// [`is_joined`]: RollbackDisjointSet::is_joined
+    #[inline]
+    #[must_use]
+    pub fn root_of(&self, child: usize) -> usize {
+        let mut current = child;
+        loop {
+            let parent = self.parents[current];
+            if parent == current {
+                return current;
+            }
+            current = parent;
+        }
+    }
+
+    /// Adds a new element, not joined to any other element. Returns the index
+    /// of the new element.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new capacity exceeds `isize::MAX` bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::RollbackDisjointSet;
+    ///
+    /// let mut ds = RollbackDisjointSet::with_len(1);
+    /// assert_eq!(ds.add_singleton(), 1);
+    /// assert_eq!(ds.len(), 2);
+    /// assert!(!ds.is_joined(0, 1));
+    /// ```
+    #[inline]
+    pub fn add_singleton(&mut self) -> usize {
+        let id = self.len();
+        self.parents.push(id);
+        self.ranks.push(0);
+        self.record(UndoEntry::AddSingleton);
+        id
+    }
+
+    /// If `first_element` and `second_element` are in different sets, joins them together and returns `true`.
+    ///
+    /// Otherwise, does nothing and returns `false`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `first_element` or `second_element` is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::RollbackDisjointSet;
+    ///
+    /// let mut ds = RollbackDisjointSet::with_len(4); // {0}, {1}, {2}, {3}
+    ///
+    /// assert!(ds.join(0, 1)); // {0, 1}, {2}, {3}
+    /// assert!(ds.join(2, 3)); // {0, 1}, {2, 3}
+    /// assert!(!ds.join(1, 0));
+    /// assert!(!ds.is_joined(0, 3));
+    ///
+    /// assert!(ds.join(1, 2)); // {0, 1, 2, 3}
+    /// assert!(ds.is_joined(0, 3));
+    /// ```
+    #[inline]
+    pub fn join(&mut self, first_element: usize, second_element: usize) -> bool {
+        let root_first = self.root_of(first_element);
+        let root_second = self.root_of(second_element);
+
+        if root_first == root_second {
+            return false;
+        }
+
+        let (child, parent) = if self.ranks[root_first] < self.ranks[root_second] {
+            (root_first, root_second)
+        } else {
+            (root_second, root_first)
+        };
+
+        let rank_increased = self.ranks[child] == self.ranks[parent];
+        if rank_increased {
+            self.ranks[parent] += 1;
+        }
+        self.parents[child] = parent;
+        self.record(UndoEntry::Link {
+            child,
+            rank_increased,
+        });
+
+        true
+    }
+
+    /// Returns `true` if `first_element` and `second_element` are in the same subset.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `first_element` or `second_element` is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::RollbackDisjointSet;
+    ///
+    /// let mut ds = RollbackDisjointSet::with_len(3); // {0}, {1}, {2}
+    /// assert!(ds.is_joined(0, 0));
+    /// assert!(!ds.is_joined(0, 1));
+    ///
+    /// ds.join(1, 0); // {0, 1}, {2}
+    /// assert!(ds.is_joined(0, 1));
+    /// assert!(!ds.is_joined(0, 2));
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn is_joined(&self, first_element: usize, second_element: usize) -> bool {
+        self.root_of(first_element) == self.root_of(second_element)
+    }
+
+    /// Returns the number of elements in the disjoint set, regardless of how they are joined together.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::RollbackDisjointSet;
+    ///
+    /// let mut ds = RollbackDisjointSet::with_len(4);
+    /// assert_eq!(ds.len(), 4);
+    ///
+    /// ds.join(1, 3);
+    /// assert_eq!(ds.len(), 4);
+    /// ```
+    #[inline]
+    #[must_use]
+    #[allow(clippy::missing_const_for_fn)]
+    pub fn len(&self) -> usize {
+        self.parents.len()
+    }
+
+    /// Returns `true` if the disjoint set contains no elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::RollbackDisjointSet;
+    ///
+    /// assert!(RollbackDisjointSet::new().is_empty());
+    /// assert!(!RollbackDisjointSet::with_len(10).is_empty());
+    /// ```
+    #[inline]
+    #[must_use]
+    #[allow(clippy::missing_const_for_fn)]
+    pub fn is_empty(&self) -> bool {
+        self.parents.is_empty()
+    }
+
+    /// Clears the `RollbackDisjointSet`, including all recorded modifications.
+    ///
+    /// All open snapshots are discarded, so they can no longer be passed to [`rollback_to`] or [`commit`].
+    /// The disjoint set will retain its capacity, so adding elements will not
+    /// allocate.
+    ///
+    /// [`rollback_to`]: RollbackDisjointSet::rollback_to
+    /// [`commit`]: RollbackDisjointSet::commit
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::RollbackDisjointSet;
+    ///
+    /// let mut ds = RollbackDisjointSet::with_len(2);
+    /// let _discarded = ds.snapshot();
+    /// ds.join(0, 1);
+    /// ds.clear();
+    /// assert!(ds.is_empty());
+    ///
+    /// // Does not allocate!
+    /// ds.add_singleton();
+    /// ```
+    #[inline]
+    pub fn clear(&mut self) {
+        self.parents.clear();
+        self.ranks.clear();
+        self.undo_log.clear();
+        self.open_snapshots = 0;
+    }
+
+    /// Returns a `Vec` of all sets. Each entry corresponds to one set, and is a `Vec` of its elements.
+    ///
+    /// The sets are ordered by their smallest contained element. The elements inside each sets are ordered.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::RollbackDisjointSet;
+    ///
+    /// let mut ds = RollbackDisjointSet::with_len(4); // {0}, {1}, {2}, {3}
+    /// ds.join(3, 1); // {0}, {1, 3}, {2}
+    /// assert_eq!(ds.sets(), vec![vec![0], vec![1, 3], vec![2]]);
+    /// ```
+    #[must_use]
+    #[allow(clippy::missing_inline_in_public_items)]
+    pub fn sets(&self) -> Vec<Vec<usize>> {
+        let mut result = Vec::new();
+        let mut root_to_result_id = HashMap::new();
+
+        for index in 0..self.len() {
+            let root = self.root_of(index);
+            let &mut result_id = root_to_result_id.entry(root).or_insert_with(|| {
+                let id = result.len();
+                result.push(Vec::with_capacity(1));
+                id
+            });
+            result[result_id].push(index);
+        }
+
+        result
+    }
+
+    #[inline]
+    fn record(&mut self, entry: UndoEntry) {
+        if self.open_snapshots > 0 {
+            self.undo_log.push(entry);
+        }
+    }
+
+    /// Starts recording modifications, and returns a snapshot of the current state.
+    ///
+    /// The snapshot has to be passed to either [`rollback_to`] or [`commit`]. Snapshots can be nested, but have to be consumed in the reverse order they were taken.
+    ///
+    /// [`rollback_to`]: RollbackDisjointSet::rollback_to
+    /// [`commit`]: RollbackDisjointSet::commit
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::RollbackDisjointSet;
+    ///
+    /// let mut ds = RollbackDisjointSet::with_len(3); // {0}, {1}, {2}
+    ///
+    /// let outer = ds.snapshot();
+    /// ds.join(0, 1); // {0, 1}, {2}
+    ///
+    /// let inner = ds.snapshot();
+    /// ds.join(1, 2); // {0, 1, 2}
+    /// ds.rollback_to(inner); // {0, 1}, {2}
+    /// assert!(ds.is_joined(0, 1));
+    /// assert!(!ds.is_joined(0, 2));
+    ///
+    /// ds.rollback_to(outer); // {0}, {1}, {2}
+    /// assert!(!ds.is_joined(0, 1));
+    /// ```
+    #[inline]
+    #[allow(clippy::missing_const_for_fn)]
+    pub fn snapshot(&mut self) -> RollbackSnapshot {
+        self.open_snapshots += 1;
+        RollbackSnapshot {
+            undo_log_len: self.undo_log.len(),
+            depth: self.open_snapshots,
+        }
+    }
+
+    /// Consumes `snapshot` and returns the length the undo log had when it was taken.
+    #[inline]
+    #[allow(clippy::needless_pass_by_value)]
+    fn close_snapshot(&mut self, snapshot: RollbackSnapshot) -> usize {
+        let RollbackSnapshot {
+            undo_log_len,
+            depth,
+        } = snapshot;
+        assert!(
+            depth <= self.open_snapshots && undo_log_len <= self.undo_log.len(),
+            "snapshot was not taken from this disjoint set, or was already consumed"
+        );
+        assert_eq!(
+            depth, self.open_snapshots,
+            "a more recent snapshot has not been consumed yet"
+        );
+        self.open_snapshots -= 1;
+        undo_log_len
+    }
+
+    /// Undoes all modifications (calls of [`join`] and [`add_singleton`]) since `snapshot` was taken.
+    ///
+    /// Takes time proportional to the number of undone modifications.
+    ///
+    /// [`join`]: RollbackDisjointSet::join
+    /// [`add_singleton`]: RollbackDisjointSet::add_singleton
+    ///
+    /// # Panics
+    ///
+    /// Panics if a more recent snapshot has not been consumed yet.
+    /// Also panics if `snapshot` was not taken from this disjoint set, though not every such misuse is guaranteed to be detected.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::RollbackDisjointSet;
+    ///
+    /// let mut ds = RollbackDisjointSet::with_len(2); // {0}, {1}
+    ///
+    /// let snapshot = ds.snapshot();
+    /// let new = ds.add_singleton(); // {0}, {1}, {2}
+    /// ds.join(0, new); // {0, 2}, {1}
+    ///
+    /// ds.rollback_to(snapshot); // {0}, {1}
+    /// assert_eq!(ds.len(), 2);
+    /// assert!(!ds.is_joined(0, 1));
+    /// ```
+    #[inline]
+    pub fn rollback_to(&mut self, snapshot: RollbackSnapshot) {
+        let undo_log_len = self.close_snapshot(snapshot);
+
+        while self.undo_log.len() > undo_log_len {
+            if let Some(entry) = self.undo_log.pop() {
+                match entry {
+                    UndoEntry::AddSingleton => {
+                        self.parents.pop();
+                        self.ranks.pop();
+                    }
+                    UndoEntry::Link {
+                        child,
+                        rank_increased,
+                    } => {
+                        let parent = self.parents[child];
+                        if rank_increased {
+                            self.ranks[parent] -= 1;
+                        }
+                        self.parents[child] = child;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Keeps all modifications since `snapshot` was taken.
+    ///
+    /// They can still be undone by rolling back to a snapshot taken before `snapshot`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a more recent snapshot has not been consumed yet.
+    /// Also panics if `snapshot` was not taken from this disjoint set, though not every such misuse is guaranteed to be detected.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::RollbackDisjointSet;
+    ///
+    /// let mut ds = RollbackDisjointSet::with_len(3); // {0}, {1}, {2}
+    ///
+    /// let outer = ds.snapshot();
+    /// let inner = ds.snapshot();
+    /// ds.join(0, 1); // {0, 1}, {2}
+    /// ds.commit(inner);
+    /// assert!(ds.is_joined(0, 1));
+    ///
+    /// // The committed join is still undone when rolling back further.
+    /// ds.rollback_to(outer); // {0}, {1}, {2}
+    /// assert!(!ds.is_joined(0, 1));
+    /// ```
+    #[inline]
+    pub fn commit(&mut self, snapshot: RollbackSnapshot) {
+        self.close_snapshot(snapshot);
+
+        if self.open_snapshots == 0 {
+            // Nothing can be undone anymore.
+            self.undo_log.clear();
+        }
+    }
+}
+
+impl PartialEq for RollbackDisjointSet {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.sets() == other.sets()
+    }
+}
+
+impl Eq for RollbackDisjointSet {}
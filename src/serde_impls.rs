@@ -0,0 +1,89 @@
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{
+    invalid_partition::join_sets, DisjointSet, DisjointSetVec, ElementIndex, FindStrategy,
+};
+
+/// Serializes a `DisjointSet` as its [`sets`](DisjointSet::sets), so the representation only depends on the partition, not on the internal forest.
+impl<O, F, L, I, T> Serialize for DisjointSet<O, F, L, I, T>
+where
+    F: FindStrategy,
+    I: ElementIndex,
+{
+    #[inline]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.sets().serialize(serializer)
+    }
+}
+
+/// Deserializes a `DisjointSet` from a list of sets, like returned by [`sets`](DisjointSet::sets).
+///
+/// The sets may be in any order, but every element from `0` to `n - 1` has to appear in exactly one non-empty set, where `n` is the total number of elements. Otherwise, an error is returned.
+impl<'de> Deserialize<'de> for DisjointSet {
+    #[inline]
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Vec::<Vec<usize>>::deserialize(deserializer)
+            .and_then(|sets| Self::from_sets(&sets).map_err(de::Error::custom))
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename = "DisjointSetVec")]
+struct DisjointSetVecRef<'a, T> {
+    values: &'a [T],
+    sets: Vec<Vec<usize>>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename = "DisjointSetVec")]
+struct DisjointSetVecOwned<T> {
+    values: Vec<T>,
+    sets: Vec<Vec<usize>>,
+}
+
+/// Serializes a `DisjointSetVec<T>` as a struct with the fields `values`, containing all values, and `sets`, containing the [`sets`](DisjointSet::sets) of its indices.
+impl<T, O, U> Serialize for DisjointSetVec<T, O, U>
+where
+    T: Serialize,
+{
+    #[inline]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        DisjointSetVecRef {
+            values: self.values(),
+            sets: self.indices().sets(),
+        }
+        .serialize(serializer)
+    }
+}
+
+/// Deserializes a `DisjointSetVec<T>` from a struct with the fields `values` and `sets`, like it is serialized.
+///
+/// The sets may be in any order, but every index of `values` has to appear in exactly one non-empty set. Otherwise, an error is returned.
+impl<'de, T> Deserialize<'de> for DisjointSetVec<T>
+where
+    T: Deserialize<'de>,
+{
+    #[inline]
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        DisjointSetVecOwned::deserialize(deserializer).and_then(|owned| {
+            let mut dsv = Self::from(owned.values);
+            join_sets(&owned.sets, dsv.len(), |first, second| {
+                dsv.join(first, second);
+            })
+            .map_err(de::Error::custom)
+            .map(|()| dsv)
+        })
+    }
+}
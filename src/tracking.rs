@@ -0,0 +1,62 @@
+/// Whether a [`DisjointSet`] links the members of each set, in addition to the forest of parents, ranks and sizes.
+///
+/// The tracking is a type parameter, so the default [`NoTracking`] costs nothing.
+/// [`MemberTracking`] links the members of each set in a cycle, for [`members`], which costs one index per element.
+/// Use [`DisjointSet::with_len_and_tracking`] to choose one.
+///
+/// This trait is sealed, so it cannot be implemented outside of this crate.
+///
+/// [`DisjointSet`]: crate::DisjointSet
+/// [`members`]: crate::DisjointSet::members
+/// [`DisjointSet::with_len_and_tracking`]: crate::DisjointSet::with_len_and_tracking
+///
+/// # Examples
+///
+/// ```
+/// use disjoint::{DisjointSet, MemberTracking};
+///
+/// let mut ds = DisjointSet::with_len_and_tracking(4, MemberTracking);
+/// ds.join(0, 1);
+/// ds.join(2, 1);
+/// assert_eq!(ds.members(3).collect::<Vec<_>>(), vec![3]);
+/// ```
+pub trait Tracking: sealed::Sealed {
+    /// Whether the members of each set are linked.
+    const TRACKS_MEMBERS: bool;
+}
+
+/// A [`Tracking`] that links the members of each set.
+pub trait TracksMembers: Tracking {}
+
+/// Private supertrait, so that the tracked data is always consistent with the marker traits.
+mod sealed {
+    // A private supertrait is not available on old Rust versions.
+    #[allow(unreachable_pub)]
+    pub trait Sealed {}
+}
+
+/// A [`Tracking`] that does not link the members of each set, the default for [`DisjointSet`].
+///
+/// [`DisjointSet`]: crate::DisjointSet
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[allow(clippy::exhaustive_structs)]
+pub struct NoTracking;
+
+impl sealed::Sealed for NoTracking {}
+
+impl Tracking for NoTracking {
+    const TRACKS_MEMBERS: bool = false;
+}
+
+/// A [`Tracking`] that links the members of each set in a cycle.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[allow(clippy::exhaustive_structs)]
+pub struct MemberTracking;
+
+impl sealed::Sealed for MemberTracking {}
+
+impl Tracking for MemberTracking {
+    const TRACKS_MEMBERS: bool = true;
+}
+
+impl TracksMembers for MemberTracking {}
@@ -0,0 +1,551 @@
+// The `alloc` crate is not available on old Rust versions.
+#![allow(clippy::std_instead_of_alloc)]
+
+use std::{
+    cell::Cell,
+    collections::HashMap,
+    error::Error,
+    fmt::{self, Debug, Display, Formatter},
+};
+
+/// A group, used for the relative potentials of a [`WeightedDisjointSet`].
+///
+/// Implementations have to satisfy the group axioms: [`combine`] is associative, [`identity`] is neutral with respect to [`combine`], and [`inverse`] returns the inverse with respect to [`combine`].
+/// The group does not need to be commutative.
+///
+/// This trait is implemented for all primitive integers, using wrapping arithmetic, and for `bool`, using exclusive or.
+///
+/// [`combine`]: Group::combine
+/// [`identity`]: Group::identity
+/// [`inverse`]: Group::inverse
+///
+/// # Examples
+///
+/// ```
+/// use disjoint::Group;
+///
+/// // Rotations by multiples of 90 degrees.
+/// #[derive(Debug, Clone, Copy, PartialEq)]
+/// struct Rotation(u8);
+///
+/// impl Group for Rotation {
+///     fn identity() -> Self {
+///         Rotation(0)
+///     }
+///
+///     fn combine(self, other: Self) -> Self {
+///         Rotation((self.0 + other.0) % 4)
+///     }
+///
+///     fn inverse(self) -> Self {
+///         Rotation((4 - self.0) % 4)
+///     }
+/// }
+///
+/// assert_eq!(Rotation(3).combine(Rotation(3)), Rotation(2));
+/// assert_eq!(Rotation(1).combine(Rotation(1).inverse()), Rotation::identity());
+/// ```
+pub trait Group: Copy + PartialEq {
+    /// Returns the neutral element.
+    fn identity() -> Self;
+
+    /// Returns the product of `self` and `other`, in that order.
+    #[must_use]
+    fn combine(self, other: Self) -> Self;
+
+    /// Returns the inverse of `self`.
+    #[must_use]
+    fn inverse(self) -> Self;
+}
+
+macro_rules! impl_group_for_integers {
+    ($($integer:ty),*) => {
+        $(
+            impl Group for $integer {
+                #[inline]
+                fn identity() -> Self {
+                    0
+                }
+
+                #[inline]
+                fn combine(self, other: Self) -> Self {
+                    self.wrapping_add(other)
+                }
+
+                #[inline]
+                fn inverse(self) -> Self {
+                    self.wrapping_neg()
+                }
+            }
+        )*
+    };
+}
+
+impl_group_for_integers!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+impl Group for bool {
+    #[inline]
+    fn identity() -> Self {
+        false
+    }
+
+    #[inline]
+    fn combine(self, other: Self) -> Self {
+        self ^ other
+    }
+
+    #[inline]
+    fn inverse(self) -> Self {
+        self
+    }
+}
+
+/// The error returned by [`WeightedDisjointSet::join_with`] if the requested difference contradicts the known one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Contradiction<G> {
+    existing_diff: G,
+}
+
+impl<G> Contradiction<G> {
+    /// Returns the difference that was already known between the two elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::WeightedDisjointSet;
+    ///
+    /// let mut wds = WeightedDisjointSet::with_len(2);
+    /// wds.join_with(0, 1, 5).unwrap();
+    ///
+    /// let contradiction = wds.join_with(0, 1, 3).unwrap_err();
+    /// assert_eq!(*contradiction.existing_diff(), 5);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn existing_diff(&self) -> &G {
+        &self.existing_diff
+    }
+}
+
+impl<G> Display for Contradiction<G>
+where
+    G: Debug,
+{
+    #[inline]
+    #[allow(clippy::use_debug)]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "difference contradicts existing difference {:?}",
+            self.existing_diff
+        )
+    }
+}
+
+impl<G> Error for Contradiction<G> where G: Debug {}
+
+/// A disjoint-set data structure that additionally tracks a relative potential between joined elements.
+///
+/// Every element has an unknown potential in the [`Group`] `G`. Calling [`join_with`] with a difference `diff` records that the potential of `y` is `diff` combined with the potential of `x`.
+/// In additive notation, this means `y - x = diff`. Afterwards, [`diff`] returns the difference between any two joined elements, derived from all recorded differences.
+///
+/// This can be used to check the consistency of equations like `x - y = 5`, for parity constraints and bipartiteness with `bool`, or for rotation offsets.
+///
+/// [`join_with`]: WeightedDisjointSet::join_with
+/// [`diff`]: WeightedDisjointSet::diff
+///
+/// # Examples
+///
+/// ```
+/// use disjoint::WeightedDisjointSet;
+///
+/// let mut wds = WeightedDisjointSet::with_len(3);
+///
+/// // x1 - x0 = 3 and x2 - x1 = 4.
+/// assert_eq!(wds.join_with(0, 1, 3), Ok(true));
+/// assert_eq!(wds.join_with(1, 2, 4), Ok(true));
+///
+/// // Therefore, x2 - x0 = 7.
+/// assert_eq!(wds.diff(0, 2), Some(7));
+/// assert_eq!(wds.diff(2, 0), Some(-7));
+///
+/// // Consistent information is accepted, but contradicting information is reported.
+/// assert_eq!(wds.join_with(0, 2, 7), Ok(false));
+/// assert!(wds.join_with(0, 2, 8).is_err());
+/// ```
+pub struct WeightedDisjointSet<G> {
+    parents: Vec<Cell<usize>>,
+    weights: Vec<Cell<G>>,
+    ranks: Vec<u8>,
+}
+
+impl<G> Debug for WeightedDisjointSet<G>
+where
+    G: Group + Debug,
+{
+    #[allow(clippy::missing_inline_in_public_items)]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WeightedDisjointSet")
+            .field("parents", &self.parents)
+            .field("weights", &self.weights)
+            .field("ranks", &self.ranks)
+            .finish()
+    }
+}
+
+impl<G> Clone for WeightedDisjointSet<G>
+where
+    G: Group,
+{
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            parents: self.parents.clone(),
+            weights: self.weights.clone(),
+            ranks: self.ranks.clone(),
+        }
+    }
+}
+
+impl<G> Default for WeightedDisjointSet<G>
+where
+    G: Group,
+{
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<G> WeightedDisjointSet<G>
+where
+    G: Group,
+{
+    #[inline]
+    #[must_use]
+    fn get_parent(&self, id: usize) -> usize {
+        self.parents[id].get()
+    }
+
+    /// Returns the root of `element`, together with the potential of `element` relative to the root.
+    ///
+    /// Uses full path compression, so that afterwards every element on the path points directly to the root.
+    #[must_use]
+    fn root_and_weight_of(&self, element: usize) -> (usize, G) {
+        let mut root = element;
+        let mut weight = G::identity();
+        loop {
+            let parent = self.get_parent(root);
+            if parent == root {
+                break;
+            }
+            weight = weight.combine(self.weights[root].get());
+            root = parent;
+        }
+
+        let mut current = element;
+        let mut current_weight = weight;
+        while current != root {
+            let parent = self.get_parent(current);
+            let old_weight = self.weights[current].replace(current_weight);
+            self.parents[current].set(root);
+            current_weight = old_weight.inverse().combine(current_weight);
+            current = parent;
+        }
+
+        (root, weight)
+    }
+
+    /// Returns an element of the subset containing `child`.
+    /// This exact element is returned for every member of the subset.
+    ///
+    /// # Important
+    ///
+    /// The specific choice of the returned element is an implementation detail.
+    /// There are no further guarantees beyond what is documented here.
+    /// If you just want to check if two elements are in the same subset, use [`is_joined`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `child` is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::WeightedDisjointSet;
+    ///
+    /// let mut wds = WeightedDisjointSet::with_len(3);
+    /// wds.join_with(0, 1, 2).unwrap();
+    /// assert_eq!(wds.root_of(0), wds.root_of(1));
+    /// assert_eq!(wds.root_of(2), 2);
+    /// ```
+    ///
+    /// [`is_joined`]: WeightedDisjointSet::is_joined
+    #[inline]
+    #[must_use]
+    pub fn root_of(&self, child: usize) -> usize {
+        self.root_and_weight_of(child).0
+    }
+
+    /// Constructs a new `WeightedDisjointSet<G>` with `len` elements, named `0` to `n - 1`, each in its own set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::WeightedDisjointSet;
+    ///
+    /// let wds = WeightedDisjointSet::<i32>::with_len(4);
+    /// assert_eq!(wds.len(), 4);
+    /// assert!(!wds.is_joined(0, 3));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn with_len(len: usize) -> Self {
+        Self {
+            parents: (0..len).map(Cell::new).collect(),
+            weights: vec![Cell::new(G::identity()); len],
+            ranks: vec![0; len],
+        }
+    }
+
+    /// Constructs a new, empty `WeightedDisjointSet<G>`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::WeightedDisjointSet;
+    ///
+    /// let wds = WeightedDisjointSet::<i32>::new();
+    /// assert!(wds.is_empty());
+    /// ```
+    #[inline]
+    #[must_use]
+    #[allow(clippy::missing_const_for_fn)]
+    pub fn new() -> Self {
+        Self {
+            parents: Vec::new(),
+            weights: Vec::new(),
+            ranks: Vec::new(),
+        }
+    }
+
+    /// Adds a new element, not joined to any other element. Returns the index
+    /// of the new element.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new capacity exceeds `isize::MAX` bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::WeightedDisjointSet;
+    ///
+    /// let mut wds = WeightedDisjointSet::<i32>::with_len(1);
+    /// assert_eq!(wds.add_singleton(), 1);
+    /// assert_eq!(wds.diff(0, 1), None);
+    /// ```
+    #[inline]
+    pub fn add_singleton(&mut self) -> usize {
+        let id = self.len();
+        self.parents.push(Cell::new(id));
+        self.weights.push(Cell::new(G::identity()));
+        self.ranks.push(0);
+        id
+    }
+
+    /// Records that the potential of `second_element` is `diff` combined with the potential of `first_element`, i.e. `second_element - first_element = diff` in additive notation.
+    ///
+    /// If the elements are in different sets, joins them together and returns `Ok(true)`.
+    /// If they are already joined with the same difference, does nothing and returns `Ok(false)`.
+    /// If they are already joined with a different difference, does nothing and returns a [`Contradiction`].
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`Contradiction`] if `diff` contradicts the difference returned by [`diff`].
+    ///
+    /// [`diff`]: WeightedDisjointSet::diff
+    ///
+    /// # Panics
+    ///
+    /// Panics if `first_element` or `second_element` is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::WeightedDisjointSet;
+    ///
+    /// // Check if a graph is bipartite, using parities.
+    /// let mut wds = WeightedDisjointSet::with_len(3);
+    /// assert_eq!(wds.join_with(0, 1, true), Ok(true));
+    /// assert_eq!(wds.join_with(1, 2, true), Ok(true));
+    ///
+    /// // Adding the edge from 2 to 0 would create an odd cycle.
+    /// assert!(wds.join_with(2, 0, true).is_err());
+    /// ```
+    #[allow(clippy::missing_inline_in_public_items)]
+    pub fn join_with(
+        &mut self,
+        first_element: usize,
+        second_element: usize,
+        diff: G,
+    ) -> Result<bool, Contradiction<G>> {
+        let (root_first, weight_first) = self.root_and_weight_of(first_element);
+        let (root_second, weight_second) = self.root_and_weight_of(second_element);
+
+        if root_first == root_second {
+            let existing_diff = weight_second.combine(weight_first.inverse());
+            return if existing_diff == diff {
+                Ok(false)
+            } else {
+                Err(Contradiction { existing_diff })
+            };
+        }
+
+        // The potential of `root_second` relative to `root_first`.
+        let root_diff = weight_second.inverse().combine(diff).combine(weight_first);
+
+        let rank_first = self.ranks[root_first];
+        let rank_second = self.ranks[root_second];
+        if rank_first < rank_second {
+            self.parents[root_first].set(root_second);
+            self.weights[root_first].set(root_diff.inverse());
+        } else {
+            if rank_first == rank_second {
+                self.ranks[root_first] += 1;
+            }
+            self.parents[root_second].set(root_first);
+            self.weights[root_second].set(root_diff);
+        }
+
+        Ok(true)
+    }
+
+    /// Returns the difference between the potentials of `first_element` and `second_element`, if they are joined.
+    ///
+    /// This is the `diff` for which `second_element - first_element = diff` in additive notation, so it is consistent with [`join_with`].
+    /// Returns `None` if the elements are in different sets.
+    ///
+    /// [`join_with`]: WeightedDisjointSet::join_with
+    ///
+    /// # Panics
+    ///
+    /// Panics if `first_element` or `second_element` is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::WeightedDisjointSet;
+    ///
+    /// let mut wds = WeightedDisjointSet::with_len(3);
+    /// wds.join_with(1, 0, 10).unwrap();
+    ///
+    /// assert_eq!(wds.diff(1, 0), Some(10));
+    /// assert_eq!(wds.diff(0, 1), Some(-10));
+    /// assert_eq!(wds.diff(2, 2), Some(0));
+    /// assert_eq!(wds.diff(0, 2), None);
+    /// ```
+    #[must_use]
+    #[inline]
+    // `bool::then` is not available on old Rust versions.
+    #[allow(clippy::if_then_some_else_none)]
+    pub fn diff(&self, first_element: usize, second_element: usize) -> Option<G> {
+        let (root_first, weight_first) = self.root_and_weight_of(first_element);
+        let (root_second, weight_second) = self.root_and_weight_of(second_element);
+
+        if root_first == root_second {
+            Some(weight_second.combine(weight_first.inverse()))
+        } else {
+            None
+        }
+    }
+
+    /// Returns `true` if `first_element` and `second_element` are in the same subset.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `first_element` or `second_element` is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::WeightedDisjointSet;
+    ///
+    /// let mut wds = WeightedDisjointSet::with_len(3);
+    /// wds.join_with(0, 1, 1).unwrap();
+    /// assert!(wds.is_joined(1, 0));
+    /// assert!(!wds.is_joined(0, 2));
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn is_joined(&self, first_element: usize, second_element: usize) -> bool {
+        self.root_of(first_element) == self.root_of(second_element)
+    }
+
+    /// Returns the number of elements in the disjoint set, regardless of how they are joined together.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::WeightedDisjointSet;
+    ///
+    /// let mut wds = WeightedDisjointSet::with_len(4);
+    /// wds.join_with(1, 3, 7).unwrap();
+    /// assert_eq!(wds.len(), 4);
+    /// ```
+    #[must_use]
+    #[inline]
+    #[allow(clippy::missing_const_for_fn)]
+    pub fn len(&self) -> usize {
+        self.parents.len()
+    }
+
+    /// Returns `true` if the disjoint set contains no elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::WeightedDisjointSet;
+    ///
+    /// assert!(WeightedDisjointSet::<i32>::new().is_empty());
+    /// assert!(!WeightedDisjointSet::<i32>::with_len(1).is_empty());
+    /// ```
+    #[must_use]
+    #[inline]
+    #[allow(clippy::missing_const_for_fn)]
+    pub fn is_empty(&self) -> bool {
+        self.parents.is_empty()
+    }
+
+    /// Returns a `Vec` of all sets. Each entry corresponds to one set, and is a `Vec` of its elements.
+    ///
+    /// The sets are ordered by their smallest contained element. The elements inside each sets are ordered.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use disjoint::WeightedDisjointSet;
+    ///
+    /// let mut wds = WeightedDisjointSet::with_len(4);
+    /// wds.join_with(3, 1, true).unwrap();
+    /// assert_eq!(wds.sets(), vec![vec![0], vec![1, 3], vec![2]]);
+    /// ```
+    #[must_use]
+    #[allow(clippy::missing_inline_in_public_items)]
+    pub fn sets(&self) -> Vec<Vec<usize>> {
+        let mut result = Vec::new();
+        let mut root_to_result_id = HashMap::new();
+
+        for index in 0..self.len() {
+            let root = self.root_of(index);
+            let &mut result_id = root_to_result_id.entry(root).or_insert_with(|| {
+                let id = result.len();
+                result.push(Vec::with_capacity(1));
+                id
+            });
+            result[result_id].push(index);
+        }
+
+        result
+    }
+}
@@ -0,0 +1,179 @@
+use disjoint::{disjoint_set_vec, DisjointSet, DisjointSetVec, MemberTracking};
+use std::io::{ErrorKind, Read};
+
+fn to_bytes(ds: &DisjointSet) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    ds.write_to(&mut bytes).unwrap();
+    bytes
+}
+
+fn error_kind(bytes: &[u8]) -> ErrorKind {
+    DisjointSet::read_from(bytes).unwrap_err().kind()
+}
+
+#[test]
+fn round_trips() {
+    let len = 1000;
+    let mut ds = DisjointSet::with_len(len);
+    for element in 0..len {
+        ds.join(element, (element * element + 7) % 97);
+    }
+
+    let read = DisjointSet::read_from(&to_bytes(&ds)[..]).unwrap();
+    assert_eq!(read, ds);
+    assert_eq!(read.sets(), ds.sets());
+
+    let empty = DisjointSet::new();
+    assert_eq!(
+        DisjointSet::read_from(&to_bytes(&empty)[..]).unwrap(),
+        empty
+    );
+}
+
+#[test]
+fn output_does_not_depend_on_join_order() {
+    let mut first = DisjointSet::with_len(6);
+    first.join(5, 1);
+    first.join(1, 3);
+    first.join(0, 4);
+
+    let mut second = DisjointSet::with_len(6);
+    second.join(4, 0);
+    second.join(3, 5);
+    second.join(3, 1);
+
+    assert_eq!(to_bytes(&first), to_bytes(&second));
+}
+
+#[test]
+fn layout() {
+    let mut ds = DisjointSet::with_len(200);
+    ds.join(0, 199);
+
+    let bytes = to_bytes(&ds);
+    assert_eq!(&bytes[..4], b"DJST");
+    assert_eq!(bytes[4], 1);
+    // 200 as varint.
+    assert_eq!(&bytes[5..7], &[0xc8, 0x01]);
+    // The labels 128 to 198 take two bytes, the other 129 labels one byte.
+    assert_eq!(bytes.len(), 7 + 2 * 71 + 129 + 8);
+    // The last element is in the first set.
+    assert_eq!(bytes[bytes.len() - 9], 0);
+}
+
+#[test]
+fn detects_corruption() {
+    let mut ds = DisjointSet::with_len(10);
+    ds.join(2, 7);
+    let bytes = to_bytes(&ds);
+
+    for position in 0..bytes.len() {
+        let mut corrupted = bytes.clone();
+        corrupted[position] ^= 0x10;
+        assert!(DisjointSet::read_from(&corrupted[..]).is_err());
+    }
+}
+
+#[test]
+fn rejects_invalid_header() {
+    let mut bytes = to_bytes(&DisjointSet::with_len(3));
+
+    bytes[0] = b'X';
+    assert_eq!(error_kind(&bytes), ErrorKind::InvalidData);
+
+    bytes[0] = b'D';
+    bytes[4] = 2;
+    assert_eq!(error_kind(&bytes), ErrorKind::InvalidData);
+}
+
+#[test]
+fn rejects_non_canonical_labels() {
+    // Magic, version, 2 elements, labels 1 and 0, and a bogus checksum.
+    let mut bytes = b"DJST".to_vec();
+    bytes.extend_from_slice(&[1, 2, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+    let error = DisjointSet::read_from(&bytes[..]).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::InvalidData);
+    assert_eq!(error.to_string(), "labels are not canonical");
+}
+
+#[test]
+fn rejects_oversized_varint() {
+    let mut bytes = b"DJST".to_vec();
+    bytes.push(1);
+    bytes.extend_from_slice(&[0xff; 11]);
+    bytes.push(0x01);
+    assert_eq!(error_kind(&bytes), ErrorKind::InvalidData);
+}
+
+#[test]
+fn rejects_truncated_data() {
+    let bytes = to_bytes(&DisjointSet::with_len(20));
+
+    for len in 0..bytes.len() {
+        assert_eq!(error_kind(&bytes[..len]), ErrorKind::UnexpectedEof);
+    }
+}
+
+#[test]
+fn reads_exactly_the_written_data() {
+    let mut first = DisjointSet::with_len(4);
+    first.join(0, 3);
+    let mut second = DisjointSet::with_len(2);
+    second.join(0, 1);
+
+    let mut bytes = to_bytes(&first);
+    bytes.extend(to_bytes(&second));
+    bytes.push(42);
+
+    let mut reader = &bytes[..];
+    assert_eq!(DisjointSet::read_from(&mut reader).unwrap(), first);
+    assert_eq!(DisjointSet::read_from(&mut reader).unwrap(), second);
+
+    let mut rest = Vec::new();
+    reader.read_to_end(&mut rest).unwrap();
+    assert_eq!(rest, vec![42]);
+}
+
+#[test]
+fn disjoint_set_vec_indices_round_trip() {
+    let mut dsv = disjoint_set_vec![10, 20, 30, 40];
+    dsv.join(3, 1);
+    dsv.join(2, 1);
+
+    let mut bytes = Vec::new();
+    dsv.write_indices_to(&mut bytes).unwrap();
+    assert_eq!(bytes, to_bytes(dsv.indices()));
+
+    let mut other = disjoint_set_vec!['a', 'b', 'c', 'd'];
+    other.read_indices_from(&bytes[..]).unwrap();
+    assert_eq!(other.indices(), dsv.indices());
+    assert_eq!(other.values(), &['a', 'b', 'c', 'd']);
+}
+
+#[test]
+fn disjoint_set_vec_read_indices_updates_members() {
+    let mut dsv = DisjointSetVec::with_values_and_tracking(vec!['a', 'b', 'c'], MemberTracking);
+    dsv.join(0, 1);
+
+    let mut ds = DisjointSet::with_len(3);
+    ds.join(2, 1);
+    dsv.read_indices_from(&to_bytes(&ds)[..]).unwrap();
+
+    assert_eq!(dsv.members(0).collect::<Vec<_>>(), vec![&'a']);
+    let mut members: Vec<_> = dsv.members(2).collect();
+    members.sort();
+    assert_eq!(members, vec![&'b', &'c']);
+    assert_eq!(dsv.size_of(1), 2);
+    assert_eq!(dsv.indices().check_invariants(), Ok(()));
+}
+
+#[test]
+fn disjoint_set_vec_rejects_len_mismatch() {
+    let mut dsv = disjoint_set_vec!['a', 'b', 'c'];
+    dsv.join(0, 1);
+
+    let bytes = to_bytes(&DisjointSet::with_len(4));
+    let error = dsv.read_indices_from(&bytes[..]).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::InvalidData);
+    assert!(dsv.is_joined(0, 1));
+}
@@ -0,0 +1,165 @@
+use std::{sync::Arc, thread};
+
+use disjoint::{ConcurrentDisjointSet, DisjointSet};
+
+fn verify_subsets(disjoint_set: &ConcurrentDisjointSet, expected_subsets_ordered: &[Vec<usize>]) {
+    assert_eq!(
+        disjoint_set.len(),
+        expected_subsets_ordered
+            .iter()
+            .map(|subset| subset.len())
+            .sum()
+    );
+
+    for (subset_id, subset) in expected_subsets_ordered.iter().enumerate() {
+        for (other_subset_id, other_subset) in expected_subsets_ordered.iter().enumerate() {
+            for &member in subset {
+                for &other_member in other_subset {
+                    assert_eq!(
+                        disjoint_set.is_joined(member, other_member),
+                        subset_id == other_subset_id
+                    )
+                }
+            }
+        }
+    }
+
+    assert_eq!(disjoint_set.sets(), expected_subsets_ordered)
+}
+
+#[test]
+fn constructor_prodcues_singletons() {
+    let disjoint_set = ConcurrentDisjointSet::with_len(5);
+
+    let expected_subsets = [vec![0], vec![1], vec![2], vec![3], vec![4]];
+    verify_subsets(&disjoint_set, &expected_subsets);
+}
+
+#[test]
+fn join_returns_if_sets_were_disjoint() {
+    let disjoint_set = ConcurrentDisjointSet::with_len(5);
+
+    assert!(disjoint_set.join(0, 1));
+    assert!(!disjoint_set.join(1, 0));
+    assert!(disjoint_set.join(2, 3));
+    assert!(disjoint_set.join(3, 0));
+    assert!(!disjoint_set.join(1, 2));
+    assert!(!disjoint_set.join(4, 4));
+
+    let expected_subsets = [vec![0, 1, 2, 3], vec![4]];
+    verify_subsets(&disjoint_set, &expected_subsets);
+}
+
+#[test]
+fn join_in_index_order_produces_one_set() {
+    let disjoint_set = ConcurrentDisjointSet::with_len(1000);
+
+    for index in 1..1000 {
+        assert!(disjoint_set.join(index - 1, index));
+    }
+
+    let expected_subsets = [(0..1000).collect::<Vec<_>>()];
+    verify_subsets(&disjoint_set, &expected_subsets);
+}
+
+#[test]
+fn add_singleton_produces_singleton() {
+    let mut disjoint_set = ConcurrentDisjointSet::with_len(3);
+    disjoint_set.join(0, 2);
+    assert_eq!(disjoint_set.add_singleton(), 3);
+    verify_subsets(&disjoint_set, &[vec![0, 2], vec![1], vec![3]]);
+}
+
+#[test]
+#[should_panic]
+fn panic_join_element_oob() {
+    let disjoint_set = ConcurrentDisjointSet::with_len(100);
+    disjoint_set.join(0, 100);
+}
+
+#[test]
+fn concurrent_joins_are_all_applied_exactly_once() {
+    let len = 10_000;
+    let number_threads = 8;
+    let disjoint_set = Arc::new(ConcurrentDisjointSet::with_len(len));
+
+    // Every thread tries to join the same chain, so every link has to be reported as new by exactly one thread.
+    let handles: Vec<_> = (0..number_threads)
+        .map(|thread_id| {
+            let disjoint_set = Arc::clone(&disjoint_set);
+            thread::spawn(move || {
+                let mut successful_joins = 0;
+                for offset in 0..len - 1 {
+                    let index = (offset + thread_id * 997) % (len - 1);
+                    if disjoint_set.join(index, index + 1) {
+                        successful_joins += 1;
+                    }
+                }
+                successful_joins
+            })
+        })
+        .collect();
+
+    let total_successful_joins: usize = handles
+        .into_iter()
+        .map(|handle| handle.join().unwrap())
+        .sum();
+
+    assert_eq!(total_successful_joins, len - 1);
+    assert_eq!(disjoint_set.sets().len(), 1);
+}
+
+#[test]
+fn concurrent_joins_of_separate_sets_stay_separate() {
+    let number_threads = 4;
+    let len_per_thread = 1000;
+    let disjoint_set = Arc::new(ConcurrentDisjointSet::with_len(
+        number_threads * len_per_thread,
+    ));
+
+    // Thread t joins all elements congruent to t modulo the number of threads.
+    let handles: Vec<_> = (0..number_threads)
+        .map(|thread_id| {
+            let disjoint_set = Arc::clone(&disjoint_set);
+            thread::spawn(move || {
+                for step in 1..len_per_thread {
+                    disjoint_set.join(
+                        thread_id + (step - 1) * number_threads,
+                        thread_id + step * number_threads,
+                    );
+                    assert!(!disjoint_set.is_joined(thread_id, (thread_id + 1) % number_threads));
+                }
+            })
+        })
+        .collect();
+
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    let expected_subsets: Vec<Vec<_>> = (0..number_threads)
+        .map(|thread_id| {
+            (0..len_per_thread)
+                .map(|step| thread_id + step * number_threads)
+                .collect()
+        })
+        .collect();
+    verify_subsets(&disjoint_set, &expected_subsets);
+}
+
+#[test]
+fn conversion_from_and_into_disjoint_set_preserves_sets() {
+    let mut disjoint_set = DisjointSet::with_len(6);
+    disjoint_set.join(0, 3);
+    disjoint_set.join(4, 3);
+    disjoint_set.join(1, 5);
+
+    let concurrent = ConcurrentDisjointSet::from(disjoint_set.clone());
+    verify_subsets(&concurrent, &[vec![0, 3, 4], vec![1, 5], vec![2]]);
+
+    concurrent.join(2, 5);
+    let mut expected = disjoint_set;
+    expected.join(2, 5);
+
+    assert_eq!(DisjointSet::from(concurrent), expected);
+}
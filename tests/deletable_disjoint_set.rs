@@ -0,0 +1,190 @@
+use disjoint::{DeletableDisjointSet, DisjointSet};
+
+fn verify_subsets(disjoint_set: &DeletableDisjointSet, expected_subsets_ordered: &[Vec<usize>]) {
+    for (subset_id, subset) in expected_subsets_ordered.iter().enumerate() {
+        for (other_subset_id, other_subset) in expected_subsets_ordered.iter().enumerate() {
+            for &member in subset {
+                assert_eq!(disjoint_set.size_of(member), subset.len());
+                for &other_member in other_subset {
+                    assert_eq!(
+                        disjoint_set.is_joined(member, other_member),
+                        subset_id == other_subset_id
+                    )
+                }
+            }
+        }
+    }
+
+    assert_eq!(disjoint_set.sets(), expected_subsets_ordered)
+}
+
+#[test]
+fn behaves_like_disjoint_set_without_deletion() {
+    let mut deletable = DeletableDisjointSet::with_len(8);
+    let mut reference = DisjointSet::with_len(8);
+
+    for &(first, second) in &[(0, 4), (2, 7), (4, 2), (6, 6), (7, 0), (1, 3)] {
+        assert_eq!(deletable.join(first, second), reference.join(first, second));
+    }
+    assert_eq!(deletable.add_singleton(), reference.add_singleton());
+
+    verify_subsets(&deletable, &reference.sets());
+}
+
+#[test]
+fn isolate_keeps_others_joined() {
+    let mut ds = DeletableDisjointSet::with_len(5);
+    ds.join(0, 1);
+    ds.join(2, 3);
+    ds.join(1, 3);
+
+    assert!(ds.isolate(1));
+    verify_subsets(&ds, &[vec![0, 2, 3], vec![1], vec![4]]);
+
+    assert!(!ds.isolate(1));
+    assert!(!ds.isolate(4));
+    verify_subsets(&ds, &[vec![0, 2, 3], vec![1], vec![4]]);
+
+    // The isolated element can be joined again.
+    ds.join(1, 4);
+    ds.join(4, 0);
+    verify_subsets(&ds, &[vec![0, 1, 2, 3, 4]]);
+}
+
+#[test]
+fn isolate_last_remaining_member_returns_false() {
+    let mut ds = DeletableDisjointSet::with_len(2);
+    ds.join(0, 1);
+
+    assert!(ds.isolate(0));
+    assert!(!ds.isolate(1));
+    verify_subsets(&ds, &[vec![0], vec![1]]);
+}
+
+#[test]
+fn remove_excludes_element_from_sets() {
+    let mut ds = DeletableDisjointSet::with_len(4);
+    ds.join(0, 1);
+    ds.join(1, 2);
+
+    ds.remove(1);
+    ds.remove(3);
+    assert!(!ds.contains(1));
+    assert!(!ds.contains(3));
+    assert!(ds.contains(0));
+    assert_eq!(ds.len(), 4);
+    verify_subsets(&ds, &[vec![0, 2]]);
+}
+
+#[test]
+#[should_panic]
+fn panic_join_removed_element() {
+    let mut ds = DeletableDisjointSet::with_len(3);
+    ds.remove(1);
+    ds.join(0, 1);
+}
+
+#[test]
+#[should_panic]
+fn panic_remove_twice() {
+    let mut ds = DeletableDisjointSet::with_len(3);
+    ds.remove(1);
+    ds.remove(1);
+}
+
+#[test]
+fn many_isolations_trigger_rebuild_and_stay_correct() {
+    let len = 100;
+    let mut ds = DeletableDisjointSet::with_len(len);
+    for element in 1..len {
+        ds.join(element - 1, element);
+    }
+
+    // Repeatedly take the even elements out and join them back in, creating many vacant nodes.
+    for round in 0..10 {
+        for element in (0..len).filter(|element| element % 2 == 0) {
+            ds.isolate(element);
+        }
+
+        let odd: Vec<_> = (0..len).filter(|element| element % 2 == 1).collect();
+        let mut expected: Vec<Vec<usize>> = Vec::new();
+        for element in 0..len {
+            if element % 2 == 0 {
+                expected.push(vec![element]);
+            } else if element == 1 {
+                expected.push(odd.clone());
+            }
+        }
+        verify_subsets(&ds, &expected);
+
+        for element in (0..len).filter(|element| element % 2 == 0) {
+            ds.join(element, (element + 1 + 2 * round) % len);
+        }
+        verify_subsets(&ds, &[(0..len).collect()]);
+    }
+}
+
+#[test]
+fn conversion_from_disjoint_set_preserves_sets() {
+    let mut disjoint_set = DisjointSet::with_len(5);
+    disjoint_set.join(0, 3);
+    disjoint_set.join(4, 3);
+
+    let mut ds = DeletableDisjointSet::from(disjoint_set);
+    verify_subsets(&ds, &[vec![0, 3, 4], vec![1], vec![2]]);
+
+    ds.remove(3);
+    verify_subsets(&ds, &[vec![0, 4], vec![1], vec![2]]);
+}
+
+#[test]
+fn root_of_is_live_member() {
+    let len = 50;
+    let mut ds = DeletableDisjointSet::with_len(len);
+    for element in 1..len {
+        ds.join(element - 1, element);
+    }
+
+    // Takes out the current representative over and over, across several rebuilds.
+    for round in 0..len - 1 {
+        let root = ds.root_of(len - 1);
+        if round % 2 == 0 {
+            assert!(ds.isolate(root));
+            assert_eq!(ds.root_of(root), root);
+        } else {
+            ds.remove(root);
+        }
+
+        let new_root = ds.root_of(len - 1);
+        assert_ne!(new_root, root);
+        assert!(new_root < ds.len());
+        assert!(ds.contains(new_root));
+        assert!(ds.is_joined(new_root, len - 1));
+        assert_eq!(ds.root_of(new_root), new_root);
+    }
+    assert_eq!(ds.size_of(len - 1), 1);
+}
+
+#[test]
+fn root_of_after_conversion_and_join_is_live_member() {
+    let mut disjoint_set = DisjointSet::with_len(6);
+    disjoint_set.join(0, 3);
+    disjoint_set.join(4, 3);
+    disjoint_set.join(1, 5);
+
+    let mut ds = DeletableDisjointSet::from(disjoint_set);
+    ds.join(5, 4); // {0, 1, 3, 4, 5}, {2}
+
+    // Removes the representative until one member is left.
+    for _ in 0..4 {
+        ds.remove(ds.root_of(ds.sets()[0][0]));
+
+        let members = ds.sets()[0].clone();
+        let root = ds.root_of(members[0]);
+        assert!(members.contains(&root));
+        for &member in &members {
+            assert_eq!(ds.root_of(member), root);
+        }
+    }
+    assert_eq!(ds.sets().len(), 2);
+}
@@ -1,6 +1,34 @@
-use disjoint::DisjointSet;
+use std::{
+    cmp::Ordering,
+    collections::{hash_map::DefaultHasher, HashSet},
+    hash::{Hash, Hasher},
+};
+
+use disjoint::{
+    disjoint_set, ByRank, DisjointSet, InvalidPartition, InvariantViolation, MemberTracking,
+    NoObserver, PathSplitting, Tracking,
+};
+
+type TrackedDisjointSet<S> = DisjointSet<NoObserver, PathSplitting, ByRank, usize, S>;
+
+fn with_sets_and_tracking<S>(sets: &[Vec<usize>], tracking: S) -> TrackedDisjointSet<S>
+where
+    S: Tracking,
+{
+    let len = sets.iter().map(|set| set.len()).sum();
+    let mut ds = DisjointSet::with_len_and_tracking(len, tracking);
+    for set in sets {
+        for &element in set {
+            ds.join(set[0], element);
+        }
+    }
+    ds
+}
 
-fn verify_subsets(disjoint_set: &DisjointSet, expected_subsets_ordered: &[Vec<usize>]) {
+fn verify_subsets<S>(disjoint_set: &TrackedDisjointSet<S>, expected_subsets_ordered: &[Vec<usize>])
+where
+    S: Tracking,
+{
     assert_eq!(
         disjoint_set.len(),
         expected_subsets_ordered
@@ -25,6 +53,23 @@ fn verify_subsets(disjoint_set: &DisjointSet, expected_subsets_ordered: &[Vec<us
     assert_eq!(disjoint_set.sets(), expected_subsets_ordered)
 }
 
+fn verify_members(
+    disjoint_set: &TrackedDisjointSet<MemberTracking>,
+    expected_subsets_ordered: &[Vec<usize>],
+) {
+    verify_subsets(disjoint_set, expected_subsets_ordered);
+
+    for subset in expected_subsets_ordered {
+        for &member in subset {
+            let mut members: Vec<_> = disjoint_set.members(member).collect();
+            assert_eq!(members[0], member);
+            members.sort();
+            assert_eq!(&members, subset);
+            assert_eq!(disjoint_set.size_of(member), subset.len());
+        }
+    }
+}
+
 #[test]
 fn constructor_prodcues_singletons() {
     let disjoint_set = DisjointSet::with_len(5);
@@ -255,6 +300,19 @@ fn same_len_not_equal() {
     assert_ne!(l, r);
 }
 
+#[test]
+fn refinement_not_equal() {
+    let mut l = DisjointSet::with_len(3);
+    let mut r = DisjointSet::with_len(3);
+
+    l.join(0, 1);
+    r.join(0, 1);
+    r.join(1, 2);
+
+    assert_ne!(l, r);
+    assert_ne!(r, l);
+}
+
 #[test]
 fn different_joining_order_equal() {
     let mut l = DisjointSet::with_len(5);
@@ -376,7 +434,7 @@ fn root_of_works() {
 
 #[test]
 fn can_join_elements_added_later() {
-    let mut ds = DisjointSet::with_len(3);
+    let mut ds = DisjointSet::with_len_and_tracking(3, MemberTracking);
 
     ds.join(0, 2);
 
@@ -388,7 +446,7 @@ fn can_join_elements_added_later() {
     ds.join(3, 0);
 
     let expected = [vec![0, 2, 3], vec![1], vec![4, 5]];
-    verify_subsets(&ds, &expected);
+    verify_members(&ds, &expected);
 }
 
 #[test]
@@ -422,3 +480,654 @@ fn clear_removes_elements() {
     set.clear();
     assert_eq!(set.len(), 0);
 }
+
+#[test]
+fn size_of_tracks_joins() {
+    let mut ds = DisjointSet::with_len(6);
+    for element in 0..6 {
+        assert_eq!(ds.size_of(element), 1);
+    }
+
+    ds.join(0, 1);
+    ds.join(2, 3);
+    ds.join(3, 4);
+    ds.join(4, 2);
+    assert_eq!(ds.size_of(0), 2);
+    assert_eq!(ds.size_of(1), 2);
+    assert_eq!(ds.size_of(4), 3);
+    assert_eq!(ds.size_of(5), 1);
+
+    ds.join(1, 4);
+    for element in 0..5 {
+        assert_eq!(ds.size_of(element), 5);
+    }
+    assert_eq!(ds.add_singleton(), 6);
+    assert_eq!(ds.size_of(6), 1);
+}
+
+#[test]
+fn num_sets_tracks_add_singleton_join_and_clear() {
+    let mut ds = DisjointSet::new();
+    assert_eq!(ds.num_sets(), 0);
+
+    ds.add_singleton();
+    ds.add_singleton();
+    ds.add_singleton();
+    assert_eq!(ds.num_sets(), 3);
+
+    assert!(ds.join(0, 2));
+    assert_eq!(ds.num_sets(), 2);
+    assert!(!ds.join(2, 0));
+    assert_eq!(ds.num_sets(), 2);
+
+    ds.clear();
+    assert_eq!(ds.num_sets(), 0);
+    ds.add_singleton();
+    assert_eq!(ds.num_sets(), 1);
+}
+
+#[test]
+fn largest_set_tracks_joins() {
+    let mut ds = DisjointSet::new();
+    assert_eq!(ds.largest_set(), None);
+
+    ds.add_singleton();
+    assert_eq!(ds.largest_set(), Some(0));
+
+    ds = DisjointSet::with_len(7);
+    ds.join(5, 6);
+    assert!(ds.is_joined(ds.largest_set().unwrap(), 5));
+    ds.join(0, 1);
+    ds.join(1, 2);
+    assert!(ds.is_joined(ds.largest_set().unwrap(), 0));
+    ds.join(3, 4);
+    ds.join(4, 5);
+    assert!(ds.is_joined(ds.largest_set().unwrap(), 3));
+    assert_eq!(ds.size_of(ds.largest_set().unwrap()), 4);
+
+    ds.clear();
+    assert_eq!(ds.largest_set(), None);
+}
+
+#[test]
+fn size_histogram_counts_sets_by_size() {
+    let mut ds = DisjointSet::with_len(8);
+    ds.join(0, 1);
+    ds.join(2, 3);
+    ds.join(4, 5);
+    ds.join(5, 6);
+
+    let histogram = ds.size_histogram();
+    assert_eq!(
+        histogram.into_iter().collect::<Vec<_>>(),
+        vec![(1, 1), (2, 2), (3, 1)]
+    );
+    assert!(DisjointSet::new().size_histogram().is_empty());
+}
+
+#[test]
+fn members_follow_joins() {
+    let len = 300;
+    let mut ds = DisjointSet::with_len_and_tracking(len, MemberTracking);
+    for element in 0..len {
+        ds.join(element, (element * 7) % 30);
+    }
+    ds.add_singleton();
+
+    for element in 0..len {
+        let mut members: Vec<_> = ds.members(element).collect();
+        members.sort();
+        let expected: Vec<_> = ds
+            .sets()
+            .into_iter()
+            .find(|set| set.contains(&element))
+            .unwrap();
+        assert_eq!(members, expected);
+        assert_eq!(members.len(), ds.size_of(element));
+    }
+    assert_eq!(ds.members(len).collect::<Vec<_>>(), vec![len]);
+
+    ds.clear();
+    ds.add_singleton();
+    assert_eq!(ds.members(0).collect::<Vec<_>>(), vec![0]);
+}
+
+#[test]
+#[should_panic]
+fn panic_members_element_oob() {
+    let ds = DisjointSet::with_len_and_tracking(3, MemberTracking);
+    let _ = ds.members(3);
+}
+
+#[test]
+fn labels_follow_sets() {
+    let len = 300;
+    let mut ds = DisjointSet::with_len(len);
+    for element in 0..len {
+        ds.join(element, (element * 11) % 45);
+    }
+
+    let (labels, number_labels) = ds.labels();
+    let sets = ds.sets();
+    assert_eq!(number_labels, sets.len());
+    assert_eq!(number_labels, ds.num_sets());
+    for (label, set) in sets.iter().enumerate() {
+        for &element in set {
+            assert_eq!(labels[element], label);
+        }
+    }
+
+    assert_eq!(DisjointSet::new().labels(), (Vec::new(), 0));
+}
+
+#[test]
+fn from_labels_inverts_labels() {
+    let mut ds = DisjointSet::with_len(8);
+    ds.join(7, 2);
+    ds.join(5, 0);
+    ds.join(2, 4);
+
+    let (labels, _) = ds.labels();
+    let from_labels = DisjointSet::from_labels(&labels);
+    assert_eq!(from_labels, ds);
+    assert_eq!(from_labels.sets(), ds.sets());
+
+    let from_chars = DisjointSet::from_labels(&['x', 'y', 'x', 'z', 'y']);
+    assert_eq!(from_chars.sets(), vec![vec![0, 2], vec![1, 4], vec![3]]);
+    assert!(DisjointSet::from_labels::<u8>(&[]).is_empty());
+}
+
+#[test]
+fn from_sets_inverts_sets() {
+    let mut ds = DisjointSet::with_len(8);
+    ds.join(6, 1);
+    ds.join(3, 1);
+    ds.join(0, 7);
+
+    let from_sets = DisjointSet::from_sets(&ds.sets()).unwrap();
+    assert_eq!(from_sets, ds);
+    assert_eq!(from_sets.sets(), ds.sets());
+
+    let shuffled = DisjointSet::from_sets(&[vec![5], vec![7, 0], vec![4], vec![2], vec![1, 3, 6]]);
+    assert_eq!(shuffled.unwrap().sets(), ds.sets());
+    assert!(DisjointSet::from_sets(&[]).unwrap().is_empty());
+}
+
+#[test]
+fn from_sets_rejects_invalid_partitions() {
+    let errors = [
+        (vec![vec![0, 1], vec![]], InvalidPartition::EmptySet),
+        (
+            vec![vec![0, 1], vec![3]],
+            InvalidPartition::OutOfBounds { element: 3, len: 3 },
+        ),
+        (
+            vec![vec![0, 1], vec![1]],
+            InvalidPartition::Duplicate { element: 1 },
+        ),
+        (
+            vec![vec![2, 2, 0]],
+            InvalidPartition::Duplicate { element: 2 },
+        ),
+    ];
+
+    for (sets, expected_error) in errors.iter() {
+        assert_eq!(DisjointSet::from_sets(sets).unwrap_err(), *expected_error);
+    }
+
+    assert_eq!(
+        InvalidPartition::OutOfBounds { element: 3, len: 3 }.to_string(),
+        "element 3 is out of bounds for 3 elements"
+    );
+}
+
+#[test]
+fn collect_grows_to_largest_element() {
+    let edges = [(1, 4), (4, 2), (7, 7), (0, 6)];
+    let ds: DisjointSet = edges.iter().cloned().collect();
+    verify_subsets(&ds, &[vec![0, 6], vec![1, 2, 4], vec![3], vec![5], vec![7]]);
+
+    let empty: DisjointSet = Vec::new().into_iter().collect();
+    assert!(empty.is_empty());
+}
+
+#[test]
+fn extend_keeps_existing_joins() {
+    let mut ds = DisjointSet::with_len(3);
+    ds.join(0, 2);
+    ds.extend(vec![(2, 4), (1, 1)]);
+    verify_subsets(&ds, &[vec![0, 2, 4], vec![1], vec![3]]);
+
+    ds.extend(Vec::new());
+    assert_eq!(ds.len(), 5);
+}
+
+#[test]
+fn disjoint_set_macro_builds_groups() {
+    let ds = disjoint_set![[0, 1], [2], [3, 4, 5]];
+    verify_subsets(&ds, &[vec![0, 1], vec![2], vec![3, 4, 5]]);
+
+    let trailing_commas = disjoint_set![[4, 1,], [3, 0, 2],];
+    verify_subsets(&trailing_commas, &[vec![0, 2, 3], vec![1, 4]]);
+
+    assert_eq!(disjoint_set![], DisjointSet::new());
+}
+
+#[test]
+#[should_panic(expected = "invalid partition: element 3 is out of bounds for 3 elements")]
+fn panic_disjoint_set_macro_invalid_partition() {
+    let _ = disjoint_set![[0, 3], [1]];
+}
+
+#[test]
+fn refinement_follows_joins() {
+    let fine = disjoint_set![[0, 4], [1], [2, 5], [3]];
+    let coarse = disjoint_set![[0, 2, 4, 5], [1, 3]];
+    let other = disjoint_set![[0, 1], [2, 3], [4, 5]];
+
+    assert!(fine.is_refinement_of(&coarse));
+    assert!(!coarse.is_refinement_of(&fine));
+    assert!(!fine.is_refinement_of(&other));
+    assert!(!other.is_refinement_of(&fine));
+
+    let singletons = DisjointSet::with_len(6);
+    let everything = disjoint_set![[0, 1, 2, 3, 4, 5]];
+    for ds in &[&fine, &coarse, &other] {
+        assert!(singletons.is_refinement_of(ds));
+        assert!(ds.is_refinement_of(&everything));
+        assert!(ds.is_refinement_of(ds));
+    }
+}
+
+#[test]
+fn union_with_is_finest_common_coarsening() {
+    let len = 200;
+    let first: DisjointSet = (0..len)
+        .map(|element| (element, element * 3 % 40))
+        .collect();
+    let second: DisjointSet = (0..len)
+        .map(|element| (element, element * 7 % 25))
+        .collect();
+
+    let mut union = first.clone();
+    union.union_with(&second);
+    assert!(first.is_refinement_of(&union));
+    assert!(second.is_refinement_of(&union));
+
+    let mut expected = first.clone();
+    for element in 0..len {
+        for other in 0..len {
+            if second.is_joined(element, other) {
+                expected.join(element, other);
+            }
+        }
+    }
+    assert_eq!(union, expected);
+
+    let mut chain = disjoint_set![[0, 1], [2, 3], [4]];
+    chain.union_with(&disjoint_set![[0], [1, 2], [3, 4]]);
+    assert_eq!(chain, disjoint_set![[0, 1, 2, 3, 4]]);
+}
+
+#[test]
+fn intersection_is_coarsest_common_refinement() {
+    let len = 200;
+    let first: DisjointSet = (0..len).map(|element| (element, element % 12)).collect();
+    let second: DisjointSet = (0..len).map(|element| (element, element % 8)).collect();
+
+    let intersection = first.intersection(&second);
+    assert!(intersection.is_refinement_of(&first));
+    assert!(intersection.is_refinement_of(&second));
+    assert_eq!(intersection, second.intersection(&first));
+
+    for element in 0..len {
+        for other in 0..len {
+            assert_eq!(
+                intersection.is_joined(element, other),
+                element % 24 == other % 24
+            );
+        }
+    }
+
+    assert_eq!(first.intersection(&first), first);
+    assert_eq!(
+        DisjointSet::new().intersection(&DisjointSet::new()),
+        DisjointSet::new()
+    );
+}
+
+#[test]
+#[should_panic(expected = "lengths differ")]
+fn panic_intersection_different_len() {
+    let _ = DisjointSet::with_len(2).intersection(&DisjointSet::with_len(3));
+}
+
+#[test]
+#[should_panic(expected = "lengths differ")]
+fn panic_is_refinement_of_different_len() {
+    let _ = DisjointSet::with_len(3).is_refinement_of(&DisjointSet::with_len(2));
+}
+
+fn hash_of<T: Hash>(value: &T) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[test]
+// The interior mutability only compresses paths, which does not change the hash.
+#[allow(clippy::mutable_key_type)]
+fn hash_does_not_depend_on_forest() {
+    let mut l = DisjointSet::with_len(6);
+    l.join(0, 5);
+    l.join(5, 3);
+    l.join(1, 2);
+
+    let mut r = DisjointSet::with_len(6);
+    r.join(2, 1);
+    r.join(3, 0);
+    r.join(3, 5);
+    // Compresses paths in `r`, but not in `l`.
+    assert!(r.is_joined(0, 5));
+
+    assert_eq!(l, r);
+    assert_eq!(hash_of(&l), hash_of(&r));
+
+    let states: HashSet<_> = vec![l, r, disjoint_set![[0, 3, 5], [1], [2], [4]]]
+        .into_iter()
+        .collect();
+    assert_eq!(states.len(), 2);
+}
+
+#[test]
+fn partial_cmp_by_refinement() {
+    let fine = disjoint_set![[0, 4], [1], [2, 5], [3]];
+    let coarse = disjoint_set![[0, 2, 4, 5], [1, 3]];
+    let other = disjoint_set![[0, 1], [2, 3], [4, 5]];
+
+    assert_eq!(fine.partial_cmp(&coarse), Some(Ordering::Less));
+    assert_eq!(coarse.partial_cmp(&fine), Some(Ordering::Greater));
+    assert_eq!(fine.partial_cmp(&fine.clone()), Some(Ordering::Equal));
+    assert_eq!(fine.partial_cmp(&other), None);
+    assert_eq!(other.partial_cmp(&coarse), None);
+    assert!(fine <= coarse);
+    assert!(fine != other);
+
+    // Same number of sets, but different partitions.
+    assert_eq!(
+        disjoint_set![[0, 1], [2]].partial_cmp(&disjoint_set![[0], [1, 2]]),
+        None
+    );
+    assert_eq!(
+        DisjointSet::with_len(2).partial_cmp(&DisjointSet::with_len(3)),
+        None
+    );
+}
+
+#[test]
+fn append_shifts_indices() {
+    let mut ds = with_sets_and_tracking(&[vec![0, 2], vec![1]], MemberTracking);
+    let mut other = DisjointSet::with_len_and_tracking(4, MemberTracking);
+    other.join(3, 1);
+    other.join(1, 0);
+
+    assert_eq!(ds.append(other), 3);
+    verify_members(&ds, &[vec![0, 2], vec![1], vec![3, 4, 6], vec![5]]);
+    assert_eq!(ds.num_sets(), 4);
+    assert_eq!(ds.size_of(6), 3);
+
+    // Joins and new elements keep working across both parts.
+    ds.join(2, 5);
+    assert_eq!(ds.add_singleton(), 7);
+    verify_members(&ds, &[vec![0, 2, 5], vec![1], vec![3, 4, 6], vec![7]]);
+
+    assert_eq!(
+        ds.append(DisjointSet::with_len_and_tracking(0, MemberTracking)),
+        8
+    );
+    assert_eq!(ds.len(), 8);
+}
+
+#[test]
+fn append_tracks_largest_set() {
+    let mut ds = disjoint_set![[0, 1], [2]];
+    ds.append(disjoint_set![[0, 1, 2]]);
+    let largest = ds.largest_set().unwrap();
+    assert!(ds.is_joined(largest, 3));
+    assert_eq!(ds.size_of(largest), 3);
+
+    ds.append(disjoint_set![[0], [1]]);
+    assert!(ds.is_joined(ds.largest_set().unwrap(), 3));
+
+    let mut empty = DisjointSet::new();
+    assert_eq!(empty.append(disjoint_set![[1, 0]]), 0);
+    assert_eq!(
+        empty.largest_set().map(|largest| empty.size_of(largest)),
+        Some(2)
+    );
+}
+
+#[test]
+fn append_combines_shards() {
+    let shards: Vec<DisjointSet> = (0..4)
+        .map(|shard| {
+            (0..50)
+                .map(|element| (element, (element * (shard + 2)) % 9))
+                .collect()
+        })
+        .collect();
+
+    let mut combined = DisjointSet::new();
+    let mut offsets = Vec::new();
+    for shard in &shards {
+        offsets.push(combined.append(shard.clone()));
+    }
+
+    assert_eq!(offsets, vec![0, 50, 100, 150]);
+    for (shard, &offset) in shards.iter().zip(&offsets) {
+        for element in 0..50 {
+            for other in 0..50 {
+                assert_eq!(
+                    combined.is_joined(element + offset, other + offset),
+                    shard.is_joined(element, other)
+                );
+            }
+        }
+    }
+    assert!(!combined.is_joined(0, 50));
+}
+
+#[test]
+fn restrict_induces_partition() {
+    let len = 100;
+    let ds: DisjointSet = (0..len).map(|element| (element, element % 7)).collect();
+    let elements: Vec<_> = (0..len).filter(|element| element % 3 == 1).rev().collect();
+
+    let restricted = ds.restrict(&elements);
+    assert_eq!(restricted.len(), elements.len());
+    for (index, &element) in elements.iter().enumerate() {
+        for (other_index, &other) in elements.iter().enumerate() {
+            assert_eq!(
+                restricted.is_joined(index, other_index),
+                ds.is_joined(element, other)
+            );
+        }
+    }
+
+    assert_eq!(ds.restrict(&[5, 5]), disjoint_set![[0, 1]]);
+    assert!(ds.restrict(&[]).is_empty());
+}
+
+#[test]
+fn permute_renames_elements() {
+    let ds = disjoint_set![[0, 4], [1, 2, 5], [3]];
+    let permutation = [3, 0, 5, 1, 2, 4];
+
+    let permuted = ds.permute(&permutation);
+    verify_subsets(&permuted, &[vec![0, 4, 5], vec![1], vec![2, 3]]);
+
+    let identity: Vec<_> = (0..6).collect();
+    assert_eq!(ds.permute(&identity), ds);
+}
+
+#[test]
+#[should_panic(expected = "not a permutation")]
+fn panic_permute_not_a_permutation() {
+    let _ = DisjointSet::with_len(3).permute(&[0, 2, 0]);
+}
+
+#[test]
+#[should_panic(expected = "permutation has wrong length")]
+fn panic_permute_wrong_length() {
+    let _ = DisjointSet::with_len(3).permute(&[1, 0]);
+}
+
+#[test]
+fn quotient_merges_images() {
+    let ds = disjoint_set![[0, 5], [1], [2, 3], [4], [6]];
+
+    let quotient = ds.quotient(4, |element| element / 2);
+    verify_subsets(&quotient, &[vec![0, 2], vec![1], vec![3]]);
+
+    // Elements without preimage stay singletons.
+    let spread = ds.quotient(10, |element| element + 3);
+    verify_subsets(
+        &spread,
+        &[
+            vec![0],
+            vec![1],
+            vec![2],
+            vec![3, 8],
+            vec![4],
+            vec![5, 6],
+            vec![7],
+            vec![9],
+        ],
+    );
+
+    assert_eq!(ds.quotient(1, |_| 0), disjoint_set![[0]]);
+}
+
+#[test]
+#[should_panic(expected = "image out of bounds")]
+fn panic_quotient_image_oob() {
+    let _ = DisjointSet::with_len(3).quotient(2, |element| element);
+}
+
+#[test]
+fn forest_introspection() {
+    let len = 200;
+    let mut ds = DisjointSet::with_len(len);
+    for element in 0..len {
+        ds.join(element, element * 17 % 31);
+        ds.join(element, (element + 3) / 2);
+    }
+
+    let mut max_depth = 0;
+    for element in 0..len {
+        let parent = ds.parent_of(element);
+        let depth = ds.depth_of(element);
+        if parent == element {
+            assert_eq!(depth, 0);
+        } else {
+            assert!(ds.rank_of(parent) > ds.rank_of(element));
+            assert_eq!(ds.depth_of(parent) + 1, depth);
+        }
+        assert!(depth <= usize::from(ds.rank_of(ds.root_of(element))));
+        max_depth = max_depth.max(depth);
+    }
+    assert_eq!(ds.max_height(), max_depth);
+    assert_eq!(DisjointSet::new().max_height(), 0);
+
+    // Path compression only makes trees flatter.
+    for element in 0..len {
+        let _ = ds.root_of(element);
+    }
+    assert!(ds.max_height() <= 1);
+}
+
+#[test]
+fn to_dot_has_one_line_per_element() {
+    let mut ds = DisjointSet::with_len(10);
+    ds.join(2, 7);
+    ds.join(7, 9);
+
+    let dot = ds.to_dot();
+    assert!(dot.starts_with("digraph {\n"));
+    assert!(dot.ends_with("}\n"));
+    assert_eq!(dot.lines().count(), 12);
+    assert_eq!(dot.matches("->").count(), 2);
+    assert!(dot.contains(&format!("    7 -> {};", ds.parent_of(7))));
+}
+
+#[test]
+fn display_lists_sets() {
+    assert_eq!(
+        disjoint_set![[0, 3], [1], [2, 4]].to_string(),
+        "{0, 3}, {1}, {2, 4}"
+    );
+    assert_eq!(DisjointSet::with_len(1).to_string(), "{0}");
+    assert_eq!(DisjointSet::new().to_string(), "");
+}
+
+#[test]
+fn invariants_hold_after_operations() {
+    let mut ds = DisjointSet::new();
+    for step in 0..500 {
+        let element = ds.add_singleton();
+        ds.join(element, step * 37 % (element + 1));
+        ds.join(step % 7, element / 3);
+        let _ = ds.root_of(step * 11 % (element + 1));
+        assert_eq!(ds.check_invariants(), Ok(()));
+    }
+
+    let mut other = disjoint_set![[0, 2], [1]];
+    other.append(ds.clone());
+    assert_eq!(other.check_invariants(), Ok(()));
+    assert_eq!(ds.restrict(&[5, 3, 400]).check_invariants(), Ok(()));
+    assert_eq!(
+        ds.quotient(10, |element| element % 10).check_invariants(),
+        Ok(())
+    );
+
+    let mut bytes = Vec::new();
+    ds.write_to(&mut bytes).unwrap();
+    let read = DisjointSet::read_from(&bytes[..]).unwrap();
+    assert_eq!(read.check_invariants(), Ok(()));
+
+    ds.clear();
+    assert_eq!(ds.check_invariants(), Ok(()));
+}
+
+#[test]
+fn invariant_violation_messages() {
+    assert_eq!(
+        InvariantViolation::LengthMismatch {
+            parents: 3,
+            ranks: 2
+        }
+        .to_string(),
+        "there are 3 parents, but 2 ranks"
+    );
+    assert_eq!(
+        InvariantViolation::ParentOutOfBounds {
+            element: 1,
+            parent: 5,
+            len: 3
+        }
+        .to_string(),
+        "parent 5 of element 1 is out of bounds for 3 elements"
+    );
+    assert_eq!(
+        InvariantViolation::Cycle { element: 2 }.to_string(),
+        "element 2 is on a cycle of parent links"
+    );
+    assert_eq!(
+        InvariantViolation::RankNotIncreasing {
+            element: 0,
+            parent: 1
+        }
+        .to_string(),
+        "rank of element 0 is not smaller than the rank of its parent 1"
+    );
+}
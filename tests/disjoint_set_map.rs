@@ -0,0 +1,151 @@
+use std::hash::{Hash, Hasher};
+
+use disjoint::DisjointSetMap;
+
+fn verify_subsets(disjoint_set: &DisjointSetMap<String>, expected_subsets_ordered: &[Vec<&str>]) {
+    assert_eq!(
+        disjoint_set.len(),
+        expected_subsets_ordered
+            .iter()
+            .map(|subset| subset.len())
+            .sum()
+    );
+
+    for (subset_id, subset) in expected_subsets_ordered.iter().enumerate() {
+        for (other_subset_id, other_subset) in expected_subsets_ordered.iter().enumerate() {
+            for &member in subset {
+                assert!(disjoint_set.contains(member));
+                for &other_member in other_subset {
+                    assert_eq!(
+                        disjoint_set.is_joined(member, other_member),
+                        subset_id == other_subset_id
+                    );
+                    assert_eq!(
+                        disjoint_set.root_of(member) == disjoint_set.root_of(other_member),
+                        subset_id == other_subset_id
+                    );
+                }
+            }
+        }
+    }
+
+    let sets: Vec<Vec<&str>> = disjoint_set
+        .sets()
+        .into_iter()
+        .map(|set| set.into_iter().map(String::as_str).collect())
+        .collect();
+    assert_eq!(sets, expected_subsets_ordered)
+}
+
+#[test]
+fn join_inserts_unknown_keys() {
+    let mut dsm = DisjointSetMap::new();
+    assert!(dsm.join("a", "b"));
+    assert!(dsm.join("c", "a"));
+    assert!(!dsm.join("b", "c"));
+    assert_eq!(dsm.insert("d".to_owned()), 3);
+    assert!(!dsm.join("d", "d"));
+
+    verify_subsets(&dsm, &[vec!["a", "b", "c"], vec!["d"]]);
+}
+
+#[test]
+fn insert_is_idempotent() {
+    let mut dsm = DisjointSetMap::new();
+    assert_eq!(dsm.insert("x".to_owned()), 0);
+    dsm.join("x", "y");
+    assert_eq!(dsm.insert("x".to_owned()), 0);
+    assert_eq!(dsm.insert("y".to_owned()), 1);
+
+    verify_subsets(&dsm, &[vec!["x", "y"]]);
+}
+
+#[test]
+fn unknown_keys_behave_like_singletons() {
+    let mut dsm = DisjointSetMap::new();
+    dsm.join("a", "b");
+
+    assert!(dsm.is_joined("unknown", "unknown"));
+    assert!(!dsm.is_joined("a", "unknown"));
+    assert!(!dsm.is_joined("unknown", "other"));
+    assert_eq!(dsm.root_of("unknown"), None);
+    assert!(!dsm.contains("unknown"));
+}
+
+#[test]
+fn indices_follow_insertion_order() {
+    let mut dsm = DisjointSetMap::new();
+    dsm.join(&(1, 2), &(3, 4));
+    dsm.insert((5, 6));
+    dsm.join(&(5, 6), &(1, 2));
+
+    assert_eq!(dsm.keys(), [(1, 2), (3, 4), (5, 6)]);
+    assert_eq!(dsm.index_of(&(5, 6)), Some(2));
+    assert_eq!(dsm.indices().sets(), vec![vec![0, 1, 2]]);
+}
+
+#[test]
+fn equality_ignores_insertion_order() {
+    let mut first = DisjointSetMap::new();
+    first.join(&'a', &'b');
+    first.insert('c');
+
+    let mut second = DisjointSetMap::new();
+    second.insert('c');
+    second.join(&'b', &'a');
+
+    assert_eq!(first, second);
+
+    second.join(&'c', &'a');
+    assert_ne!(first, second);
+
+    let mut third = DisjointSetMap::new();
+    third.join(&'a', &'b');
+    third.insert('d');
+    assert_ne!(first, third);
+}
+
+#[test]
+fn clear_removes_all_keys() {
+    let mut dsm = DisjointSetMap::new();
+    dsm.join("a", "b");
+    dsm.clear();
+    verify_subsets(&dsm, &[]);
+
+    dsm.join("b", "c");
+    verify_subsets(&dsm, &[vec!["b", "c"]]);
+}
+
+/// A key that is not `Clone` and whose hash ignores its value, so all keys collide.
+#[derive(Debug, PartialEq, Eq)]
+struct Colliding(u32);
+
+impl Hash for Colliding {
+    fn hash<H>(&self, _: &mut H)
+    where
+        H: Hasher,
+    {
+    }
+}
+
+#[test]
+fn keys_need_not_be_clone_and_may_collide() {
+    let mut dsm = DisjointSetMap::new();
+    for value in 0..10 {
+        assert_eq!(dsm.insert(Colliding(value)), value as usize);
+    }
+    assert_eq!(dsm.insert(Colliding(3)), 3);
+    assert_eq!(dsm.len(), 10);
+
+    for value in 0..10 {
+        assert_eq!(dsm.index_of(&Colliding(value)), Some(value as usize));
+        assert_eq!(dsm.root_of(&Colliding(value)), Some(&Colliding(value)));
+    }
+    assert!(!dsm.contains(&Colliding(10)));
+    assert!(!dsm.is_joined(&Colliding(2), &Colliding(7)));
+    assert_eq!(dsm.keys()[7], Colliding(7));
+
+    dsm.clear();
+    assert_eq!(dsm.insert(Colliding(7)), 0);
+    assert!(!dsm.contains(&Colliding(2)));
+}
@@ -1,6 +1,11 @@
-use std::rc::Rc;
+use std::{
+    cmp::Ordering,
+    collections::{hash_map::DefaultHasher, HashSet},
+    hash::{Hash, Hasher},
+    rc::Rc,
+};
 
-use disjoint::{disjoint_set_vec, DisjointSet, DisjointSetVec};
+use disjoint::{disjoint_set_vec, DisjointSet, DisjointSetVec, MemberTracking};
 
 fn verify_subsets<T>(disjoint_set: &DisjointSetVec<T>, expected_subsets: &[Vec<usize>]) {
     assert_eq!(
@@ -674,3 +679,159 @@ fn from_slice_constructor() {
 
     assert_eq!(dsv, expected);
 }
+
+#[test]
+fn size_of_and_num_sets_track_joins() {
+    let mut dsv = disjoint_set_vec!['a', 'b', 'c', 'd'];
+    assert_eq!(dsv.num_sets(), 4);
+
+    dsv.join(0, 3);
+    dsv.join(3, 1);
+    assert_eq!(dsv.size_of(1), 3);
+    assert_eq!(dsv.size_of(2), 1);
+    assert_eq!(dsv.num_sets(), 2);
+
+    dsv.push('e');
+    assert_eq!(dsv.num_sets(), 3);
+    assert_eq!(dsv.size_of(4), 1);
+}
+
+#[test]
+fn members_yield_values_of_set() {
+    let mut dsv =
+        DisjointSetVec::with_values_and_tracking(vec![10, 20, 30, 40, 50], MemberTracking);
+    dsv.join(4, 1);
+    dsv.join(1, 2);
+
+    let mut members: Vec<_> = dsv.members(2).cloned().collect();
+    assert_eq!(members[0], 30);
+    members.sort();
+    assert_eq!(members, vec![20, 30, 50]);
+    assert_eq!(dsv.members(3).collect::<Vec<_>>(), vec![&40]);
+}
+
+#[test]
+fn collect_creates_singletons() {
+    let dsv: DisjointSetVec<_> = (0..5).map(|value| value * value).collect();
+    assert_eq!(*dsv.values(), [0, 1, 4, 9, 16]);
+    assert_eq!(*dsv.indices(), DisjointSet::with_len(5));
+}
+
+#[test]
+fn extend_adds_singletons() {
+    let mut dsv = disjoint_set_vec!['a', 'b', 'c'];
+    dsv.join(0, 2);
+    dsv.extend("de".chars());
+
+    assert_eq!(*dsv.values(), ['a', 'b', 'c', 'd', 'e']);
+    assert_eq!(
+        dsv.indices().sets(),
+        vec![vec![0, 2], vec![1], vec![3], vec![4]]
+    );
+}
+
+fn hash_of<T: Hash>(value: &T) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[test]
+// The interior mutability only compresses paths, which does not change the hash.
+#[allow(clippy::mutable_key_type)]
+fn hash_is_consistent_with_eq() {
+    let mut l = disjoint_set_vec![1, 2, 3, 4];
+    l.join(0, 3);
+    l.join(3, 2);
+    let mut r = disjoint_set_vec![1, 2, 3, 4];
+    r.join(2, 0);
+    r.join(3, 2);
+
+    assert_eq!(l, r);
+    assert_eq!(hash_of(&l), hash_of(&r));
+
+    let mut different_values = r.clone();
+    different_values[1] = 5;
+    let states: HashSet<_> = vec![l, r, different_values].into_iter().collect();
+    assert_eq!(states.len(), 2);
+}
+
+#[test]
+fn partial_cmp_requires_equal_values() {
+    let mut fine = disjoint_set_vec!['a', 'b', 'c'];
+    fine.join(0, 2);
+    let mut coarse = fine.clone();
+    coarse.join(1, 2);
+
+    assert_eq!(fine.partial_cmp(&coarse), Some(Ordering::Less));
+    assert_eq!(coarse.partial_cmp(&fine), Some(Ordering::Greater));
+    assert_eq!(fine.partial_cmp(&fine), Some(Ordering::Equal));
+
+    let mut renamed = coarse.clone();
+    renamed[0] = 'z';
+    assert_eq!(fine.partial_cmp(&renamed), None);
+}
+
+#[test]
+fn append_moves_values() {
+    let mut dsv = disjoint_set_vec![String::from("a"), String::from("b")];
+    dsv.join(0, 1);
+    let mut other = disjoint_set_vec![String::from("c"), String::from("d"), String::from("e")];
+    other.join(2, 1);
+
+    assert_eq!(dsv.append(other), 2);
+    assert_eq!(dsv.values().concat(), "abcde");
+    assert_eq!(dsv.indices().sets(), vec![vec![0, 1], vec![2], vec![3, 4]]);
+
+    assert_eq!(dsv.push(String::from("f")), 5);
+}
+
+#[test]
+fn restrict_and_permute_carry_values() {
+    let mut dsv = disjoint_set_vec![10, 20, 30, 40];
+    dsv.join(1, 3);
+
+    let restricted = dsv.restrict(&[3, 0, 1]);
+    assert_eq!(*restricted.values(), [40, 10, 20]);
+    assert_eq!(restricted.indices().sets(), vec![vec![0, 2], vec![1]]);
+
+    let permuted = dsv.permute(&[1, 2, 3, 0]);
+    assert_eq!(*permuted.values(), [40, 10, 20, 30]);
+    assert_eq!(
+        permuted.indices().sets(),
+        vec![vec![0, 2], vec![1], vec![3]]
+    );
+}
+
+#[test]
+fn quotient_collects_values() {
+    let mut dsv = disjoint_set_vec!['a', 'b', 'c', 'd', 'e'];
+    dsv.join(0, 4);
+
+    let quotient = dsv.quotient(4, |index| index / 2);
+    assert_eq!(
+        *quotient.values(),
+        [vec!['a', 'b'], vec!['c', 'd'], vec!['e'], vec![]]
+    );
+    assert_eq!(
+        quotient.indices().sets(),
+        vec![vec![0, 2], vec![1], vec![3]]
+    );
+}
+
+#[test]
+fn display_lists_sets_of_values() {
+    let mut dsv = disjoint_set_vec!["x", "y", "z"];
+    dsv.join(0, 2);
+    assert_eq!(dsv.to_string(), "{x, z}, {y}");
+    assert_eq!(DisjointSetVec::<u8>::new().to_string(), "");
+}
+
+#[test]
+fn to_dot_escapes_labels() {
+    let dsv = disjoint_set_vec!["say \"hi\"", "back\\slash\nnewline"];
+    assert_eq!(
+        dsv.to_dot(),
+        "graph {\n    subgraph cluster_0 {\n        0 [label=\"say \\\"hi\\\"\"];\n    }\n    subgraph cluster_1 {\n        1 [label=\"back\\\\slash\\nnewline\"];\n    }\n}\n"
+    );
+}
@@ -0,0 +1,116 @@
+use disjoint::{DisjointSet, DisjointSetWith, Merge};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Aggregate {
+    sum: i64,
+    min: i64,
+    count: usize,
+}
+
+impl Aggregate {
+    fn single(value: i64) -> Self {
+        Aggregate {
+            sum: value,
+            min: value,
+            count: 1,
+        }
+    }
+}
+
+impl Merge for Aggregate {
+    fn merge(&mut self, other: Self) {
+        self.sum += other.sum;
+        self.min = self.min.min(other.min);
+        self.count += other.count;
+    }
+}
+
+#[test]
+fn aggregates_match_sets() {
+    let len = 200;
+    let values: Vec<i64> = (0..len)
+        .map(|value| (value * 37 % 101) as i64 - 50)
+        .collect();
+    let mut dsw: DisjointSetWith<_> = values
+        .iter()
+        .cloned()
+        .map(Aggregate::single)
+        .collect::<Vec<_>>()
+        .into();
+    let mut reference = DisjointSet::with_len(len);
+
+    for step in 0..len / 2 {
+        let first = (step * 17) % len;
+        let second = (step * 29 + 3) % len;
+        assert_eq!(dsw.join(first, second), reference.join(first, second));
+    }
+
+    assert_eq!(dsw.num_sets(), reference.num_sets());
+    for (set, aggregate) in dsw.sets() {
+        let expected = Aggregate {
+            sum: set.iter().map(|&element| values[element]).sum(),
+            min: set.iter().map(|&element| values[element]).min().unwrap(),
+            count: set.len(),
+        };
+        assert_eq!(*aggregate, expected);
+        for &element in &set {
+            assert_eq!(*dsw.data_of(element), expected);
+        }
+    }
+    assert_eq!(dsw.indices().sets(), reference.sets());
+}
+
+#[test]
+fn join_merges_second_into_first() {
+    let mut dsw = DisjointSetWith::from(vec![vec!['a'], vec!['b'], vec!['c'], vec!['d']]);
+
+    // Regardless of which root survives internally, the value of the first set comes first.
+    assert!(dsw.join(1, 0));
+    assert!(dsw.join(3, 2));
+    assert!(dsw.join(2, 0));
+    assert_eq!(dsw.data_of(3), &vec!['d', 'c', 'b', 'a']);
+    assert!(!dsw.join(0, 3));
+    assert_eq!(dsw.data_of(1), &vec!['d', 'c', 'b', 'a']);
+}
+
+#[test]
+fn join_by_uses_custom_merge() {
+    let mut dsw = DisjointSetWith::from(vec![5, 3, 8]);
+
+    assert!(dsw.join_by(0, 1, |max, other| *max = (*max).max(other)));
+    assert_eq!(*dsw.data_of(1), 5);
+
+    assert!(!dsw.join_by(1, 0, |_, _| panic!("sets are already joined")));
+
+    *dsw.data_of_mut(0) = 10;
+    assert!(dsw.join_by(2, 1, |max, other| *max = (*max).max(other)));
+    assert_eq!(*dsw.data_of(2), 10);
+}
+
+#[test]
+fn push_adds_singleton() {
+    let mut dsw = DisjointSetWith::new();
+    assert!(dsw.is_empty());
+    assert_eq!(dsw.push(Aggregate::single(4)), 0);
+    assert_eq!(dsw.push(Aggregate::single(-1)), 1);
+    assert!(!dsw.is_joined(0, 1));
+
+    dsw.join(0, 1);
+    assert_eq!(dsw.root_of(0), dsw.root_of(1));
+    assert_eq!(
+        *dsw.data_of(1),
+        Aggregate {
+            sum: 3,
+            min: -1,
+            count: 2
+        }
+    );
+    assert_eq!(dsw.len(), 2);
+}
+
+#[test]
+#[should_panic]
+fn panic_data_of_element_oob() {
+    let dsw = DisjointSetWith::from(vec![1, 2]);
+    let _ = dsw.data_of(2);
+}
@@ -0,0 +1,154 @@
+use std::io::ErrorKind;
+
+use disjoint::{
+    ByIndex, ByRank, CompactDisjointSet, DisjointSet, ElementIndex, Journal, MemberTracking,
+    NoCompression, NoObserver, PathSplitting,
+};
+
+type TrackedCompactDisjointSet<I> =
+    DisjointSet<NoObserver, PathSplitting, ByRank, I, MemberTracking>;
+
+fn tracked_with_len<I>(len: usize) -> TrackedCompactDisjointSet<I>
+where
+    I: ElementIndex,
+{
+    DisjointSet::try_with_len_observer_strategies_and_tracking(
+        len,
+        NoObserver,
+        PathSplitting,
+        ByRank,
+        MemberTracking,
+    )
+    .unwrap()
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Vertex(u32);
+
+impl ElementIndex for Vertex {
+    const MAX: usize = <u32 as ElementIndex>::MAX;
+
+    fn from_usize(index: usize) -> Self {
+        Self(u32::from_usize(index))
+    }
+
+    fn to_usize(self) -> usize {
+        self.0.to_usize()
+    }
+}
+
+fn agrees_with_default<I>()
+where
+    I: ElementIndex,
+{
+    let len = 200;
+    let mut ds = CompactDisjointSet::<I>::try_with_len(len).unwrap();
+    let mut reference = DisjointSet::with_len(len);
+
+    for element in 0..len {
+        let (first, second) = (element * 13 % len, (element * 7 + 3) % len);
+        assert_eq!(ds.join(first, second), reference.join(first, second));
+    }
+    assert_eq!(ds.check_invariants(), Ok(()));
+    for element in 0..len {
+        assert_eq!(ds.size_of(element), reference.size_of(element));
+    }
+    assert_eq!(ds.num_sets(), reference.num_sets());
+    assert_eq!(ds.sets(), reference.sets());
+    assert_eq!(ds, reference);
+}
+
+#[test]
+fn all_index_types_agree_with_default() {
+    agrees_with_default::<u16>();
+    agrees_with_default::<u32>();
+    agrees_with_default::<u64>();
+    agrees_with_default::<usize>();
+    agrees_with_default::<Vertex>();
+}
+
+#[test]
+fn max_of_index_types() {
+    assert_eq!(<u16 as ElementIndex>::MAX, 65_535);
+    assert_eq!(<u32 as ElementIndex>::MAX, 4_294_967_295);
+    assert_eq!(<usize as ElementIndex>::MAX, !0);
+    assert_eq!(<u64 as ElementIndex>::MAX, !0);
+}
+
+#[test]
+fn try_with_len_reports_overflow() {
+    let error = CompactDisjointSet::<u16>::try_with_len(65_536).unwrap_err();
+    assert_eq!(error.max_len(), 65_535);
+    assert_eq!(
+        error.to_string(),
+        "the index type can hold at most 65535 elements"
+    );
+    assert!(CompactDisjointSet::<u16>::try_with_len(65_535).is_ok());
+}
+
+#[test]
+fn try_add_singleton_reports_overflow() {
+    let mut ds = CompactDisjointSet::<u16>::try_with_len(65_535).unwrap();
+    ds.join(0, 65_534);
+    assert_eq!(ds.try_add_singleton().unwrap_err().max_len(), 65_535);
+    assert_eq!(ds.len(), 65_535);
+    assert!(ds.is_joined(0, 65_534));
+}
+
+#[test]
+#[should_panic]
+fn panic_add_singleton_overflow() {
+    let mut ds = CompactDisjointSet::<u16>::try_with_len(65_535).unwrap();
+    ds.add_singleton();
+}
+
+#[test]
+#[should_panic]
+fn panic_append_overflow() {
+    let mut ds = CompactDisjointSet::<u16>::try_with_len(40_000).unwrap();
+    ds.append(CompactDisjointSet::<u16>::try_with_len(30_000).unwrap());
+}
+
+#[test]
+fn replay_reports_overflow() {
+    let mut journal = Journal::new(Vec::new()).unwrap();
+    for _ in 0..65_536 {
+        journal.add_singleton().unwrap();
+    }
+    let (_, bytes) = journal.into_parts();
+
+    let mut ds = CompactDisjointSet::<u16>::try_with_len(0).unwrap();
+    let error = ds.replay(&bytes[..]).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::InvalidData);
+    assert_eq!(ds.len(), 65_535);
+}
+
+#[test]
+fn compact_index_combines_with_strategies() {
+    let mut roots = Vec::new();
+    {
+        let mut ds: DisjointSet<_, _, _, u16> = DisjointSet::try_with_len_observer_and_strategies(
+            4,
+            |root, _, _| roots.push(root),
+            NoCompression,
+            ByIndex,
+        )
+        .unwrap();
+        ds.join(3, 2);
+        ds.join(2, 1);
+        assert_eq!(ds.root_of(3), 1);
+        assert_eq!(ds.depth_of(3), 2);
+    }
+    assert_eq!(roots, vec![2, 1]);
+}
+
+#[test]
+fn compact_index_can_grow_and_append() {
+    let mut ds = tracked_with_len::<u32>(2);
+    ds.extend(vec![(0, 3), (4, 1)]);
+    let offset = ds.append(tracked_with_len::<u32>(2));
+    assert_eq!(offset, 5);
+    ds.join(5, 6);
+    assert_eq!(ds.sets(), vec![vec![0, 3], vec![1, 4], vec![2], vec![5, 6]]);
+    assert_eq!(ds.members(6).count(), 2);
+}
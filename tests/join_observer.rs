@@ -0,0 +1,108 @@
+use std::mem;
+
+use disjoint::{disjoint_set, DisjointSet, DisjointSetVec, JoinObserver, NoObserver};
+
+#[derive(Debug, Default, Clone)]
+struct Recorder {
+    joins: Vec<(usize, usize, usize)>,
+}
+
+impl JoinObserver for Recorder {
+    fn on_join(&mut self, root: usize, absorbed_root: usize, size: usize) {
+        self.joins.push((root, absorbed_root, size));
+    }
+}
+
+#[test]
+fn observer_sees_each_successful_join() {
+    let len = 100;
+    let mut ds = DisjointSet::with_len_and_observer(len, Recorder::default());
+    let mut reference = DisjointSet::with_len(len);
+
+    for element in 0..len {
+        let other = (element * 13 + 5) % len;
+        let roots = (ds.root_of(element), ds.root_of(other));
+        let number_joins = ds.observer().joins.len();
+
+        assert_eq!(ds.join(element, other), reference.join(element, other));
+
+        if roots.0 == roots.1 {
+            assert_eq!(ds.observer().joins.len(), number_joins);
+        } else {
+            let (root, absorbed_root, size) = *ds.observer().joins.last().unwrap();
+            assert!(roots == (root, absorbed_root) || roots == (absorbed_root, root));
+            assert_eq!(ds.root_of(element), root);
+            assert_eq!(size, ds.size_of(element));
+        }
+    }
+
+    assert_eq!(ds, reference);
+    assert_eq!(ds.observer().joins.len(), len - ds.num_sets());
+}
+
+#[test]
+fn observer_sees_bulk_joins() {
+    let mut ds = DisjointSet::with_len_and_observer(4, Recorder::default());
+    ds.union_with(&disjoint_set![[0, 2], [1], [3]]);
+    ds.extend(vec![(1, 3), (4, 0)]);
+
+    let sizes: Vec<_> = ds.observer().joins.iter().map(|join| join.2).collect();
+    assert_eq!(sizes, vec![2, 2, 3]);
+    assert_eq!(ds.len(), 5);
+}
+
+#[test]
+fn closure_observer() {
+    let mut absorbed_roots = Vec::new();
+    {
+        let mut ds = DisjointSet::with_len_and_observer(3, |_, absorbed_root, _| {
+            absorbed_roots.push(absorbed_root);
+        });
+        ds.join(1, 2);
+        ds.add_singleton();
+        ds.join(3, 1);
+    }
+    assert_eq!(absorbed_roots, vec![2, 3]);
+}
+
+#[test]
+fn no_observer_is_free() {
+    assert_eq!(mem::size_of::<NoObserver>(), 0);
+    assert_eq!(
+        mem::size_of::<DisjointSet>(),
+        mem::size_of::<DisjointSet<()>>()
+    );
+}
+
+#[test]
+fn disjoint_set_vec_observer() {
+    let mut dsv =
+        DisjointSetVec::with_values_and_observer(vec!['a', 'b', 'c'], Recorder::default());
+    dsv.push('d');
+    dsv.join(3, 0);
+    dsv.join(0, 3);
+    dsv.join(1, 2);
+    assert_eq!(dsv.indices().observer().joins.len(), 2);
+
+    dsv.observer_mut().joins.clear();
+
+    // Reading the indices keeps the observer.
+    let mut bytes = Vec::new();
+    DisjointSet::with_len(4).write_to(&mut bytes).unwrap();
+    dsv.read_indices_from(&bytes[..]).unwrap();
+    assert_eq!(dsv.num_sets(), 4);
+    dsv.join(2, 0);
+    assert_eq!(dsv.indices().observer().joins, vec![(2, 0, 2)]);
+}
+
+#[test]
+fn append_keeps_own_observer() {
+    let mut ds = DisjointSet::with_len_and_observer(2, Recorder::default());
+    let mut other = DisjointSet::with_len_and_observer(2, Recorder::default());
+    other.join(0, 1);
+
+    assert_eq!(ds.append(other), 2);
+    assert!(ds.observer().joins.is_empty());
+    ds.join(0, 3);
+    assert_eq!(ds.observer().joins, vec![(2, 0, 3)]);
+}
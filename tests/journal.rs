@@ -0,0 +1,174 @@
+use std::io::{self, ErrorKind, Write};
+
+use disjoint::{DisjointSet, Journal};
+
+/// Writes a journal of some operations, returning the bytes and the partition after each operation.
+fn journal_with_states() -> (Vec<u8>, Vec<DisjointSet>) {
+    let mut journal = Journal::new(Vec::new()).unwrap();
+    let mut states = vec![journal.disjoint_set().clone()];
+    for element in 0..30 {
+        journal.add_singleton().unwrap();
+        states.push(journal.disjoint_set().clone());
+        if journal.join(element, element * 7 % (element + 1)).unwrap() {
+            states.push(journal.disjoint_set().clone());
+        }
+    }
+    (journal.into_parts().1, states)
+}
+
+#[test]
+fn replay_rebuilds_partition() {
+    let (bytes, states) = journal_with_states();
+
+    let mut ds = DisjointSet::new();
+    assert_eq!(
+        ds.replay(&bytes[..]).unwrap(),
+        (states.len() - 1, bytes.len() as u64)
+    );
+    assert_eq!(&ds, states.last().unwrap());
+}
+
+#[test]
+fn replay_is_idempotent() {
+    let (bytes, states) = journal_with_states();
+    let expected = states.last().unwrap();
+
+    let mut ds = DisjointSet::new();
+    ds.replay(&bytes[..]).unwrap();
+    ds.replay(&bytes[..]).unwrap();
+    assert_eq!(&ds, expected);
+
+    // Replaying onto a snapshot taken while writing the journal.
+    for state in &states {
+        let mut ds = state.clone();
+        ds.replay(&bytes[..]).unwrap();
+        assert_eq!(&ds, expected);
+    }
+}
+
+#[test]
+fn replay_ignores_truncated_last_record() {
+    let (bytes, states) = journal_with_states();
+
+    for len in 0..bytes.len() {
+        let mut ds = DisjointSet::new();
+        let (number_records, valid_len) = ds.replay(&bytes[..len]).unwrap();
+        assert_eq!(ds, states[number_records]);
+
+        // The valid prefix holds exactly the records read.
+        let mut prefix = DisjointSet::new();
+        let prefix_bytes = &bytes[..valid_len as usize];
+        assert_eq!(
+            prefix.replay(prefix_bytes).unwrap(),
+            (number_records, valid_len)
+        );
+        assert_eq!(prefix, ds);
+    }
+}
+
+#[test]
+fn resume_appends_to_journal() {
+    let mut journal = Journal::new(Vec::new()).unwrap();
+    journal.add_singleton().unwrap();
+    journal.add_singleton().unwrap();
+    let (_, mut bytes) = journal.into_parts();
+
+    let mut ds = DisjointSet::new();
+    ds.replay(&bytes[..]).unwrap();
+    let mut journal = Journal::resume(ds, &mut bytes);
+    assert_eq!(journal.add_singleton().unwrap(), 2);
+    assert!(journal.join(2, 0).unwrap());
+    assert!(!journal.join(0, 2).unwrap());
+    let (ds, _) = journal.into_parts();
+
+    let mut mirror = DisjointSet::new();
+    assert_eq!(mirror.replay(&bytes[..]).unwrap().0, 4);
+    assert_eq!(mirror, ds);
+}
+
+#[test]
+fn resume_after_crash_truncates_partial_record() {
+    let (mut bytes, states) = journal_with_states();
+    let last = states.last().unwrap();
+
+    // A crash cut off the last record.
+    bytes.truncate(bytes.len() - 2);
+
+    let mut ds = DisjointSet::new();
+    let (number_records, valid_len) = ds.replay(&bytes[..]).unwrap();
+    assert_eq!(ds, states[number_records]);
+    assert_ne!(&ds, last);
+
+    bytes.truncate(valid_len as usize);
+    let mut journal = Journal::resume(ds, &mut bytes);
+    let first_element = journal.add_singleton().unwrap();
+    let second_element = journal.add_singleton().unwrap();
+    assert!(journal.join(first_element, second_element).unwrap());
+    assert!(journal.join(second_element, 0).unwrap());
+    let (ds, _) = journal.into_parts();
+
+    let mut mirror = DisjointSet::new();
+    assert_eq!(
+        mirror.replay(&bytes[..]).unwrap(),
+        (number_records + 4, bytes.len() as u64)
+    );
+    assert_eq!(mirror, ds);
+}
+
+#[test]
+fn replay_empty_input() {
+    let mut ds = DisjointSet::with_len(2);
+    assert_eq!(ds.replay(io::empty()).unwrap(), (0, 0));
+    assert_eq!(ds, DisjointSet::with_len(2));
+}
+
+#[test]
+fn replay_detects_corruption() {
+    let (mut bytes, _) = journal_with_states();
+    let last = bytes.len() - 1;
+    bytes[last] ^= 1;
+    let error = DisjointSet::new().replay(&bytes[..]).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::InvalidData);
+
+    let mut bytes = Vec::new();
+    DisjointSet::with_len(3).write_to(&mut bytes).unwrap();
+    let error = DisjointSet::new().replay(&bytes[..]).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::InvalidData);
+}
+
+#[test]
+fn replay_detects_missing_elements() {
+    let (_, bytes) = Journal::new(Vec::new()).unwrap().into_parts();
+    let mut journal = Journal::resume(DisjointSet::with_len(2), bytes);
+    journal.join(0, 1).unwrap();
+    let (_, bytes) = journal.into_parts();
+
+    let mut ds = DisjointSet::with_len(1);
+    let error = ds.replay(&bytes[..]).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::InvalidData);
+    assert_eq!(ds.replay(&bytes[..0]).unwrap(), (0, 0));
+}
+
+struct FailingWriter;
+
+impl Write for FailingWriter {
+    // `io::Error::other` is not available on old Rust versions.
+    #[allow(clippy::io_other_error)]
+    fn write(&mut self, _: &[u8]) -> io::Result<usize> {
+        Err(io::Error::new(ErrorKind::Other, "disk full"))
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn failed_write_does_not_apply_operation() {
+    assert!(Journal::new(FailingWriter).is_err());
+
+    let mut journal = Journal::resume(DisjointSet::with_len(2), FailingWriter);
+    assert!(journal.add_singleton().is_err());
+    assert!(journal.join(0, 1).is_err());
+    assert_eq!(journal.disjoint_set(), &DisjointSet::with_len(2));
+}
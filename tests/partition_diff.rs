@@ -0,0 +1,86 @@
+use disjoint::{disjoint_set, DisjointSet};
+
+#[test]
+fn equal_partitions_have_empty_diff() {
+    let mut old = DisjointSet::with_len(5);
+    old.join(0, 3);
+    let mut new = DisjointSet::with_len(5);
+    new.join(3, 0);
+
+    let diff = DisjointSet::diff(&old, &new);
+    assert!(diff.is_empty());
+    assert_eq!(diff.merges().count(), 0);
+    assert!(diff.moved().is_empty());
+    assert_eq!(diff.added(), 5..5);
+    assert_eq!(diff.to_string(), "no changes");
+}
+
+#[test]
+fn merges_follow_joins() {
+    let old = disjoint_set![[0, 5], [1], [2, 6], [3], [4]];
+    let mut new = old.clone();
+    new.join(6, 1);
+    new.join(1, 3);
+    new.join(4, 0);
+
+    let diff = DisjointSet::diff(&old, &new);
+    let merges: Vec<_> = diff
+        .merges()
+        .map(|merge| (merge.sources().to_vec(), merge.target().to_vec()))
+        .collect();
+    assert_eq!(
+        merges,
+        vec![
+            (vec![vec![0, 5], vec![4]], vec![0, 4, 5]),
+            (vec![vec![1], vec![2, 6], vec![3]], vec![1, 2, 3, 6]),
+        ]
+    );
+    assert!(diff.moved().is_empty());
+    assert!(!diff.is_empty());
+    assert_eq!(
+        diff.to_string(),
+        "merged {0, 5} and {4} into {0, 4, 5}\nmerged {1}, {2, 6} and {3} into {1, 2, 3, 6}"
+    );
+}
+
+#[test]
+fn added_elements_are_singleton_sources() {
+    let old = disjoint_set![[0, 1], [2]];
+    let mut new = old.clone();
+    new.add_singleton();
+    new.add_singleton();
+    new.join(4, 0);
+
+    let diff = DisjointSet::diff(&old, &new);
+    let merge = diff.merges().next().unwrap();
+    assert_eq!(merge.sources(), &[vec![0, 1], vec![4]]);
+    assert_eq!(merge.target(), &[0, 1, 4]);
+    assert_eq!(diff.merges().count(), 1);
+    assert_eq!(diff.added(), 3..5);
+    assert_eq!(
+        diff.to_string(),
+        "merged {0, 1} and {4} into {0, 1, 4}\nadded 3, 4"
+    );
+}
+
+#[test]
+fn split_sets_report_moved_elements() {
+    let old = disjoint_set![[0, 2, 4], [1, 3]];
+    let new = disjoint_set![[0, 1, 3], [2], [4]];
+
+    let diff = DisjointSet::diff(&old, &new);
+    assert_eq!(diff.moved(), &[2, 4]);
+
+    let merge = diff.merges().next().unwrap();
+    assert_eq!(merge.sources(), &[vec![0], vec![1, 3]]);
+    assert_eq!(
+        diff.to_string(),
+        "merged {0} and {1, 3} into {0, 1, 3}\nmoved 2, 4"
+    );
+}
+
+#[test]
+#[should_panic(expected = "new partition has fewer elements than old partition")]
+fn panic_new_shorter_than_old() {
+    let _ = DisjointSet::diff(&DisjointSet::with_len(3), &DisjointSet::with_len(2));
+}
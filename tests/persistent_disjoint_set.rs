@@ -0,0 +1,95 @@
+use disjoint::{DisjointSet, PersistentDisjointSet};
+
+fn verify_subsets(disjoint_set: &PersistentDisjointSet, expected_subsets_ordered: &[Vec<usize>]) {
+    assert_eq!(
+        disjoint_set.len(),
+        expected_subsets_ordered
+            .iter()
+            .map(|subset| subset.len())
+            .sum()
+    );
+
+    for (subset_id, subset) in expected_subsets_ordered.iter().enumerate() {
+        for (other_subset_id, other_subset) in expected_subsets_ordered.iter().enumerate() {
+            for &member in subset {
+                for &other_member in other_subset {
+                    assert_eq!(
+                        disjoint_set.is_joined(member, other_member),
+                        subset_id == other_subset_id
+                    )
+                }
+            }
+        }
+    }
+
+    assert_eq!(disjoint_set.sets(), expected_subsets_ordered)
+}
+
+#[test]
+fn constructor_prodcues_singletons() {
+    let disjoint_set = PersistentDisjointSet::with_len(5);
+
+    let expected_subsets = [vec![0], vec![1], vec![2], vec![3], vec![4]];
+    verify_subsets(&disjoint_set, &expected_subsets);
+}
+
+#[test]
+fn join_leaves_old_version_untouched() {
+    let old = PersistentDisjointSet::with_len(4).join(0, 1);
+    let new = old.join(2, 3).join(1, 2);
+
+    verify_subsets(&old, &[vec![0, 1], vec![2], vec![3]]);
+    verify_subsets(&new, &[vec![0, 1, 2, 3]]);
+}
+
+#[test]
+fn join_of_joined_elements_is_equal() {
+    let ds = PersistentDisjointSet::with_len(3).join(0, 2);
+    assert_eq!(ds.join(2, 0), ds);
+}
+
+#[test]
+fn add_singleton_leaves_old_version_untouched() {
+    let old = PersistentDisjointSet::with_len(2).join(0, 1);
+    let new = old.add_singleton().add_singleton().join(2, 0);
+
+    verify_subsets(&old, &[vec![0, 1]]);
+    verify_subsets(&new, &[vec![0, 1, 2], vec![3]]);
+}
+
+#[test]
+fn many_versions_behave_like_disjoint_sets() {
+    let len = 2000;
+    let mut versions = vec![PersistentDisjointSet::with_len(len)];
+    let mut references = vec![DisjointSet::with_len(len)];
+
+    for step in 0..len {
+        let first = (step * 7) % len;
+        let second = (step * 13 + 5) % len;
+
+        // Branch off from an older version every few steps.
+        let base = step / 3;
+        let mut reference = references[base].clone();
+        reference.join(first, second);
+        versions.push(versions[base].join(first, second));
+        references.push(reference);
+    }
+
+    for (version, reference) in versions.iter().zip(&references).step_by(97) {
+        assert_eq!(version.sets(), reference.sets());
+    }
+}
+
+#[test]
+#[should_panic]
+fn panic_join_element_oob() {
+    let ds = PersistentDisjointSet::with_len(100);
+    let _ = ds.join(0, 100);
+}
+
+#[test]
+#[should_panic]
+fn panic_is_joined_element_oob() {
+    let ds = PersistentDisjointSet::new();
+    let _ = ds.is_joined(0, 0);
+}
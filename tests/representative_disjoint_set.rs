@@ -0,0 +1,105 @@
+use disjoint::{
+    ByPriority, DisjointSet, FirstJoined, MaxIndex, MinIndex, RepresentativeDisjointSet,
+    RepresentativePolicy,
+};
+
+fn joins(len: usize) -> Vec<(usize, usize)> {
+    (0..len)
+        .map(|step| ((step * 17 + 4) % len, (step * 41) % len))
+        .collect()
+}
+
+fn verify_representatives<P, F>(ds: &RepresentativeDisjointSet<P>, expected_representative: F)
+where
+    P: RepresentativePolicy,
+    F: Fn(&[usize]) -> usize,
+{
+    for set in ds.sets() {
+        let representative = expected_representative(&set);
+        for &element in &set {
+            assert_eq!(ds.root_of(element), representative);
+        }
+    }
+}
+
+#[test]
+fn min_and_max_index_do_not_depend_on_join_order() {
+    let len = 300;
+    let mut min = RepresentativeDisjointSet::<MinIndex>::with_len(len);
+    let mut max = RepresentativeDisjointSet::<MaxIndex>::with_len(len);
+    let mut reference = DisjointSet::with_len(len);
+
+    for (first, second) in joins(len) {
+        let newly_joined = reference.join(first, second);
+        assert_eq!(min.join(first, second), newly_joined);
+        assert_eq!(max.join(second, first), newly_joined);
+    }
+
+    assert_eq!(min.sets(), reference.sets());
+    assert_eq!(max.sets(), reference.sets());
+    verify_representatives(&min, |set| *set.iter().min().unwrap());
+    verify_representatives(&max, |set| *set.iter().max().unwrap());
+}
+
+#[test]
+fn first_joined_keeps_first_argument() {
+    let mut ds = RepresentativeDisjointSet::<FirstJoined>::with_len(6);
+
+    // Grow a set by repeatedly joining new elements into it.
+    for &element in &[4, 1, 5, 0] {
+        ds.join(3, element);
+    }
+    assert_eq!(ds.root_of(0), 3);
+
+    // Joining the set into a singleton makes the singleton the representative.
+    ds.join(2, 4);
+    verify_representatives(&ds, |_| 2);
+}
+
+#[test]
+fn by_priority_prefers_highest_priority() {
+    let priorities = [5, 1, 9, 9, 3, 7];
+    let mut ds = RepresentativeDisjointSet::with_len_and_policy(
+        priorities.len(),
+        ByPriority(|element| priorities[element]),
+    );
+
+    ds.join(0, 1);
+    ds.join(4, 5);
+    verify_representatives(&ds, |set| match set {
+        [0, 1] => 0,
+        [4, 5] => 5,
+        _ => set[0],
+    });
+
+    // Ties keep the representative of the first argument.
+    ds.join(3, 2);
+    assert_eq!(ds.root_of(2), 3);
+
+    ds.join(1, 5);
+    ds.join(0, 2);
+    verify_representatives(&ds, |_| 3);
+}
+
+#[test]
+fn add_singleton_is_own_representative() {
+    let mut ds = RepresentativeDisjointSet::<MaxIndex>::new();
+    assert!(ds.is_empty());
+    assert_eq!(ds.add_singleton(), 0);
+    assert_eq!(ds.add_singleton(), 1);
+    assert_eq!(ds.root_of(1), 1);
+
+    ds.join(1, 0);
+    assert_eq!(ds.root_of(0), 1);
+    assert_eq!(ds.add_singleton(), 2);
+    ds.join(0, 2);
+    assert_eq!(ds.root_of(1), 2);
+    assert_eq!(ds.len(), 3);
+}
+
+#[test]
+#[should_panic]
+fn panic_root_of_element_oob() {
+    let ds = RepresentativeDisjointSet::<MinIndex>::with_len(2);
+    let _ = ds.root_of(2);
+}
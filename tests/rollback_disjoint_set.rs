@@ -0,0 +1,205 @@
+use disjoint::{DisjointSet, RollbackDisjointSet};
+
+fn verify_subsets(disjoint_set: &RollbackDisjointSet, expected_subsets_ordered: &[Vec<usize>]) {
+    assert_eq!(
+        disjoint_set.len(),
+        expected_subsets_ordered
+            .iter()
+            .map(|subset| subset.len())
+            .sum()
+    );
+
+    for (subset_id, subset) in expected_subsets_ordered.iter().enumerate() {
+        for (other_subset_id, other_subset) in expected_subsets_ordered.iter().enumerate() {
+            for &member in subset {
+                for &other_member in other_subset {
+                    assert_eq!(
+                        disjoint_set.is_joined(member, other_member),
+                        subset_id == other_subset_id
+                    )
+                }
+            }
+        }
+    }
+
+    assert_eq!(disjoint_set.sets(), expected_subsets_ordered)
+}
+
+#[test]
+fn behaves_like_disjoint_set_without_snapshots() {
+    let mut rollback = RollbackDisjointSet::with_len(10);
+    let mut reference = DisjointSet::with_len(10);
+
+    for &(first, second) in &[(0, 4), (2, 7), (4, 2), (9, 9), (7, 0), (1, 8), (8, 3)] {
+        assert_eq!(rollback.join(first, second), reference.join(first, second));
+    }
+    assert_eq!(rollback.add_singleton(), reference.add_singleton());
+
+    verify_subsets(&rollback, &reference.sets());
+}
+
+#[test]
+fn rollback_undoes_joins() {
+    let mut ds = RollbackDisjointSet::with_len(5);
+    ds.join(0, 1);
+
+    let snapshot = ds.snapshot();
+    ds.join(2, 3);
+    ds.join(1, 3);
+    ds.join(4, 0);
+    verify_subsets(&ds, &[vec![0, 1, 2, 3, 4]]);
+
+    ds.rollback_to(snapshot);
+    verify_subsets(&ds, &[vec![0, 1], vec![2], vec![3], vec![4]]);
+}
+
+#[test]
+fn rollback_undoes_add_singleton() {
+    let mut ds = RollbackDisjointSet::with_len(2);
+
+    let snapshot = ds.snapshot();
+    assert_eq!(ds.add_singleton(), 2);
+    assert_eq!(ds.add_singleton(), 3);
+    ds.join(3, 0);
+    ds.join(2, 1);
+
+    ds.rollback_to(snapshot);
+    verify_subsets(&ds, &[vec![0], vec![1]]);
+    assert_eq!(ds.add_singleton(), 2);
+}
+
+#[test]
+fn nested_snapshots_roll_back_independently() {
+    let mut ds = RollbackDisjointSet::with_len(4);
+
+    let outer = ds.snapshot();
+    ds.join(0, 1);
+
+    let inner = ds.snapshot();
+    ds.join(2, 3);
+    ds.join(1, 2);
+    ds.rollback_to(inner);
+    verify_subsets(&ds, &[vec![0, 1], vec![2], vec![3]]);
+
+    let inner = ds.snapshot();
+    ds.join(3, 1);
+    ds.commit(inner);
+    verify_subsets(&ds, &[vec![0, 1, 3], vec![2]]);
+
+    ds.rollback_to(outer);
+    verify_subsets(&ds, &[vec![0], vec![1], vec![2], vec![3]]);
+}
+
+#[test]
+fn commit_keeps_modifications() {
+    let mut ds = RollbackDisjointSet::with_len(3);
+
+    let snapshot = ds.snapshot();
+    ds.join(0, 2);
+    ds.add_singleton();
+    ds.commit(snapshot);
+    verify_subsets(&ds, &[vec![0, 2], vec![1], vec![3]]);
+
+    let snapshot = ds.snapshot();
+    ds.join(1, 3);
+    ds.rollback_to(snapshot);
+    verify_subsets(&ds, &[vec![0, 2], vec![1], vec![3]]);
+}
+
+#[test]
+fn ranks_are_restored_by_rollback() {
+    let mut ds = RollbackDisjointSet::with_len(8);
+    ds.join(0, 1);
+
+    // Joining equal ranks increases the rank of the new root, which has to be undone.
+    let snapshot = ds.snapshot();
+    ds.join(2, 3);
+    ds.join(0, 2);
+    ds.rollback_to(snapshot);
+
+    let mut reference = DisjointSet::with_len(8);
+    reference.join(0, 1);
+    for &(first, second) in &[(4, 5), (6, 7), (4, 6), (2, 4), (0, 3)] {
+        ds.join(first, second);
+        reference.join(first, second);
+    }
+    verify_subsets(&ds, &reference.sets());
+}
+
+#[test]
+#[should_panic]
+fn rollback_to_snapshot_of_other_disjoint_set_panics() {
+    let mut ds = RollbackDisjointSet::with_len(3);
+    let snapshot = RollbackDisjointSet::with_len(3).snapshot();
+    ds.rollback_to(snapshot);
+}
+
+#[test]
+fn clear_resets_undo_log_and_snapshots() {
+    let mut ds = RollbackDisjointSet::with_len(3);
+    let _discarded = ds.snapshot();
+    ds.join(0, 1);
+    ds.clear();
+    verify_subsets(&ds, &[]);
+
+    for _ in 0..3 {
+        ds.add_singleton();
+    }
+    ds.join(0, 1);
+    let snapshot = ds.snapshot();
+    ds.join(1, 2);
+    ds.rollback_to(snapshot);
+    verify_subsets(&ds, &[vec![0, 1], vec![2]]);
+}
+
+#[test]
+#[should_panic(expected = "a more recent snapshot has not been consumed yet")]
+fn rollback_to_outer_snapshot_with_open_inner_snapshot_panics() {
+    let mut ds = RollbackDisjointSet::with_len(3);
+    let outer = ds.snapshot();
+    ds.join(0, 1);
+    let _inner = ds.snapshot();
+    ds.join(1, 2);
+    ds.rollback_to(outer);
+}
+
+#[test]
+#[should_panic(expected = "a more recent snapshot has not been consumed yet")]
+fn commit_outer_snapshot_with_open_inner_snapshot_panics() {
+    let mut ds = RollbackDisjointSet::with_len(3);
+    let outer = ds.snapshot();
+    let _inner = ds.snapshot();
+    ds.commit(outer);
+}
+
+#[test]
+#[should_panic]
+fn rollback_to_snapshot_discarded_by_clear_panics() {
+    let mut ds = RollbackDisjointSet::with_len(3);
+    let snapshot = ds.snapshot();
+    ds.clear();
+    ds.add_singleton();
+    ds.rollback_to(snapshot);
+}
+
+#[test]
+#[should_panic]
+fn panic_join_element_oob() {
+    let mut ds = RollbackDisjointSet::with_len(100);
+    ds.join(0, 100);
+}
+
+#[test]
+fn equal_if_same_sets() {
+    let mut first = RollbackDisjointSet::with_len(4);
+    let mut second = RollbackDisjointSet::with_len(4);
+
+    first.join(0, 1);
+    first.join(1, 2);
+    second.join(2, 0);
+    second.join(1, 0);
+    assert_eq!(first, second);
+
+    second.join(3, 0);
+    assert_ne!(first, second);
+}
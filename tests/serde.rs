@@ -0,0 +1,335 @@
+#![cfg(feature = "serde")]
+
+//! Round trips through a minimal in-memory data format, to avoid depending on a serialization crate.
+
+use disjoint::{disjoint_set_vec, DisjointSet, DisjointSetVec};
+use serde::{
+    de::{
+        value::{Error, MapDeserializer, SeqDeserializer},
+        Deserialize, Deserializer, IntoDeserializer, Visitor,
+    },
+    forward_to_deserialize_any,
+    ser::{Impossible, Serialize, SerializeSeq, SerializeStruct, Serializer},
+};
+
+#[derive(Debug, Clone, PartialEq)]
+enum Value {
+    U64(u64),
+    Char(char),
+    Seq(Vec<Value>),
+    Struct(Vec<(&'static str, Value)>),
+}
+
+fn seq(values: Vec<Value>) -> Value {
+    Value::Seq(values)
+}
+
+fn sets(sets: &[&[u64]]) -> Value {
+    seq(sets
+        .iter()
+        .map(|set| seq(set.iter().map(|&element| Value::U64(element)).collect()))
+        .collect())
+}
+
+struct ValueSerializer;
+
+struct SeqSerializer(Vec<Value>);
+
+struct StructSerializer(Vec<(&'static str, Value)>);
+
+fn unsupported<T>() -> Result<T, Error> {
+    Err(serde::ser::Error::custom("unsupported"))
+}
+
+impl Serializer for ValueSerializer {
+    type Ok = Value;
+    type Error = Error;
+    type SerializeSeq = SeqSerializer;
+    type SerializeTuple = Impossible<Value, Error>;
+    type SerializeTupleStruct = Impossible<Value, Error>;
+    type SerializeTupleVariant = Impossible<Value, Error>;
+    type SerializeMap = Impossible<Value, Error>;
+    type SerializeStruct = StructSerializer;
+    type SerializeStructVariant = Impossible<Value, Error>;
+
+    fn serialize_u64(self, v: u64) -> Result<Value, Error> {
+        Ok(Value::U64(v))
+    }
+    fn serialize_char(self, v: char) -> Result<Value, Error> {
+        Ok(Value::Char(v))
+    }
+    fn serialize_seq(self, len: Option<usize>) -> Result<SeqSerializer, Error> {
+        Ok(SeqSerializer(Vec::with_capacity(len.unwrap_or(0))))
+    }
+    fn serialize_struct(self, _: &'static str, len: usize) -> Result<StructSerializer, Error> {
+        Ok(StructSerializer(Vec::with_capacity(len)))
+    }
+
+    fn serialize_bool(self, _: bool) -> Result<Value, Error> {
+        unsupported()
+    }
+    fn serialize_i8(self, _: i8) -> Result<Value, Error> {
+        unsupported()
+    }
+    fn serialize_i16(self, _: i16) -> Result<Value, Error> {
+        unsupported()
+    }
+    fn serialize_i32(self, _: i32) -> Result<Value, Error> {
+        unsupported()
+    }
+    fn serialize_i64(self, _: i64) -> Result<Value, Error> {
+        unsupported()
+    }
+    fn serialize_u8(self, v: u8) -> Result<Value, Error> {
+        self.serialize_u64(v.into())
+    }
+    fn serialize_u16(self, v: u16) -> Result<Value, Error> {
+        self.serialize_u64(v.into())
+    }
+    fn serialize_u32(self, v: u32) -> Result<Value, Error> {
+        self.serialize_u64(v.into())
+    }
+    fn serialize_f32(self, _: f32) -> Result<Value, Error> {
+        unsupported()
+    }
+    fn serialize_f64(self, _: f64) -> Result<Value, Error> {
+        unsupported()
+    }
+    fn serialize_str(self, _: &str) -> Result<Value, Error> {
+        unsupported()
+    }
+    fn serialize_bytes(self, _: &[u8]) -> Result<Value, Error> {
+        unsupported()
+    }
+    fn serialize_none(self) -> Result<Value, Error> {
+        unsupported()
+    }
+    fn serialize_some<T: ?Sized + Serialize>(self, _: &T) -> Result<Value, Error> {
+        unsupported()
+    }
+    fn serialize_unit(self) -> Result<Value, Error> {
+        unsupported()
+    }
+    fn serialize_unit_struct(self, _: &'static str) -> Result<Value, Error> {
+        unsupported()
+    }
+    fn serialize_unit_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+    ) -> Result<Value, Error> {
+        unsupported()
+    }
+    fn serialize_newtype_struct<T: ?Sized + Serialize>(
+        self,
+        _: &'static str,
+        _: &T,
+    ) -> Result<Value, Error> {
+        unsupported()
+    }
+    fn serialize_newtype_variant<T: ?Sized + Serialize>(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: &T,
+    ) -> Result<Value, Error> {
+        unsupported()
+    }
+    fn serialize_tuple(self, _: usize) -> Result<Self::SerializeTuple, Error> {
+        unsupported()
+    }
+    fn serialize_tuple_struct(
+        self,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeTupleStruct, Error> {
+        unsupported()
+    }
+    fn serialize_tuple_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeTupleVariant, Error> {
+        unsupported()
+    }
+    fn serialize_map(self, _: Option<usize>) -> Result<Self::SerializeMap, Error> {
+        unsupported()
+    }
+    fn serialize_struct_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeStructVariant, Error> {
+        unsupported()
+    }
+}
+
+impl SerializeSeq for SeqSerializer {
+    type Ok = Value;
+    type Error = Error;
+
+    fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Error> {
+        self.0.push(value.serialize(ValueSerializer)?);
+        Ok(())
+    }
+    fn end(self) -> Result<Value, Error> {
+        Ok(Value::Seq(self.0))
+    }
+}
+
+impl SerializeStruct for StructSerializer {
+    type Ok = Value;
+    type Error = Error;
+
+    fn serialize_field<T: ?Sized + Serialize>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        self.0.push((key, value.serialize(ValueSerializer)?));
+        Ok(())
+    }
+    fn end(self) -> Result<Value, Error> {
+        Ok(Value::Struct(self.0))
+    }
+}
+
+impl<'de> Deserializer<'de> for Value {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self {
+            Value::U64(v) => visitor.visit_u64(v),
+            Value::Char(v) => visitor.visit_char(v),
+            Value::Seq(values) => visitor.visit_seq(SeqDeserializer::new(values.into_iter())),
+            Value::Struct(fields) => visitor.visit_map(MapDeserializer::new(fields.into_iter())),
+        }
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct enum identifier ignored_any
+    }
+}
+
+impl<'de> IntoDeserializer<'de, Error> for Value {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
+
+fn to_value<T: Serialize>(value: &T) -> Value {
+    value.serialize(ValueSerializer).unwrap()
+}
+
+fn from_value<'de, T: Deserialize<'de>>(value: Value) -> Result<T, String> {
+    T::deserialize(value).map_err(|error| error.to_string())
+}
+
+#[test]
+fn disjoint_set_serializes_as_sets() {
+    let mut ds = DisjointSet::with_len(5);
+    ds.join(4, 1);
+    ds.join(1, 2);
+    assert_eq!(to_value(&ds), sets(&[&[0], &[1, 2, 4], &[3]]));
+
+    // The representation does not depend on the join order.
+    let mut other = DisjointSet::with_len(5);
+    other.join(2, 4);
+    other.join(2, 1);
+    assert_eq!(to_value(&other), to_value(&ds));
+
+    assert_eq!(to_value(&DisjointSet::new()), sets(&[]));
+}
+
+#[test]
+fn disjoint_set_round_trips() {
+    let mut ds = DisjointSet::with_len(50);
+    for element in 0..50 {
+        ds.join(element, (element * element) % 17);
+    }
+
+    let deserialized: DisjointSet = from_value(to_value(&ds)).unwrap();
+    assert_eq!(deserialized, ds);
+    assert_eq!(deserialized.sets(), ds.sets());
+}
+
+#[test]
+fn disjoint_set_accepts_any_set_order() {
+    let ds: DisjointSet = from_value(sets(&[&[3, 1], &[2], &[0, 4]])).unwrap();
+    assert_eq!(ds.sets(), vec![vec![0, 4], vec![1, 3], vec![2]]);
+}
+
+#[test]
+fn disjoint_set_rejects_malformed_sets() {
+    let errors = [
+        (sets(&[&[0, 1], &[]]), "sets must not be empty"),
+        (
+            sets(&[&[0, 1], &[3]]),
+            "element 3 is out of bounds for 3 elements",
+        ),
+        (sets(&[&[0, 1], &[1]]), "element 1 appears more than once"),
+        (sets(&[&[0, 2, 2, 3]]), "element 2 appears more than once"),
+        (
+            seq(vec![Value::U64(0)]),
+            "invalid type: integer `0`, expected a sequence",
+        ),
+    ];
+
+    for (value, expected_error) in errors.iter().cloned() {
+        assert_eq!(
+            from_value::<DisjointSet>(value).unwrap_err(),
+            expected_error
+        );
+    }
+}
+
+#[test]
+fn disjoint_set_vec_serializes_values_and_sets() {
+    let mut dsv = disjoint_set_vec!['a', 'b', 'c'];
+    dsv.join(2, 0);
+
+    let value = to_value(&dsv);
+    assert_eq!(
+        value,
+        Value::Struct(vec![
+            (
+                "values",
+                seq(vec![Value::Char('a'), Value::Char('b'), Value::Char('c')])
+            ),
+            ("sets", sets(&[&[0, 2], &[1]])),
+        ])
+    );
+
+    let deserialized: DisjointSetVec<char> = from_value(value).unwrap();
+    assert_eq!(deserialized, dsv);
+}
+
+#[test]
+fn disjoint_set_vec_rejects_sets_not_matching_values() {
+    let values = seq(vec![Value::Char('a'), Value::Char('b')]);
+    let with_sets = |sets| Value::Struct(vec![("values", values.clone()), ("sets", sets)]);
+
+    assert_eq!(
+        from_value::<DisjointSetVec<char>>(with_sets(sets(&[&[0, 1, 2]]))).unwrap_err(),
+        "element 2 is out of bounds for 2 elements"
+    );
+    assert_eq!(
+        from_value::<DisjointSetVec<char>>(with_sets(sets(&[&[1]]))).unwrap_err(),
+        "element 0 is missing"
+    );
+    assert_eq!(
+        from_value::<DisjointSetVec<char>>(Value::Struct(vec![("values", values.clone())]))
+            .unwrap_err(),
+        "missing field `sets`"
+    );
+}
@@ -0,0 +1,160 @@
+use std::{cell::Cell, fmt::Debug, mem};
+
+use disjoint::{
+    ByIndex, ByRank, BySize, DisjointSet, FindStrategy, FullCompression, LinkStrategy,
+    NoCompression, NoObserver, PathHalving, PathSplitting, RandomizedIndex,
+};
+
+fn joins(len: usize) -> impl Iterator<Item = (usize, usize)> {
+    (0..len).map(move |element| (element * 13 % len, (element * 7 + 3) % len))
+}
+
+fn agrees_with_default<F, L>(find: F, link: L)
+where
+    F: FindStrategy + Debug,
+    L: LinkStrategy + Debug,
+{
+    let len = 300;
+    let mut ds = DisjointSet::with_len_and_strategies(len, find, link);
+    let mut reference = DisjointSet::with_len(len);
+
+    for (first, second) in joins(len) {
+        assert_eq!(ds.join(first, second), reference.join(first, second));
+        assert_eq!(ds.check_invariants(), Ok(()));
+    }
+    for element in 0..len {
+        assert_eq!(ds.size_of(element), reference.size_of(element));
+    }
+    assert_eq!(ds.num_sets(), reference.num_sets());
+    assert_eq!(ds, reference);
+}
+
+#[test]
+fn all_strategies_agree_with_default() {
+    agrees_with_default(FullCompression, ByRank);
+    agrees_with_default(FullCompression, BySize);
+    agrees_with_default(FullCompression, ByIndex);
+    agrees_with_default(FullCompression, RandomizedIndex::new());
+    agrees_with_default(PathHalving, ByRank);
+    agrees_with_default(PathHalving, BySize);
+    agrees_with_default(PathHalving, ByIndex);
+    agrees_with_default(PathHalving, RandomizedIndex::new());
+    agrees_with_default(PathSplitting, ByRank);
+    agrees_with_default(PathSplitting, BySize);
+    agrees_with_default(PathSplitting, ByIndex);
+    agrees_with_default(PathSplitting, RandomizedIndex::new());
+    agrees_with_default(NoCompression, ByRank);
+    agrees_with_default(NoCompression, BySize);
+    agrees_with_default(NoCompression, ByIndex);
+    agrees_with_default(NoCompression, RandomizedIndex::new());
+}
+
+/// Returns the parents of a chain where the parent of each element is the previous one.
+fn chain(len: usize) -> Vec<Cell<usize>> {
+    (0..len)
+        .map(|element| Cell::new(element.saturating_sub(1)))
+        .collect()
+}
+
+fn parents_after_find<F>(find: F) -> Vec<usize>
+where
+    F: FindStrategy,
+{
+    let parents = chain(7);
+    assert_eq!(find.find(&parents, 6), 0);
+    parents.into_iter().map(Cell::into_inner).collect()
+}
+
+#[test]
+fn find_strategies_compress_paths() {
+    assert_eq!(parents_after_find(FullCompression), [0, 0, 0, 0, 0, 0, 0]);
+    assert_eq!(parents_after_find(PathHalving), [0, 0, 0, 2, 2, 4, 4]);
+    assert_eq!(parents_after_find(PathSplitting), [0, 0, 0, 1, 2, 3, 4]);
+    assert_eq!(parents_after_find(NoCompression), [0, 0, 1, 2, 3, 4, 5]);
+}
+
+#[test]
+fn no_compression_keeps_forest() {
+    let mut ds = DisjointSet::with_len_and_strategies(5, NoCompression, ByIndex);
+    for element in (1..5).rev() {
+        ds.join(element - 1, element);
+    }
+    let parents: Vec<_> = (0..5).map(|element| ds.parent_of(element)).collect();
+    for element in 0..5 {
+        assert_eq!(ds.root_of(element), 0);
+    }
+    assert!((0..5).all(|element| ds.parent_of(element) == parents[element]));
+}
+
+#[test]
+fn by_index_roots_are_smallest_elements() {
+    let mut ds = DisjointSet::with_len_and_strategies(100, PathSplitting, ByIndex);
+    ds.extend(joins(100));
+    for set in ds.sets() {
+        for &element in &set {
+            assert_eq!(ds.root_of(element), set[0]);
+        }
+    }
+}
+
+#[test]
+fn by_size_keeps_trees_flat() {
+    let len = 1 << 10;
+    let mut ds = DisjointSet::with_len_and_strategies(len, NoCompression, BySize);
+    let mut width = 1;
+    while width < len {
+        for start in (0..len).step_by(2 * width) {
+            ds.join(start + width, start);
+        }
+        width *= 2;
+    }
+    assert_eq!(ds.num_sets(), 1);
+    assert!(ds.max_height() <= 10);
+}
+
+#[test]
+fn strategies_combine_with_observer() {
+    let mut sizes = Vec::new();
+    {
+        let mut ds = DisjointSet::with_len_observer_and_strategies(
+            4,
+            |_, _, size| sizes.push(size),
+            PathHalving,
+            RandomizedIndex::with_seed(5),
+        );
+        ds.join(0, 1);
+        ds.join(2, 3);
+        ds.join(3, 0);
+        assert_eq!(*ds.find_strategy(), PathHalving);
+        assert_eq!(*ds.link_strategy(), RandomizedIndex::with_seed(5));
+    }
+    assert_eq!(sizes, vec![2, 2, 4]);
+}
+
+#[test]
+fn strategies_are_free() {
+    assert_eq!(
+        mem::size_of::<DisjointSet>(),
+        mem::size_of::<DisjointSet<NoObserver, FullCompression, ByIndex>>()
+    );
+}
+
+#[test]
+fn randomized_index_depends_on_seed() {
+    let root_for_seed = |seed| {
+        let mut ds = DisjointSet::with_len_and_strategies(
+            2,
+            PathSplitting,
+            RandomizedIndex::with_seed(seed),
+        );
+        ds.join(0, 1);
+        ds.root_of(0)
+    };
+
+    let roots: Vec<_> = (0..64).map(root_for_seed).collect();
+    assert_eq!(roots, (0..64).map(root_for_seed).collect::<Vec<_>>());
+    assert!(roots.contains(&0));
+    assert!(roots.contains(&1));
+
+    assert_ne!(RandomizedIndex::new().seed(), RandomizedIndex::new().seed());
+}
@@ -0,0 +1,142 @@
+use disjoint::{DisjointSet, Group, WeightedDisjointSet};
+
+/// Permutations of three elements, the smallest non-commutative group.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Permutation([usize; 3]);
+
+impl Group for Permutation {
+    fn identity() -> Self {
+        Permutation([0, 1, 2])
+    }
+
+    fn combine(self, other: Self) -> Self {
+        Permutation([self.0[other.0[0]], self.0[other.0[1]], self.0[other.0[2]]])
+    }
+
+    fn inverse(self) -> Self {
+        let mut result = [0; 3];
+        for (index, &image) in self.0.iter().enumerate() {
+            result[image] = index;
+        }
+        Permutation(result)
+    }
+}
+
+#[test]
+fn diffs_are_derived_transitively() {
+    let mut wds = WeightedDisjointSet::with_len(5);
+    assert_eq!(wds.join_with(0, 1, 3_i64), Ok(true));
+    assert_eq!(wds.join_with(2, 3, -2), Ok(true));
+    assert_eq!(wds.join_with(3, 1, 10), Ok(true));
+
+    // x1 = x0 + 3, x3 = x2 - 2, x1 = x3 + 10.
+    assert_eq!(wds.diff(0, 1), Some(3));
+    assert_eq!(wds.diff(2, 3), Some(-2));
+    assert_eq!(wds.diff(0, 2), Some(-5));
+    assert_eq!(wds.diff(2, 0), Some(5));
+    assert_eq!(wds.diff(0, 4), None);
+    assert_eq!(wds.sets(), vec![vec![0, 1, 2, 3], vec![4]]);
+}
+
+#[test]
+fn contradiction_leaves_set_unchanged() {
+    let mut wds = WeightedDisjointSet::with_len(3);
+    wds.join_with(0, 1, 1_i32).unwrap();
+    wds.join_with(1, 2, 1).unwrap();
+
+    let contradiction = wds.join_with(2, 0, 3).unwrap_err();
+    assert_eq!(*contradiction.existing_diff(), -2);
+    assert_eq!(
+        contradiction.to_string(),
+        "difference contradicts existing difference -2"
+    );
+
+    assert_eq!(wds.join_with(2, 0, -2), Ok(false));
+    assert_eq!(wds.diff(0, 2), Some(2));
+}
+
+#[test]
+fn parity_detects_odd_cycles() {
+    let mut wds = WeightedDisjointSet::with_len(6);
+
+    // An even cycle is bipartite.
+    for &(first, second) in &[(0, 1), (1, 2), (2, 3)] {
+        assert_eq!(wds.join_with(first, second, true), Ok(true));
+    }
+    assert_eq!(wds.join_with(3, 0, true), Ok(false));
+
+    // An odd cycle is not.
+    wds.join_with(4, 5, true).unwrap();
+    wds.join_with(5, 0, true).unwrap();
+    assert!(wds.join_with(4, 0, true).is_err());
+    assert_eq!(wds.diff(4, 2), Some(false));
+}
+
+#[test]
+fn non_commutative_group_respects_order() {
+    let swap_first = Permutation([1, 0, 2]);
+    let swap_last = Permutation([0, 2, 1]);
+
+    let mut wds = WeightedDisjointSet::with_len(4);
+    wds.join_with(0, 1, swap_first).unwrap();
+    wds.join_with(2, 3, swap_last).unwrap();
+    wds.join_with(1, 2, swap_first).unwrap();
+
+    // x3 = swap_last * swap_first * swap_first * x0 = swap_last * x0.
+    assert_eq!(wds.diff(0, 3), Some(swap_last));
+    assert_eq!(wds.diff(3, 0), Some(swap_last.inverse()));
+    assert_eq!(wds.diff(1, 3), Some(swap_last.combine(swap_first)));
+    assert_ne!(wds.diff(1, 3), Some(swap_first.combine(swap_last)));
+    assert_eq!(wds.diff(0, 2), Some(swap_first.combine(swap_first)));
+}
+
+#[test]
+fn long_chains_behave_like_disjoint_sets() {
+    let len = 1000;
+    let mut wds = WeightedDisjointSet::with_len(len);
+    let mut reference = DisjointSet::with_len(len);
+
+    // Element i has potential 3 * i within each residue class modulo 7.
+    for step in 0..len {
+        let first = (step * 13) % len;
+        let second = (step * 31 + 7) % len;
+        if first % 7 == second % 7 {
+            let diff = 3 * (second as i64 - first as i64);
+            let newly_joined = reference.join(first, second);
+            assert_eq!(wds.join_with(first, second, diff), Ok(newly_joined));
+        }
+    }
+
+    assert_eq!(wds.sets(), reference.sets());
+    for first in (0..len).step_by(17) {
+        for second in (0..len).step_by(23) {
+            let expected = if reference.is_joined(first, second) {
+                Some(3 * (second as i64 - first as i64))
+            } else {
+                None
+            };
+            assert_eq!(wds.diff(first, second), expected);
+        }
+    }
+}
+
+#[test]
+fn add_singleton_is_unjoined() {
+    let mut wds = WeightedDisjointSet::new();
+    assert!(wds.is_empty());
+    assert_eq!(wds.add_singleton(), 0);
+    assert_eq!(wds.add_singleton(), 1);
+    assert_eq!(wds.diff(0, 1), None);
+    assert_eq!(wds.diff(1, 1), Some(0_u8));
+
+    wds.join_with(1, 0, 200).unwrap();
+    assert_eq!(wds.diff(0, 1), Some(56));
+    assert_eq!(wds.len(), 2);
+}
+
+#[test]
+#[should_panic]
+fn panic_join_with_element_oob() {
+    let mut wds = WeightedDisjointSet::with_len(3);
+    let _ = wds.join_with(0, 3, 1);
+}